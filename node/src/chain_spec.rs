@@ -1,12 +1,14 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, PalletAbacConfig,
+	PalletDidConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
+use serde::Deserialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{bytes::from_hex, sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::{fs::File, path::Path};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -36,8 +38,122 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+/// Identities, delegates, access control attributes and endorsements provisioned at genesis.
+///
+/// In the JSON file, accounts are SS58 addresses, delegate types and names are text and
+/// values are text or `0x`-prefixed hex for binary ones such as public keys.
+#[derive(Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields, default)]
+pub struct IdentityGenesis {
+	/// `[identity, owner]` of identities owned by another account.
+	pub owners: Vec<(AccountId, AccountId)>,
+	/// `[identity, delegate type, delegate]` of `pallet_did` delegates.
+	pub delegates: Vec<(AccountId, String, AccountId)>,
+	/// `[identity, name, value]` of `pallet_did` attributes.
+	pub attributes: Vec<(AccountId, String, String)>,
+	/// `[identity, name, value]` of `pallet_abac` attributes.
+	pub abac_attributes: Vec<(AccountId, String, String)>,
+	/// `[endorser, identity, name]` of `pallet_abac` endorsements.
+	pub endorsements: Vec<(AccountId, AccountId, String)>,
+}
+
+impl IdentityGenesis {
+	/// Loads the lists from a JSON file, checking that its values decode.
+	pub fn from_json_file(path: &Path) -> Result<Self, String> {
+		let file = File::open(path)
+			.map_err(|e| format!("Error opening identity file `{}`: {}", path.display(), e))?;
+		let identities: Self = serde_json::from_reader(file)
+			.map_err(|e| format!("Error parsing identity file `{}`: {}", path.display(), e))?;
+		identities.did_config()?;
+		identities.abac_config()?;
+		Ok(identities)
+	}
+
+	/// Checks that the accounts paying the deposits of the delegates and attributes of
+	/// `pallet_did`, the owners of their identities, are among `endowed_accounts`.
+	/// The deposits are reserved at genesis, which cannot be built otherwise.
+	pub fn check_endowed(&self, endowed_accounts: &[AccountId]) -> Result<(), String> {
+		let owner = |identity: &AccountId| {
+			self.owners
+				.iter()
+				.rev()
+				.find(|(owned, _)| owned == identity)
+				.map_or_else(|| identity.clone(), |(_, owner)| owner.clone())
+		};
+		let identities = self
+			.delegates
+			.iter()
+			.map(|(identity, ..)| identity)
+			.chain(self.attributes.iter().map(|(identity, ..)| identity));
+		for identity in identities {
+			let owner = owner(identity);
+			if !endowed_accounts.contains(&owner) {
+				return Err(format!(
+					"Owner `{}` of identity `{}` is not endowed to pay its deposits",
+					owner, identity
+				))
+			}
+		}
+		Ok(())
+	}
+
+	fn did_config(&self) -> Result<PalletDidConfig, String> {
+		Ok(PalletDidConfig {
+			owners: self.owners.clone(),
+			delegates: self
+				.delegates
+				.iter()
+				.map(|(identity, delegate_type, delegate)| {
+					(identity.clone(), delegate_type.clone().into_bytes(), delegate.clone())
+				})
+				.collect(),
+			attributes: self
+				.attributes
+				.iter()
+				.map(|(identity, name, value)| {
+					Ok((identity.clone(), name.clone().into_bytes(), value_bytes(value)?))
+				})
+				.collect::<Result<_, String>>()?,
+		})
+	}
+
+	fn abac_config(&self) -> Result<PalletAbacConfig, String> {
+		Ok(PalletAbacConfig {
+			attributes: self
+				.abac_attributes
+				.iter()
+				.map(|(identity, name, value)| {
+					Ok((identity.clone(), name.clone().into_bytes(), value_bytes(value)?))
+				})
+				.collect::<Result<_, String>>()?,
+			endorsements: self
+				.endorsements
+				.iter()
+				.map(|(endorser, identity, name)| {
+					(endorser.clone(), identity.clone(), name.clone().into_bytes())
+				})
+				.collect(),
+		})
+	}
+}
+
+/// Decodes an attribute value, `0x`-prefixed hex or text.
+fn value_bytes(value: &str) -> Result<Vec<u8>, String> {
+	if value.starts_with("0x") {
+		from_hex(value).map_err(|e| format!("Invalid hex value `{}`: {}", value, e))
+	} else {
+		Ok(value.as_bytes().to_vec())
+	}
+}
+
+/// Development chain, with the identities of `identities` if given.
+pub fn development_config(identities: Option<&Path>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let endowed_accounts = testnet_endowed_accounts();
+	let identities = identities.map(IdentityGenesis::from_json_file).transpose()?;
+	if let Some(identities) = &identities {
+		identities.check_endowed(&endowed_accounts)?;
+	}
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -53,20 +169,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				endowed_accounts.clone(),
+				identities.clone().unwrap_or_default(),
 				true,
 			)
 		},
@@ -84,8 +188,14 @@ pub fn development_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+/// Local testnet, with the identities of `identities` if given.
+pub fn local_testnet_config(identities: Option<&Path>) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	let endowed_accounts = testnet_endowed_accounts();
+	let identities = identities.map(IdentityGenesis::from_json_file).transpose()?;
+	if let Some(identities) = &identities {
+		identities.check_endowed(&endowed_accounts)?;
+	}
 
 	Ok(ChainSpec::from_genesis(
 		// Name
@@ -101,20 +211,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				endowed_accounts.clone(),
+				identities.clone().unwrap_or_default(),
 				true,
 			)
 		},
@@ -132,12 +230,31 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
+/// Pre-funded accounts of the development chain and the local testnet.
+fn testnet_endowed_accounts() -> Vec<AccountId> {
+	vec![
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+		get_account_id_from_seed::<sr25519::Public>("Dave"),
+		get_account_id_from_seed::<sr25519::Public>("Eve"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie"),
+		get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
+		get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
+	]
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	identities: IdentityGenesis,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -160,5 +277,123 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		pallet_abac: identities.abac_config().expect("identity file is checked on load; qed"),
+		pallet_did: identities.did_config().expect("identity file is checked on load; qed"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::crypto::Ss58Codec;
+
+	fn account(seed: &str) -> AccountId {
+		get_account_id_from_seed::<sr25519::Public>(seed)
+	}
+
+	/// Writes `json` to a file of the temporary directory and loads it.
+	fn load(name: &str, json: &str) -> Result<IdentityGenesis, String> {
+		let path = std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()));
+		std::fs::write(&path, json).unwrap();
+		let identities = IdentityGenesis::from_json_file(&path);
+		std::fs::remove_file(&path).unwrap();
+		identities
+	}
+
+	#[test]
+	fn identity_file_is_loaded_into_the_pallet_configs() {
+		let (alice, bob, charlie) = (account("Alice"), account("Bob"), account("Charlie"));
+		let json = format!(
+			r#"{{
+				"owners": [["{alice}", "{bob}"]],
+				"delegates": [["{alice}", "veriKey", "{charlie}"]],
+				"attributes": [["{alice}", "name", "alice"], ["{alice}", "key", "0x0102"]],
+				"abacAttributes": [["{alice}", "role", "admin"]],
+				"endorsements": [["{charlie}", "{alice}", "role"]]
+			}}"#,
+			alice = alice.to_ss58check(),
+			bob = bob.to_ss58check(),
+			charlie = charlie.to_ss58check(),
+		);
+		let identities = load("identities", &json).unwrap();
+
+		let did = identities.did_config().unwrap();
+		assert_eq!(did.owners, vec![(alice.clone(), bob)]);
+		assert_eq!(did.delegates, vec![(alice.clone(), b"veriKey".to_vec(), charlie.clone())]);
+		assert_eq!(
+			did.attributes,
+			vec![
+				(alice.clone(), b"name".to_vec(), b"alice".to_vec()),
+				(alice.clone(), b"key".to_vec(), vec![1, 2]),
+			]
+		);
+
+		let abac = identities.abac_config().unwrap();
+		assert_eq!(abac.attributes, vec![(alice.clone(), b"role".to_vec(), b"admin".to_vec())]);
+		assert_eq!(abac.endorsements, vec![(charlie, alice, b"role".to_vec())]);
+	}
+
+	#[test]
+	fn lists_missing_from_the_identity_file_are_empty() {
+		let identities = load("empty-identities", "{}").unwrap();
+		assert!(identities.did_config().unwrap().owners.is_empty());
+		assert!(identities.abac_config().unwrap().attributes.is_empty());
+	}
+
+	#[test]
+	fn identity_file_with_unknown_fields_or_invalid_values_is_rejected() {
+		let error = load("unknown-identities", r#"{ "identities": [] }"#).err().unwrap();
+		assert!(error.contains("Error parsing identity file"));
+
+		let json = format!(r#"{{ "attributes": [["{}", "key", "0xzz"]] }}"#, account("Alice"));
+		let error = load("invalid-identities", &json).err().unwrap();
+		assert!(error.contains("Invalid hex value `0xzz`"));
+
+		let error = IdentityGenesis::from_json_file(Path::new("/nonexistent/identities.json"))
+			.err()
+			.unwrap();
+		assert!(error.contains("Error opening identity file"));
+	}
+
+	#[test]
+	fn values_are_hex_with_a_prefix_or_text() {
+		assert_eq!(value_bytes("0x0aff").unwrap(), vec![0x0a, 0xff]);
+		assert_eq!(value_bytes("0x").unwrap(), Vec::<u8>::new());
+		assert_eq!(value_bytes("0aff").unwrap(), b"0aff".to_vec());
+		assert_eq!(value_bytes("").unwrap(), Vec::<u8>::new());
+		assert!(value_bytes("0x0").is_err());
+	}
+
+	#[test]
+	fn deposits_are_paid_by_the_last_owner_of_each_identity() {
+		let endowed = vec![account("Alice"), account("Bob")];
+		let delegate = (account("Alice"), "veriKey".to_string(), account("Bob"));
+		let attribute = (account("Charlie"), "name".to_string(), "charlie".to_string());
+
+		// The identities pay their own deposits.
+		let identities =
+			IdentityGenesis { delegates: vec![delegate.clone()], ..Default::default() };
+		assert!(identities.check_endowed(&endowed).is_ok());
+		let identities =
+			IdentityGenesis { attributes: vec![attribute.clone()], ..Default::default() };
+		assert!(identities.check_endowed(&endowed).is_err());
+
+		// Owners pay the deposits of the identities they own, the last one listed prevails.
+		let identities = IdentityGenesis {
+			owners: vec![
+				(account("Charlie"), account("Dave")),
+				(account("Charlie"), account("Bob")),
+			],
+			attributes: vec![attribute],
+			..Default::default()
+		};
+		assert!(identities.check_endowed(&endowed).is_ok());
+		let identities = IdentityGenesis {
+			owners: vec![(account("Alice"), account("Dave"))],
+			delegates: vec![delegate],
+			..Default::default()
+		};
+		let error = identities.check_endowed(&endowed).err().unwrap();
+		assert!(error.contains(&account("Dave").to_string()));
 	}
 }
@@ -36,9 +36,17 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
+		// `dev:<file>` and `local:<file>` provision the identities of a JSON file at genesis.
+		if let Some(file) = id.strip_prefix("dev:") {
+			return Ok(Box::new(chain_spec::development_config(Some(file.as_ref()))?))
+		}
+		if let Some(file) = id.strip_prefix("local:") {
+			return Ok(Box::new(chain_spec::local_testnet_config(Some(file.as_ref()))?))
+		}
+
 		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
+			"dev" => Box::new(chain_spec::development_config(None)?),
+			"" | "local" => Box::new(chain_spec::local_testnet_config(None)?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
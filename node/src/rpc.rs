@@ -8,7 +8,7 @@
 use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index, BlockNumber, Hash}; 
+use node_template_runtime::{opaque::Block, AccountId, Balance, Index, BlockNumber, Hash, Moment};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -37,19 +37,25 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: pallet_did_rpc::DidRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+	C::Api: pallet_abac_rpc::AbacRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	use pallet_contracts_rpc::{Contracts, ContractsApiServer};
+	use pallet_did_rpc::{Did, DidApiServer};
+	use pallet_abac_rpc::{Abac, AbacApiServer};
 
 	let mut module = RpcModule::new(());
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
-	module.merge(Contracts::new(client).into_rpc())?;
+	module.merge(Contracts::new(client.clone()).into_rpc())?;
+	module.merge(Did::new(client.clone()).into_rpc())?;
+	module.merge(Abac::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
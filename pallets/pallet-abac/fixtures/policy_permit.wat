@@ -0,0 +1,19 @@
+;; A policy which permits every request, returning the `bool` true.
+(module
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 1) encoded decision
+	(data (i32.const 0) "\01")
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_return
+			(i32.const 0)	;; Return flags
+			(i32.const 0)	;; Pointer to the decision
+			(i32.const 1)	;; Length of the decision
+		)
+		(unreachable)
+	)
+)
@@ -0,0 +1,10 @@
+;; A policy which traps on every request.
+(module
+	(import "env" "memory" (memory 1 1))
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(unreachable)
+	)
+)
@@ -0,0 +1,40 @@
+;; A policy which overwrites its storage before permitting a request.
+;; The value keeps its length so no storage deposit is needed.
+(module
+	(import "seal0" "seal_set_storage" (func $seal_set_storage (param i32 i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 32) storage key, all zero
+
+	;; [32, 36) value set on deployment
+	(data (i32.const 32) "init")
+
+	;; [36, 40) value set on evaluation
+	(data (i32.const 36) "eval")
+
+	;; [40, 41) encoded decision
+	(data (i32.const 40) "\01")
+
+	(func (export "deploy")
+		(call $seal_set_storage
+			(i32.const 0)	;; Pointer to the storage key
+			(i32.const 32)	;; Pointer to the value
+			(i32.const 4)	;; Length of the value
+		)
+	)
+
+	(func (export "call")
+		(call $seal_set_storage
+			(i32.const 0)	;; Pointer to the storage key
+			(i32.const 36)	;; Pointer to the value
+			(i32.const 4)	;; Length of the value
+		)
+		(call $seal_return
+			(i32.const 0)	;; Return flags
+			(i32.const 40)	;; Pointer to the decision
+			(i32.const 1)	;; Length of the decision
+		)
+		(unreachable)
+	)
+)
@@ -0,0 +1,29 @@
+//! Runtime API definition required by ABAC RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding access decisions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_abac::types::AccessDecision;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The API to evaluate the access control policies of `pallet_abac`.
+	pub trait AbacApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Evaluates the policies attached to `object` for `subject` performing `action`,
+		/// as `request_access` would without submitting it. Policies are dry-run, whatever
+		/// they write is discarded.
+		fn evaluate(
+			subject: AccountId,
+			object: AccountId,
+			action: Vec<u8>,
+			context: Vec<u8>,
+		) -> Result<AccessDecision<AccountId>, DispatchError>;
+	}
+}
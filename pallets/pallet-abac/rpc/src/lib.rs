@@ -0,0 +1,136 @@
+//! Node-specific RPC methods for access decisions of ABAC.
+//! Policies are dry-run by the runtime through `AbacApi`, so checking an access costs
+//! no transaction and leaves no trace on chain, unlike `request_access`.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_abac::types::{AccessDecision, CombiningAlgorithm, PolicyDecision, PolicyResult};
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, Bytes};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_abac_rpc_runtime_api::AbacApi as AbacRuntimeApi;
+
+const RUNTIME_ERROR: i32 = 1;
+const EVALUATION_FAILED: i32 = 2;
+
+/// ABAC RPC methods.
+#[rpc(client, server)]
+pub trait AbacApi<BlockHash, AccountId> {
+	/// Decides whether `subject` may perform `action` on `object` at a given block.
+	///
+	/// Every policy attached to the object is dry-run with the optional `context`.
+	/// Returns the combined decision along with the decision and gas consumed of each
+	/// policy, in priority order.
+	#[method(name = "abac_evaluate")]
+	fn evaluate(
+		&self,
+		subject: AccountId,
+		object: AccountId,
+		action: String,
+		context: Option<Bytes>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Value>;
+}
+
+/// Implementation of the ABAC RPC methods.
+pub struct Abac<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Abac<C, B> {
+	/// Create new `Abac` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> AbacApiServer<<Block as BlockT>::Hash, AccountId> for Abac<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: AbacRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Ss58Codec,
+{
+	fn evaluate(
+		&self,
+		subject: AccountId,
+		object: AccountId,
+		action: String,
+		context: Option<Bytes>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Value> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let decision = api
+			.evaluate(&at, subject, object, action.into_bytes(), context.unwrap_or_default().0)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_err(|err| {
+				CallError::Custom(ErrorObject::owned(
+					EVALUATION_FAILED,
+					"Evaluation failed",
+					Some(format!("{:?}", err)),
+				))
+			})?;
+
+		Ok(access_decision(decision))
+	}
+}
+
+/// Renders an access decision with the result of each policy.
+fn access_decision<AccountId: Ss58Codec>(decision: AccessDecision<AccountId>) -> Value {
+	let results: Vec<Value> = decision.results.iter().map(policy_result).collect();
+	json!({
+		"granted": decision.granted,
+		"decision": policy_decision(decision.decision),
+		"algorithm": combining_algorithm(decision.algorithm),
+		"results": results,
+		"gasConsumed": decision.gas_consumed,
+	})
+}
+
+fn policy_result<AccountId: Ss58Codec>(result: &PolicyResult<AccountId>) -> Value {
+	json!({
+		"policy": result.policy.to_ss58check(),
+		"decision": policy_decision(result.decision),
+		"gasConsumed": result.gas_consumed,
+	})
+}
+
+fn policy_decision(decision: PolicyDecision) -> &'static str {
+	match decision {
+		PolicyDecision::Deny => "deny",
+		PolicyDecision::Permit => "permit",
+		PolicyDecision::NotApplicable => "notApplicable",
+		PolicyDecision::Indeterminate => "indeterminate",
+	}
+}
+
+fn combining_algorithm(algorithm: CombiningAlgorithm) -> &'static str {
+	match algorithm {
+		CombiningAlgorithm::DenyOverrides => "denyOverrides",
+		CombiningAlgorithm::PermitOverrides => "permitOverrides",
+		CombiningAlgorithm::FirstApplicable => "firstApplicable",
+		CombiningAlgorithm::OnlyOneApplicable => "onlyOneApplicable",
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
@@ -0,0 +1,402 @@
+//! Benchmarking setup for pallet-abac
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Abac;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller, BenchmarkError};
+use frame_support::{
+	sp_runtime::traits::Bounded,
+	traits::{Currency, Get},
+};
+use frame_system::RawOrigin;
+use pallet_contracts_primitives::Code;
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+type ContractsBalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// A valid contract which does nothing at all, see `pallet-contracts/fixtures/dummy.wat`.
+const DUMMY_CONTRACT: [u8; 47] = [
+	0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+	0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section
+	0x03, 0x03, 0x02, 0x00, 0x00, // function section
+	0x07, 0x11, 0x02, 0x06, 0x64, 0x65, 0x70, 0x6c, 0x6f, 0x79, 0x00, 0x00, 0x04, 0x63, 0x61, 0x6c,
+	0x6c, 0x00, 0x01, // export section
+	0x0a, 0x07, 0x02, 0x02, 0x00, 0x0b, 0x02, 0x00, 0x0b, // code section
+];
+
+/// Longest attribute key accepted by the pallet, made unique by its index.
+fn attr_key<T: Config>(index: u32) -> Vec<u8> {
+	let mut key = vec![b'k'; T::MaxAttrNameLength::get().saturating_sub(4) as usize];
+	key.extend(index.to_le_bytes());
+	key
+}
+
+fn bounded_attr_key<T: Config>(index: u32) -> AttrName<T> {
+	attr_key::<T>(index).try_into().expect("attribute key fits the limit")
+}
+
+fn attr_keys<T: Config>(n: u32) -> Vec<Vec<u8>> {
+	(0..n).map(attr_key::<T>).collect()
+}
+
+/// Longest attribute value accepted by the pallet.
+fn attr_value<T: Config>(value: u8) -> Vec<u8> {
+	vec![value; T::MaxAttrValueLength::get() as usize]
+}
+
+fn attr_inputs<T: Config>(n: u32, value: u8) -> Vec<AttrInput> {
+	(0..n)
+		.map(|i| AttrInput { name: attr_key::<T>(i), value: attr_value::<T>(value) })
+		.collect()
+}
+
+/// Longest enum variants accepted by the pallet, made unique by their index.
+fn enum_variants<T: Config>() -> EnumVariants<T> {
+	let variants: Vec<AttrValue<T>> = (0..T::MaxEnumVariants::get())
+		.map(|i| {
+			let mut variant = vec![b'v'; T::MaxAttrValueLength::get().saturating_sub(4) as usize];
+			variant.extend(i.to_le_bytes());
+			variant.try_into().expect("enum variant fits the limit")
+		})
+		.collect();
+	variants.try_into().expect("enum variants fit the limit")
+}
+
+/// Longest policy name accepted by the pallet.
+fn policy_name<T: Config>() -> Vec<u8> {
+	vec![b'p'; T::MaxPolicyNameLength::get() as usize]
+}
+
+/// Sets `n` attributes of an identity and spreads `e` endorsements over them.
+fn set_up_attributes<T: Config>(
+	identity: &T::AccountId,
+	n: u32,
+	e: u32,
+) -> Result<(), BenchmarkError> {
+	Abac::<T>::set_attributes(
+		RawOrigin::Signed(identity.clone()).into(),
+		identity.clone(),
+		attr_inputs::<T>(n, 0),
+	)?;
+
+	let validity: T::BlockNumber = u32::MAX.into();
+	for i in 0..e.min(n * T::MaxEndorsements::get()) {
+		let key = bounded_attr_key::<T>(i % n);
+		let endorser: T::AccountId = account("endorser", i / n, SEED);
+		let endorsement = Endorsement { validity, endorsed_time: <T as Config>::Time::now() };
+		<EndorsementOf<T>>::insert((identity, &key, &endorser), endorsement);
+		<EndorsedBy<T>>::insert(&endorser, (identity, &key), ());
+		<EndorsementCount<T>>::mutate(identity, &key, |count| *count += 1);
+	}
+	Ok(())
+}
+
+/// Deploys a contract to be attached as a policy, at an address told apart by `salt`.
+fn deploy_policy<T: Config>(
+	deployer: &T::AccountId,
+	salt: u32,
+) -> Result<T::AccountId, BenchmarkError> {
+	<T as pallet_contracts::Config>::Currency::make_free_balance_be(
+		deployer,
+		ContractsBalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	let result = <pallet_contracts::Pallet<T>>::bare_instantiate(
+		deployer.clone(),
+		0u32.into(),
+		<T as frame_system::Config>::BlockWeights::get().max_block,
+		None,
+		Code::Upload(DUMMY_CONTRACT.to_vec()),
+		Vec::new(),
+		salt.encode(),
+		false,
+	);
+	Ok(result.result.map_err(|_| "failed to deploy the policy contract")?.account_id)
+}
+
+fn assert_last_event<T: Config>(event: <T as Config>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(event.into());
+}
+
+benchmarks! {
+	set_attributes {
+		let n in 1 .. T::MaxAttributesPerCall::get();
+		let e in 0 .. T::MaxAttributesPerCall::get() * T::MaxEndorsements::get();
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_attributes::<T>(&caller, n, e)?;
+		// Every attribute has a schema its new value is validated against.
+		for i in 0..n {
+			let schema = AttrSchema {
+				value_type: AttrType::String,
+				constraints: AttrConstraints {
+					max_length: Some(T::MaxAttrValueLength::get()),
+					..Default::default()
+				},
+			};
+			<AttrSchemaOf<T>>::insert(bounded_attr_key::<T>(i), schema);
+		}
+		// Every value changes so every endorsement is removed.
+		let attrs = attr_inputs::<T>(n, 1);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), attrs)
+	verify {
+		let attr = Abac::<T>::attr_of(&caller, bounded_attr_key::<T>(0));
+		assert_eq!(attr.map(|attr| attr.value.to_vec()), Some(attr_value::<T>(1)));
+	}
+
+	clear_attributes {
+		let n in 1 .. T::MaxAttributesPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_attributes::<T>(&caller, n, 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), attr_keys::<T>(n))
+	verify {
+		assert!(Abac::<T>::attr_of(&caller, bounded_attr_key::<T>(0)).is_none());
+	}
+
+	endorse_attributes {
+		let n in 1 .. T::MaxAttributesPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let target: T::AccountId = account("target", 0, SEED);
+		set_up_attributes::<T>(&target, n, 0)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), target.clone(), attr_keys::<T>(n), None)
+	verify {
+		assert!(Abac::<T>::endorsement_of((&target, bounded_attr_key::<T>(0), &caller)).is_some());
+	}
+
+	unendorse_attributes {
+		let n in 1 .. T::MaxAttributesPerCall::get();
+		let target: T::AccountId = account("target", 0, SEED);
+		// The first endorser of every attribute withdraws its endorsements.
+		set_up_attributes::<T>(&target, n, n)?;
+		let endorser: T::AccountId = account("endorser", 0, SEED);
+	}: _(RawOrigin::Signed(endorser.clone()), endorser.clone(), target.clone(), attr_keys::<T>(n))
+	verify {
+		let key = bounded_attr_key::<T>(0);
+		assert!(Abac::<T>::endorsement_of((&target, key, &endorser)).is_none());
+	}
+
+	reject_endorsements {
+		let n in 1 .. T::MaxAttributesPerCall::get();
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_attributes::<T>(&caller, n, n)?;
+		let endorser: T::AccountId = account("endorser", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), endorser, attr_keys::<T>(n))
+	verify {
+		assert_eq!(Abac::<T>::endorsement_count(&caller, bounded_attr_key::<T>(0)), 0);
+	}
+
+	attach_policy {
+		let caller: T::AccountId = whitelisted_caller();
+		let object: T::AccountId = account("object", 0, SEED);
+		let policy = deploy_policy::<T>(&caller, 0)?;
+		// Attaching to another object requires a `PolicyAdmin` delegate of the object.
+		<T as pallet_did::Config>::Currency::make_free_balance_be(
+			&object,
+			pallet_did::BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		<pallet_did::Pallet<T>>::add_delegate(
+			RawOrigin::Signed(object.clone()).into(),
+			object.clone(),
+			caller.clone(),
+			DELEGATE_POLICY_ADMIN.to_vec(),
+			None,
+		)?;
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		caller.clone(),
+		object.clone(),
+		policy.clone(),
+		policy_name::<T>()
+	)
+	verify {
+		assert!(Abac::<T>::policy_of(&object, &policy).is_some());
+	}
+
+	detach_policy {
+		let caller: T::AccountId = whitelisted_caller();
+		let policy = deploy_policy::<T>(&caller, 0)?;
+		Abac::<T>::attach_policy(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			caller.clone(),
+			policy.clone(),
+			policy_name::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), caller.clone(), policy.clone())
+	verify {
+		assert!(Abac::<T>::policy_of(&caller, &policy).is_none());
+	}
+
+	request_access {
+		let p in 1 .. T::MaxPolicies::get();
+		let caller: T::AccountId = whitelisted_caller();
+		for i in 0..p {
+			let policy = deploy_policy::<T>(&caller, i)?;
+			Abac::<T>::attach_policy(
+				RawOrigin::Signed(caller.clone()).into(),
+				caller.clone(),
+				caller.clone(),
+				policy,
+				policy_name::<T>(),
+			)?;
+		}
+		let action = vec![b'a'; T::MaxAttrNameLength::get() as usize];
+		let context = vec![0u8; T::MaxContextLength::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), caller.clone(), action.clone(), context)
+	verify {
+		// The dummy contract returns nothing, which is indeterminate and denies the access.
+		let denied_by = Abac::<T>::policy_priorities(&caller).first().map(|(_, p)| p.clone());
+		let event = Event::AccessDenied(caller.clone(), caller, action, denied_by);
+		assert_last_event::<T>(event.into());
+	}
+
+	set_combining_algorithm {
+		let caller: T::AccountId = whitelisted_caller();
+		let object: T::AccountId = account("object", 0, SEED);
+		<T as pallet_did::Config>::Currency::make_free_balance_be(
+			&object,
+			pallet_did::BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		<pallet_did::Pallet<T>>::add_delegate(
+			RawOrigin::Signed(object.clone()).into(),
+			object.clone(),
+			caller.clone(),
+			DELEGATE_POLICY_ADMIN.to_vec(),
+			None,
+		)?;
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		caller,
+		object.clone(),
+		CombiningAlgorithm::FirstApplicable
+	)
+	verify {
+		assert_eq!(Abac::<T>::combining_algorithm(&object), CombiningAlgorithm::FirstApplicable);
+	}
+
+	set_policy_priority {
+		let caller: T::AccountId = whitelisted_caller();
+		let object: T::AccountId = account("object", 0, SEED);
+		<T as pallet_did::Config>::Currency::make_free_balance_be(
+			&object,
+			pallet_did::BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		<pallet_did::Pallet<T>>::add_delegate(
+			RawOrigin::Signed(object.clone()).into(),
+			object.clone(),
+			caller.clone(),
+			DELEGATE_POLICY_ADMIN.to_vec(),
+			None,
+		)?;
+		// The first of the most policies moves behind all the others.
+		for i in 0..T::MaxPolicies::get() {
+			let policy = deploy_policy::<T>(&caller, i)?;
+			Abac::<T>::attach_policy(
+				RawOrigin::Signed(caller.clone()).into(),
+				caller.clone(),
+				object.clone(),
+				policy,
+				policy_name::<T>(),
+			)?;
+		}
+		let policy = Abac::<T>::policy_priorities(&object)[0].1.clone();
+	}: _(RawOrigin::Signed(caller.clone()), caller, object.clone(), policy.clone(), u32::MAX)
+	verify {
+		assert_eq!(Abac::<T>::policy_priorities(&object).last(), Some(&(u32::MAX, policy)));
+	}
+
+	set_attr_schema {
+		let origin = T::SchemaOrigin::successful_origin();
+		let value_type = AttrType::Enum(enum_variants::<T>());
+	}: {
+		let name = bounded_attr_key::<T>(0);
+		Abac::<T>::set_attr_schema(origin, name, value_type, Default::default())?;
+	}
+	verify {
+		assert!(Abac::<T>::attr_schema(bounded_attr_key::<T>(0)).is_some());
+	}
+
+	remove_attr_schema {
+		let origin = T::SchemaOrigin::successful_origin();
+		Abac::<T>::set_attr_schema(
+			origin.clone(),
+			bounded_attr_key::<T>(0),
+			AttrType::Enum(enum_variants::<T>()),
+			Default::default(),
+		)?;
+	}: {
+		Abac::<T>::remove_attr_schema(origin, bounded_attr_key::<T>(0))?;
+	}
+	verify {
+		assert!(Abac::<T>::attr_schema(bounded_attr_key::<T>(0)).is_none());
+	}
+
+	// Read by policies through the runtime chain extension, the longest value decoded as the
+	// last variant of the largest enum.
+	typed_attr {
+		let caller: T::AccountId = whitelisted_caller();
+		let variants = enum_variants::<T>();
+		let value = variants[variants.len() - 1].to_vec();
+		Abac::<T>::set_attr_schema(
+			T::SchemaOrigin::successful_origin(),
+			bounded_attr_key::<T>(0),
+			AttrType::Enum(variants),
+			Default::default(),
+		)?;
+		Abac::<T>::set_attributes(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			vec![AttrInput { name: attr_key::<T>(0), value: value.clone() }],
+		)?;
+		let expected = Some(TypedValue::Enum(value));
+	}: {
+		assert_eq!(Abac::<T>::typed_attr(&caller, &bounded_attr_key::<T>(0)), expected);
+	}
+
+	purge_item {
+		// The costliest item is a policy the identity attached to an object with the most
+		// policies, all the others attached by the object itself.
+		let caller: T::AccountId = whitelisted_caller();
+		let object: T::AccountId = account("object", 0, SEED);
+		<T as pallet_did::Config>::Currency::make_free_balance_be(
+			&object,
+			pallet_did::BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		<pallet_did::Pallet<T>>::add_delegate(
+			RawOrigin::Signed(object.clone()).into(),
+			object.clone(),
+			caller.clone(),
+			DELEGATE_POLICY_ADMIN.to_vec(),
+			None,
+		)?;
+		for i in 1..T::MaxPolicies::get() {
+			let policy = deploy_policy::<T>(&object, i)?;
+			Abac::<T>::attach_policy(
+				RawOrigin::Signed(object.clone()).into(),
+				object.clone(),
+				object.clone(),
+				policy,
+				policy_name::<T>(),
+			)?;
+		}
+		let policy = deploy_policy::<T>(&caller, 0)?;
+		Abac::<T>::attach_policy(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			object.clone(),
+			policy.clone(),
+			policy_name::<T>(),
+		)?;
+		<PurgeQueue<T>>::insert(&caller, ());
+	}: {
+		Abac::<T>::purge_deactivated(Weight::MAX);
+	}
+	verify {
+		assert!(Abac::<T>::policy_of(&object, &policy).is_none());
+	}
+
+	impl_benchmark_test_suite!(Abac, crate::mock::new_test_ext(), crate::mock::Test);
+}
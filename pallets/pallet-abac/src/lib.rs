@@ -2,21 +2,62 @@
 pub mod types;
 use crate::types::*;
 
+pub mod migration;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 pub use pallet::*;
 
-use frame_support::{pallet_prelude::*, sp_std::vec::Vec, traits::Time};
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::{
+	dispatch::DispatchResultWithPostInfo,
+	pallet_prelude::*,
+	sp_runtime::traits::Zero,
+	sp_std::{str, vec::Vec},
+	storage::{with_transaction, TransactionOutcome},
+	traits::Time,
+};
 use frame_system::pallet_prelude::*;
-
-const VEC_MAX_LENGTH: usize = 64;
+use pallet_did::types::Caller;
 
 pub type Moment<T> = <<T as Config>::Time as Time>::Moment;
+pub type AttrName<T> = BoundedVec<u8, <T as Config>::MaxAttrNameLength>;
+pub type AttrValue<T> = BoundedVec<u8, <T as Config>::MaxAttrValueLength>;
+pub type PolicyName<T> = BoundedVec<u8, <T as Config>::MaxPolicyNameLength>;
+pub type AttrFor<T> = Attr<Moment<T>, AttrName<T>, AttrValue<T>>;
+pub type PolicyFor<T> = Policy<<T as frame_system::Config>::AccountId, Moment<T>, PolicyName<T>>;
+pub type EnumVariants<T> = BoundedVec<AttrValue<T>, <T as Config>::MaxEnumVariants>;
+pub type AttrSchemaFor<T> = AttrSchema<EnumVariants<T>>;
+pub type PolicyPriorities<T> =
+	BoundedVec<(u32, <T as frame_system::Config>::AccountId), <T as Config>::MaxPolicies>;
+
+/// Selector of the ink! message `evaluate(subject, object, action, context)` policy
+/// contracts implement, the first bytes of the BLAKE2b-256 hash of `evaluate`.
+/// The message returns a `PolicyDecision`, or a `bool` for `Permit` or `Deny`.
+pub const POLICY_EVALUATE_SELECTOR: [u8; 4] = [0x21, 0x94, 0xbd, 0xee];
+/// Priority of a newly attached policy, evaluated after the policies already attached with it.
+pub const DEFAULT_POLICY_PRIORITY: u32 = 0;
+/// Delegate type allowed to attach and detach the policies of an object, to be registered in
+/// the `PermissionDelegateTypes` of `pallet_did`.
+pub const DELEGATE_POLICY_ADMIN: &[u8] = b"PolicyAdmin";
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info] // Only for DEVELOPMENT or DEMONSTRATION
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
@@ -24,6 +65,37 @@ pub mod pallet {
 	pub trait Config: frame_system::Config + pallet_did::Config + pallet_contracts::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Time: Time;
+		/// The maximum number of endorsements an attribute can hold.
+		#[pallet::constant]
+		type MaxEndorsements: Get<u32>;
+		/// The maximum number of attributes handled by a single call.
+		#[pallet::constant]
+		type MaxAttributesPerCall: Get<u32>;
+		/// The maximum length of an attribute name.
+		#[pallet::constant]
+		type MaxAttrNameLength: Get<u32>;
+		/// The maximum length of an attribute value.
+		#[pallet::constant]
+		type MaxAttrValueLength: Get<u32>;
+		/// The maximum length of a policy name.
+		#[pallet::constant]
+		type MaxPolicyNameLength: Get<u32>;
+		/// The maximum number of policies attached to an object.
+		#[pallet::constant]
+		type MaxPolicies: Get<u32>;
+		/// The gas limit of the evaluation of a single policy.
+		#[pallet::constant]
+		type PolicyGasLimit: Get<Weight>;
+		/// The maximum length of the context of an access request.
+		#[pallet::constant]
+		type MaxContextLength: Get<u32>;
+		/// The maximum number of variants of an enum attribute.
+		#[pallet::constant]
+		type MaxEnumVariants: Get<u32>;
+		/// The origin allowed to define the schemas of attributes.
+		type SchemaOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
@@ -33,24 +105,43 @@ pub mod pallet {
 		Blake2_128Concat,
 		T::AccountId,
 		Blake2_128Concat,
-		Vec<u8>,
-		Attr<Moment<T>>,
+		AttrName<T>,
+		AttrFor<T>,
 		OptionQuery,
 	>;
 
+	/// Schemas of attributes by name, the values of attributes with a schema are typed.
+	#[pallet::storage]
+	#[pallet::getter(fn attr_schema)]
+	pub(super) type AttrSchemaOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, AttrName<T>, AttrSchemaFor<T>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn endorsement_of)]
 	pub(super) type EndorsementOf<T: Config> = StorageNMap<
 		_,
 		(
 			NMapKey<Blake2_128Concat, T::AccountId>,
-			NMapKey<Blake2_128Concat, Vec<u8>>,
+			NMapKey<Blake2_128Concat, AttrName<T>>,
 			NMapKey<Blake2_128Concat, T::AccountId>,
 		),
 		Endorsement<T::BlockNumber, Moment<T>>,
 		OptionQuery,
 	>;
 
+	/// Number of endorsements held by an attribute, bounded by `MaxEndorsements`.
+	#[pallet::storage]
+	#[pallet::getter(fn endorsement_count)]
+	pub(super) type EndorsementCount<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AttrName<T>,
+		u32,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn policy_of)]
 	pub(super) type PolicyOf<T: Config> = StorageDoubleMap<
@@ -59,19 +150,84 @@ pub mod pallet {
 		T::AccountId,
 		Blake2_128Concat,
 		T::AccountId,
-		Policy<T::AccountId, Moment<T>>,
+		PolicyFor<T>,
 		OptionQuery,
 	>;
 
+	/// Attributes of other identities endorsed by an identity, as `(target, name)`.
+	/// The reverse of `EndorsementOf`, so it holds no more entries than the bounded endorsements.
+	#[pallet::storage]
+	pub(super) type EndorsedBy<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::AccountId, AttrName<T>),
+		(),
+		OptionQuery,
+	>;
+
+	/// Policies an identity attached to objects, as `(object, policy)`.
+	/// The reverse of the `attached_by` of `PolicyOf`, bounded like the attachments.
+	#[pallet::storage]
+	pub(super) type AttachedBy<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId),
+		(),
+		OptionQuery,
+	>;
+
+	/// Policies attached to an object with their priority, in evaluation order.
+	/// Lower priorities are evaluated first, ties in the order of attachment.
+	#[pallet::storage]
+	#[pallet::getter(fn policy_priorities)]
+	pub(super) type PolicyPriorityOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, PolicyPriorities<T>, ValueQuery>;
+
+	/// Algorithm combining the decisions of the policies attached to an object.
+	#[pallet::storage]
+	#[pallet::getter(fn combining_algorithm)]
+	pub(super) type CombiningAlgorithmOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CombiningAlgorithm, ValueQuery>;
+
+	/// Identities deactivated in `pallet_did` whose attributes, endorsements and
+	/// policy attachments are still to be removed.
+	#[pallet::storage]
+	pub(super) type PurgeQueue<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		AttributesSet(T::AccountId, T::AccountId, Vec<AttrInput>),
 		AttributesCleared(T::AccountId, T::AccountId, Vec<Vec<u8>>),
 		AttributesEndorsed(T::AccountId, T::AccountId, T::AccountId, Vec<Vec<u8>>, T::BlockNumber),
+		/// The endorser withdrew its endorsements of the attributes of the target.
+		/// \[who, endorser, target, keys\]
 		AttributesUnendorsed(T::AccountId, T::AccountId, T::AccountId, Vec<Vec<u8>>),
+		/// The identity rejected the endorsements of its attributes by the endorser.
+		/// \[who, identity, endorser, keys\]
+		EndorsementsRejected(T::AccountId, T::AccountId, T::AccountId, Vec<Vec<u8>>),
 		PolicyAttached(T::AccountId, T::AccountId, T::AccountId, T::AccountId, Vec<u8>),
 		PolicyDetached(T::AccountId, T::AccountId, T::AccountId, T::AccountId, Vec<u8>),
+		/// Everything held about a deactivated identity was removed. \[identity\]
+		IdentityPurged(T::AccountId),
+		/// The policies of the object permitted the access. \[subject, object, action\]
+		AccessGranted(T::AccountId, T::AccountId, Vec<u8>),
+		/// The access was denied by the first policy whose decision prevailed, none if no
+		/// single policy did. \[subject, object, action, policy\]
+		AccessDenied(T::AccountId, T::AccountId, Vec<u8>, Option<T::AccountId>),
+		/// \[who, identity, object, algorithm\]
+		CombiningAlgorithmSet(T::AccountId, T::AccountId, T::AccountId, CombiningAlgorithm),
+		/// \[who, identity, object, policy, priority\]
+		PolicyPrioritySet(T::AccountId, T::AccountId, T::AccountId, T::AccountId, u32),
+		/// The schema of the attributes of a name was defined. \[name\]
+		AttrSchemaSet(Vec<u8>),
+		/// The schema of the attributes of a name was removed. \[name\]
+		AttrSchemaRemoved(Vec<u8>),
 	}
 
 	#[pallet::error]
@@ -83,82 +239,184 @@ pub mod pallet {
 		InvalidDelegate,
 		PolicyAttachmentExists,
 		PolicyAttachmentNotExists,
+		TooManyEndorsements,
+		/// The identity is owned by its controllers, the call has to be proposed to them.
+		ApprovalRequired,
+		/// The identity is deactivated in `pallet_did`.
+		IdentityDeactivated,
+		/// The object has `MaxPolicies` policies attached already.
+		TooManyPolicies,
+		/// The attribute is not endorsed by the endorser.
+		EndorsementNotExists,
+		/// The constraints do not apply to the type, or an enum has no or duplicate variants.
+		InvalidAttrSchema,
+		/// The value does not have the type of the schema or breaks its constraints.
+		InvalidAttrValue,
+		AttrSchemaNotExists,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Access control attributes, `(identity, name, value)`.
+		pub attributes: Vec<(T::AccountId, Vec<u8>, Vec<u8>)>,
+		/// Endorsements valid forever of the attributes above, `(endorser, identity, name)`.
+		pub endorsements: Vec<(T::AccountId, T::AccountId, Vec<u8>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { attributes: Vec::new(), endorsements: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let now_timestamp = <T as Config>::Time::now();
+			for (identity, name, value) in &self.attributes {
+				let name: AttrName<T> =
+					name.clone().try_into().expect("genesis attribute names fit the bound");
+				let value: AttrValue<T> =
+					value.clone().try_into().expect("genesis attribute values fit the bound");
+				let attr = Attr { name: name.clone(), value, updated_time: now_timestamp };
+				<AttrOf<T>>::insert(identity, &name, attr);
+			}
+
+			for (endorser, identity, name) in &self.endorsements {
+				let name: AttrName<T> =
+					name.clone().try_into().expect("genesis attribute names fit the bound");
+				assert!(
+					<AttrOf<T>>::contains_key(identity, &name),
+					"genesis endorsements are of genesis attributes"
+				);
+				let endorsement_key = (identity, &name, endorser);
+				if !<EndorsementOf<T>>::contains_key(endorsement_key) {
+					<EndorsementCount<T>>::mutate(identity, &name, |count| {
+						let max = T::MaxEndorsements::get();
+						assert!(*count < max, "genesis endorsements exceed MaxEndorsements");
+						*count += 1;
+					});
+				}
+				let endorsement =
+					Endorsement { validity: u32::MAX.into(), endorsed_time: now_timestamp };
+				<EndorsementOf<T>>::insert(endorsement_key, endorsement);
+				<EndorsedBy<T>>::insert(endorser, (identity, &name), ());
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migration::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migration::post_upgrade::<T>()
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::purge_deactivated(remaining_weight)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_attributes(
+			list_of_attrs.len() as u32,
+			(list_of_attrs.len() as u32).saturating_mul(T::MaxEndorsements::get()),
+		))]
 		pub fn set_attributes(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			list_of_attrs: Vec<AttrInput>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+		) -> DispatchResultWithPostInfo {
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
 
-			ensure!(list_of_attrs.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
+			ensure!(
+				list_of_attrs.len() <= T::MaxAttributesPerCall::get() as usize,
+				Error::<T>::InputVectorTooLong
+			);
+			let mut bounded_attrs: Vec<(AttrName<T>, AttrValue<T>)> = Vec::new();
 			for attr in list_of_attrs.iter() {
-				ensure!(
-					attr.name.len() <= VEC_MAX_LENGTH && attr.value.len() <= VEC_MAX_LENGTH,
-					Error::<T>::InputVectorTooLong
-				);
+				bounded_attrs.push((
+					attr.name.clone().try_into().map_err(|_| Error::<T>::InputVectorTooLong)?,
+					attr.value.clone().try_into().map_err(|_| Error::<T>::InputVectorTooLong)?,
+				));
 			}
-		
-			Self::ensure_owner(&identity, &who)?;
+
+			let who = Self::ensure_owner(&identity, &caller)?;
 
 			if Self::check_attr_keys_duplication(&list_of_attrs) == true {
 				return Err(Error::<T>::InvalidAttributes.into());
 			}
 
+			// Values of attributes with a schema have to be valid.
+			for (name, value) in bounded_attrs.iter() {
+				if let Some(schema) = Self::attr_schema(name) {
+					Self::typed_value(&schema, value)?;
+				}
+			}
+
 			// Write to storage item-by-item and overwrite if exists.
-			for attr in list_of_attrs.iter() {
-				let new_attr: Attr<Moment<T>> = Attr {
-					name: attr.name.clone(),
-					value: attr.value.clone(),
-					updated_time: <T as Config>::Time::now(),
-				};
+			let mut removed_endorsements = 0u32;
+			for (name, value) in bounded_attrs.into_iter() {
+				let new_attr: AttrFor<T> =
+					Attr { name: name.clone(), value, updated_time: <T as Config>::Time::now() };
 				// Check if it should insert or update.
-				if <AttrOf<T>>::contains_key(&identity, &attr.name) {
+				if <AttrOf<T>>::contains_key(&identity, &name) {
 					let mut changed: bool = false; // it will remove endorsement if the attribute value changed.
-					<AttrOf<T>>::mutate(&identity, &attr.name, |a| {
-						changed = a.as_ref().unwrap().value != attr.value;
+					<AttrOf<T>>::mutate(&identity, &name, |a| {
+						changed = a.as_ref().unwrap().value != new_attr.value;
 						*a = Some(new_attr);
 					});
 					if changed {
-						Self::remove_endorsements_per_attribute(&identity, &attr.name);
+						removed_endorsements +=
+							Self::remove_endorsements_per_attribute(&identity, &name);
 					}
 				} else {
-					<AttrOf<T>>::insert(&identity, &attr.name, new_attr);
+					<AttrOf<T>>::insert(&identity, &name, new_attr);
 				}
 			}
 
+			let actual_weight = <T as Config>::WeightInfo::set_attributes(
+				list_of_attrs.len() as u32,
+				removed_endorsements,
+			);
 			Self::deposit_event(Event::AttributesSet(who, identity, list_of_attrs));
-			Ok(())
+			Ok(Some(actual_weight).into())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::clear_attributes(list_of_attr_keys.len() as u32)
+		)]
 		pub fn clear_attributes(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			list_of_attr_keys: Vec<Vec<u8>>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
 
-			ensure!(list_of_attr_keys.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			for attr_key in list_of_attr_keys.iter() {
-				ensure!(attr_key.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			}
+			let bounded_keys = Self::bounded_attr_keys(&list_of_attr_keys)?;
 
-			Self::ensure_owner(&identity, &who)?;
+			let who = Self::ensure_owner(&identity, &caller)?;
 
 			// Don't accept any non-existing key.
-			for key in list_of_attr_keys.iter() {
+			for key in bounded_keys.iter() {
 				if !<AttrOf<T>>::contains_key(&identity, &key) {
 					return Err(Error::<T>::InvalidAttributes.into());
 				}
 			}
 
 			// Write to storage key-by-key
-			for key in list_of_attr_keys.iter() {
+			for key in bounded_keys.iter() {
 				<AttrOf<T>>::remove(&identity, &key);
 			}
 
@@ -166,7 +424,9 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(
+			<T as Config>::WeightInfo::endorse_attributes(list_of_attr_keys.len() as u32)
+		)]
 		pub fn endorse_attributes(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
@@ -174,17 +434,18 @@ pub mod pallet {
 			list_of_attr_keys: Vec<Vec<u8>>,
 			valid_for: Option<T::BlockNumber>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			
-			ensure!(list_of_attr_keys.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			for attr_key in list_of_attr_keys.iter() {
-				ensure!(attr_key.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			}
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
+
+			let bounded_keys = Self::bounded_attr_keys(&list_of_attr_keys)?;
 
-			Self::ensure_owner(&identity, &who)?;
+			let who = Self::ensure_owner(&identity, &caller)?;
+			ensure!(
+				!<pallet_did::Pallet<T>>::is_deactivated(&target_identity),
+				Error::<T>::IdentityDeactivated
+			);
 
 			// Don't accept any non-existing key.
-			let all_existing = Self::check_attributes_existing(&target_identity, &list_of_attr_keys);
+			let all_existing = Self::check_attributes_existing(&target_identity, &bounded_keys);
 			if !all_existing {
 				return Err(Error::<T>::InvalidAttributes.into());
 			}
@@ -196,7 +457,7 @@ pub mod pallet {
 					None => u32::MAX.into()
 			};
 
-			for key in list_of_attr_keys.iter() {
+			for key in bounded_keys.iter() {
 				let new_endorsement = Endorsement {
 					validity: validity.clone(),
 					endorsed_time: now_timestamp.clone()
@@ -208,7 +469,16 @@ pub mod pallet {
 						*e = Some(new_endorsement);
 					})
 				} else {
+					<EndorsementCount<T>>::try_mutate(&target_identity, &key, |count| {
+						ensure!(
+							*count < T::MaxEndorsements::get(),
+							Error::<T>::TooManyEndorsements
+						);
+						*count += 1;
+						Ok::<(), Error<T>>(())
+					})?;
 					<EndorsementOf<T>>::insert(endorsement_key, new_endorsement);
+					<EndorsedBy<T>>::insert(&identity, (&target_identity, key), ());
 				}
 			}
 
@@ -222,30 +492,31 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		/// Withdraws the endorsements by `identity` of the attributes of `target_identity`.
+		/// Endorsements of the same attributes by other identities are left untouched.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::unendorse_attributes(list_of_attr_keys.len() as u32)
+		)]
 		pub fn unendorse_attributes(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			target_identity: T::AccountId,
 			list_of_attr_keys: Vec<Vec<u8>>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
 
-			ensure!(list_of_attr_keys.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			for attr_key in list_of_attr_keys.iter() {
-				ensure!(attr_key.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
-			}
+			let bounded_keys = Self::bounded_attr_keys(&list_of_attr_keys)?;
 
-			Self::ensure_owner(&identity, &who)?;
+			let who = Self::ensure_owner(&identity, &caller)?;
 
 			// Don't accept any non-existing key.
-			let all_existing = Self::check_attributes_existing(&identity, &list_of_attr_keys);
+			let all_existing = Self::check_attributes_existing(&target_identity, &bounded_keys);
 			if !all_existing {
 				return Err(Error::<T>::InvalidAttributes.into());
 			}
 
-			for key in list_of_attr_keys.iter() {
-				Self::remove_endorsements_per_attribute(&identity, key);
+			for key in bounded_keys.iter() {
+				Self::remove_endorsement(&target_identity, key, &identity)?;
 			}
 
 			Self::deposit_event(Event::AttributesUnendorsed(
@@ -257,7 +528,37 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		/// Rejects the endorsements by `endorser` of the attributes of `identity`, called by
+		/// the owner of the attributes rather than the endorser.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::reject_endorsements(list_of_attr_keys.len() as u32)
+		)]
+		pub fn reject_endorsements(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			endorser: T::AccountId,
+			list_of_attr_keys: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
+
+			let bounded_keys = Self::bounded_attr_keys(&list_of_attr_keys)?;
+
+			let who = Self::ensure_owner(&identity, &caller)?;
+
+			for key in bounded_keys.iter() {
+				Self::remove_endorsement(&identity, key, &endorser)?;
+			}
+
+			Self::deposit_event(Event::EndorsementsRejected(
+				who,
+				identity,
+				endorser,
+				list_of_attr_keys,
+			));
+			Ok(())
+		}
+
+		#[pallet::weight(<T as Config>::WeightInfo::attach_policy())]
 		pub fn attach_policy(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
@@ -265,79 +566,228 @@ pub mod pallet {
 			policy: T::AccountId,
 			name: Vec<u8>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
 
-			ensure!(name.len() <= VEC_MAX_LENGTH, Error::<T>::InputVectorTooLong);
+			let bounded_name: PolicyName<T> =
+				name.clone().try_into().map_err(|_| Error::<T>::InputVectorTooLong)?;
 
-			Self::ensure_owner(&identity, &who)?;
+			let who = Self::ensure_owner(&identity, &caller)?;
 
-			// There are two cases: 
+			// There are two cases:
 			// The first one is self-attachment.
 			// The second one is called by identity has a delegate of policy attachment.
 			if identity != object {
 				Self::ensure_delegate_of_policy_admin(&object, &identity)?;
 			}
-			
+
 			Self::ensure_policy_address(&policy)?;
 
 			if <PolicyOf<T>>::contains_key(&object, &policy) {
 				return Err(Error::<T>::PolicyAttachmentExists.into());
 			}
 
-			let new_policy: Policy<T::AccountId, Moment<T>> = Policy {
-				name: name.clone(),
+			Self::insert_policy_priority(&object, &policy, DEFAULT_POLICY_PRIORITY)?;
+
+			let new_policy: PolicyFor<T> = Policy {
+				name: bounded_name,
 				attached_by: identity.clone(),
 				attached_time: <T as Config>::Time::now(),
 			};
 
 			<PolicyOf<T>>::insert(&object, &policy, new_policy);
+			<AttachedBy<T>>::insert(&identity, (&object, &policy), ());
 
 			Self::deposit_event(Event::PolicyAttached(who, identity, object, policy, name));
 			Ok(())
 		}
 
-		#[pallet::weight(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::detach_policy())]
 		pub fn detach_policy(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			object: T::AccountId,
 			policy: T::AccountId,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
 
-			Self::ensure_owner(&identity, &who)?;
+			let who = Self::ensure_owner(&identity, &caller)?;
+
+			let attachment =
+				Self::policy_of(&object, &policy).ok_or(Error::<T>::PolicyAttachmentNotExists)?;
+			Self::remove_policy(&object, &policy, &attachment.attached_by);
+
+			Self::deposit_event(Event::PolicyDetached(who, identity, object, policy, Vec::new()));
+			Ok(())
+		}
+
+		/// Sets how the decisions of the policies attached to `object` are combined, by the
+		/// object itself or its `PolicyAdmin` delegate, like `attach_policy`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_combining_algorithm())]
+		pub fn set_combining_algorithm(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			object: T::AccountId,
+			algorithm: CombiningAlgorithm,
+		) -> DispatchResult {
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
+
+			let who = Self::ensure_owner(&identity, &caller)?;
+			if identity != object {
+				Self::ensure_delegate_of_policy_admin(&object, &identity)?;
+			}
+
+			<CombiningAlgorithmOf<T>>::insert(&object, algorithm);
+
+			Self::deposit_event(Event::CombiningAlgorithmSet(who, identity, object, algorithm));
+			Ok(())
+		}
+
+		/// Requests `action` on `object` for `subject`, evaluating every policy attached to the
+		/// object with at most `PolicyGasLimit` gas each, in priority order. Access is granted
+		/// if the decisions combined by the algorithm of the object are `Permit`.
+		/// The decision is recorded by `AccessGranted` or `AccessDenied` rather than an error.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::request_access(T::MaxPolicies::get()).saturating_add(
+				T::PolicyGasLimit::get().saturating_mul(T::MaxPolicies::get() as Weight)
+			)
+		)]
+		pub fn request_access(
+			origin: OriginFor<T>,
+			subject: T::AccountId,
+			object: T::AccountId,
+			action: Vec<u8>,
+			context: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
+
+			Self::ensure_owner(&subject, &caller)?;
+
+			let decision = Self::evaluate(&subject, &object, &action, &context)?;
+
+			if decision.granted {
+				Self::deposit_event(Event::AccessGranted(subject, object, action));
+			} else {
+				let denied_by = match decision.decision {
+					PolicyDecision::Deny | PolicyDecision::Indeterminate => decision
+						.results
+						.iter()
+						.find(|result| result.decision == decision.decision)
+						.map(|result| result.policy.clone()),
+					_ => None,
+				};
+				Self::deposit_event(Event::AccessDenied(subject, object, action, denied_by));
+			}
+
+			let actual_weight =
+				<T as Config>::WeightInfo::request_access(decision.results.len() as u32)
+					.saturating_add(decision.gas_consumed);
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Defines the schema of the attributes named `name`, the values `set_attributes`
+		/// sets are validated against from then on. Attributes already set are kept as is.
+		#[pallet::weight(<T as Config>::WeightInfo::set_attr_schema())]
+		pub fn set_attr_schema(
+			origin: OriginFor<T>,
+			name: AttrName<T>,
+			value_type: AttrType<EnumVariants<T>>,
+			constraints: AttrConstraints,
+		) -> DispatchResult {
+			T::SchemaOrigin::ensure_origin(origin)?;
+
+			Self::ensure_valid_attr_type(&value_type)?;
+			Self::ensure_valid_constraints(&value_type, &constraints)?;
+
+			<AttrSchemaOf<T>>::insert(&name, AttrSchema { value_type, constraints });
+
+			Self::deposit_event(Event::AttrSchemaSet(name.into_inner()));
+			Ok(())
+		}
+
+		/// Removes the schema of the attributes named `name`, their values become untyped.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_attr_schema())]
+		pub fn remove_attr_schema(origin: OriginFor<T>, name: AttrName<T>) -> DispatchResult {
+			T::SchemaOrigin::ensure_origin(origin)?;
+
+			ensure!(<AttrSchemaOf<T>>::contains_key(&name), Error::<T>::AttrSchemaNotExists);
+
+			<AttrSchemaOf<T>>::remove(&name);
+
+			Self::deposit_event(Event::AttrSchemaRemoved(name.into_inner()));
+			Ok(())
+		}
+
+		/// Sets the priority of a policy attached to `object`, by the object itself or its
+		/// `PolicyAdmin` delegate like `attach_policy`. Lower priorities are evaluated first.
+		#[pallet::weight(<T as Config>::WeightInfo::set_policy_priority())]
+		pub fn set_policy_priority(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			object: T::AccountId,
+			policy: T::AccountId,
+			priority: u32,
+		) -> DispatchResult {
+			let caller = <pallet_did::Pallet<T>>::ensure_caller(origin)?;
+
+			let who = Self::ensure_owner(&identity, &caller)?;
+			if identity != object {
+				Self::ensure_delegate_of_policy_admin(&object, &identity)?;
+			}
 
 			if !<PolicyOf<T>>::contains_key(&object, &policy) {
 				return Err(Error::<T>::PolicyAttachmentNotExists.into());
 			}
 
-			<PolicyOf<T>>::remove(&object, &policy);
+			<PolicyPriorityOf<T>>::mutate(&object, |priorities| {
+				priorities.retain(|(_, attached)| *attached != policy)
+			});
+			Self::insert_policy_priority(&object, &policy, priority)?;
 
-			Self::deposit_event(Event::PolicyDetached(who, identity, object, policy, Vec::new()));
+			Self::deposit_event(Event::PolicyPrioritySet(who, identity, object, policy, priority));
 			Ok(())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T>{
-	/// Validates if the AccountId 'actual_owner' owns the identity.
-	fn ensure_owner(identity: &T::AccountId, actual_owner: &T::AccountId) -> DispatchResult {
-		let result = <pallet_did::Pallet<T>>::is_owner(identity, actual_owner);
+	/// Validates that `caller` acts as the owner of the identity and returns its account.
+	/// Controllers of an identity act as its owner only through a proposal approved by their
+	/// threshold, dispatched from the identity origin of `pallet_did`.
+	fn ensure_owner(
+		identity: &T::AccountId,
+		caller: &Caller<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		let approval_required: DispatchError = pallet_did::Error::<T>::ApprovalRequired.into();
+		let deactivated: DispatchError = pallet_did::Error::<T>::Deactivated.into();
+		let result = <pallet_did::Pallet<T>>::authorize(caller, identity, None);
 		match result {
+			Err(e) if e == approval_required => Err(Error::<T>::ApprovalRequired.into()),
+			Err(e) if e == deactivated => Err(Error::<T>::IdentityDeactivated.into()),
 			Err(_) => Err(Error::<T>::NotOwner.into()),
-			_ => Ok(())
+			Ok(who) => Ok(who)
 		}
 	}
 
+	/// Inserts a policy of an object into its evaluation order, after the ones of the same
+	/// priority.
+	fn insert_policy_priority(
+		object: &T::AccountId,
+		policy: &T::AccountId,
+		priority: u32,
+	) -> DispatchResult {
+		<PolicyPriorityOf<T>>::try_mutate(object, |priorities| {
+			let index = priorities.partition_point(|(p, _)| *p <= priority);
+			priorities
+				.try_insert(index, (priority, policy.clone()))
+				.map_err(|_| Error::<T>::TooManyPolicies.into())
+		})
+	}
+
 	fn ensure_delegate_of_policy_admin(
 		object: &T::AccountId,
 		attacher: &T::AccountId,
 	) -> DispatchResult {
-		let result = <pallet_did::Pallet<T>>::valid_delegate(
-			object, 
-			&b"PolicyAdmin".to_vec(),
-			attacher
-		);
+		let result = <pallet_did::Pallet<T>>::valid_delegate(object, DELEGATE_POLICY_ADMIN, attacher);
 		match result {
 			Err(_) => Err(Error::<T>::InvalidDelegate.into()),
 			_ => Ok(())
@@ -351,6 +801,169 @@ impl<T: Config> Pallet<T>{
 		Ok(())
 	}
 
+	/// Evaluates the policies attached to `object` for a request of `subject`, as
+	/// `request_access` does. Called outside of a block, it dry-runs the policies.
+	pub fn evaluate(
+		subject: &T::AccountId,
+		object: &T::AccountId,
+		action: &[u8],
+		context: &[u8],
+	) -> Result<AccessDecision<T::AccountId>, DispatchError> {
+		ensure!(
+			action.len() <= T::MaxAttrNameLength::get() as usize &&
+				context.len() <= T::MaxContextLength::get() as usize,
+			Error::<T>::InputVectorTooLong
+		);
+
+		// Every policy is evaluated, so the weight does not depend on the algorithm.
+		let input = (POLICY_EVALUATE_SELECTOR, subject, object, action, context).encode();
+		let results: Vec<PolicyResult<T::AccountId>> = Self::policy_priorities(object)
+			.into_iter()
+			.map(|(_, policy)| Self::evaluate_policy(subject, policy, input.clone()))
+			.collect();
+
+		let algorithm = Self::combining_algorithm(object);
+		let decisions: Vec<PolicyDecision> = results.iter().map(|result| result.decision).collect();
+		let decision = algorithm.combine(&decisions);
+
+		Ok(AccessDecision {
+			granted: decision == PolicyDecision::Permit,
+			decision,
+			algorithm,
+			gas_consumed: results
+				.iter()
+				.fold(0, |gas: Weight, result| gas.saturating_add(result.gas_consumed)),
+			results,
+		})
+	}
+
+	/// Calls the `evaluate` message of a policy contract on behalf of the subject.
+	/// The call runs in a storage transaction which is always rolled back, so only the decision
+	/// of the policy is kept and whatever it wrote, including its events, is discarded.
+	fn evaluate_policy(
+		subject: &T::AccountId,
+		policy: T::AccountId,
+		input: Vec<u8>,
+	) -> PolicyResult<T::AccountId> {
+		let result = with_transaction(|| {
+			let result = <pallet_contracts::Pallet<T>>::bare_call(
+				subject.clone(),
+				policy.clone(),
+				Zero::zero(),
+				T::PolicyGasLimit::get(),
+				// Writes are rolled back, a policy cannot reserve any storage deposit either.
+				Some(Zero::zero()),
+				input,
+				false,
+			);
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(result))
+		});
+		let (decision, gas_consumed) = match result {
+			Ok(result) => {
+				let decision = match result.result {
+					Ok(output) if !output.did_revert() =>
+						PolicyDecision::decode(&mut &output.data[..])
+							.unwrap_or(PolicyDecision::Indeterminate),
+					_ => PolicyDecision::Indeterminate,
+				};
+				(decision, result.gas_consumed)
+			},
+			// No transaction can be opened this deep, the policy is not called.
+			Err(_) => (PolicyDecision::Indeterminate, 0),
+		};
+		PolicyResult { policy, decision, gas_consumed }
+	}
+
+	/// Value of an attribute typed by its schema, `Bytes` if it has no schema or was set
+	/// before its schema was defined and does not match it.
+	pub fn typed_attr(
+		identity: &T::AccountId,
+		name: &AttrName<T>,
+	) -> Option<TypedValue<T::AccountId>> {
+		let attr = Self::attr_of(identity, name)?;
+		let typed =
+			Self::attr_schema(name).and_then(|schema| Self::typed_value(&schema, &attr.value).ok());
+		Some(typed.unwrap_or_else(|| TypedValue::Bytes(attr.value.into_inner())))
+	}
+
+	/// Decodes an attribute value as the type of its schema and checks the constraints.
+	fn typed_value(
+		schema: &AttrSchemaFor<T>,
+		value: &[u8],
+	) -> Result<TypedValue<T::AccountId>, Error<T>> {
+		let typed = match &schema.value_type {
+			AttrType::String => {
+				str::from_utf8(value).map_err(|_| Error::<T>::InvalidAttrValue)?;
+				TypedValue::String(value.to_vec())
+			},
+			AttrType::U64 => TypedValue::U64(Self::decode_value(value)?),
+			AttrType::I64 => TypedValue::I64(Self::decode_value(value)?),
+			AttrType::Bool => TypedValue::Bool(Self::decode_value(value)?),
+			AttrType::Timestamp => TypedValue::Timestamp(Self::decode_value(value)?),
+			AttrType::Enum(variants) => {
+				ensure!(
+					variants.iter().any(|variant| &variant[..] == value),
+					Error::<T>::InvalidAttrValue
+				);
+				TypedValue::Enum(value.to_vec())
+			},
+			AttrType::Account => TypedValue::Account(Self::decode_value(value)?),
+		};
+
+		let constraints = &schema.constraints;
+		let number = match typed {
+			TypedValue::U64(number) | TypedValue::Timestamp(number) => Some(number as i128),
+			TypedValue::I64(number) => Some(number as i128),
+			_ => None,
+		};
+		if let Some(number) = number {
+			ensure!(
+				constraints.min.map_or(true, |min| number >= min) &&
+					constraints.max.map_or(true, |max| number <= max),
+				Error::<T>::InvalidAttrValue
+			);
+		}
+		if let TypedValue::String(string) = &typed {
+			ensure!(
+				constraints.max_length.map_or(true, |max| string.len() <= max as usize),
+				Error::<T>::InvalidAttrValue
+			);
+		}
+		Ok(typed)
+	}
+
+	/// Decodes a whole SCALE encoded attribute value.
+	fn decode_value<V: Decode>(value: &[u8]) -> Result<V, Error<T>> {
+		V::decode_all(&mut &value[..]).map_err(|_| Error::<T>::InvalidAttrValue)
+	}
+
+	/// The variants of an enum attribute type have to be distinct, and there has to be one.
+	fn ensure_valid_attr_type(value_type: &AttrType<EnumVariants<T>>) -> Result<(), Error<T>> {
+		if let AttrType::Enum(variants) = value_type {
+			ensure!(!variants.is_empty(), Error::<T>::InvalidAttrSchema);
+			let distinct =
+				variants.iter().enumerate().all(|(i, variant)| !variants[..i].contains(variant));
+			ensure!(distinct, Error::<T>::InvalidAttrSchema);
+		}
+		Ok(())
+	}
+
+	/// Bounds only apply to numbers and timestamps, and a maximum length only to strings.
+	fn ensure_valid_constraints(
+		value_type: &AttrType<EnumVariants<T>>,
+		constraints: &AttrConstraints,
+	) -> Result<(), Error<T>> {
+		let numeric = matches!(value_type, AttrType::U64 | AttrType::I64 | AttrType::Timestamp);
+		let bounded = constraints.min.is_some() || constraints.max.is_some();
+		let ordered = match (constraints.min, constraints.max) {
+			(Some(min), Some(max)) => min <= max,
+			_ => true,
+		};
+		let sized = matches!(value_type, AttrType::String) || constraints.max_length.is_none();
+		ensure!((numeric || !bounded) && ordered && sized, Error::<T>::InvalidAttrSchema);
+		Ok(())
+	}
+
 	fn check_attr_keys_duplication(list_of_attrs: &Vec<AttrInput>) -> bool {
 		for attr in list_of_attrs.iter() {
 			let dup = list_of_attrs.iter().filter(|a| *a.name == attr.name).count();
@@ -361,9 +974,21 @@ impl<T: Config> Pallet<T>{
 		false
 	}
 
+	/// Bounds a list of attribute keys by `MaxAttributesPerCall` and `MaxAttrNameLength`.
+	fn bounded_attr_keys(list_of_attr_keys: &[Vec<u8>]) -> Result<Vec<AttrName<T>>, Error<T>> {
+		ensure!(
+			list_of_attr_keys.len() <= T::MaxAttributesPerCall::get() as usize,
+			Error::<T>::InputVectorTooLong
+		);
+		list_of_attr_keys
+			.iter()
+			.map(|key| key.clone().try_into().map_err(|_| Error::<T>::InputVectorTooLong))
+			.collect()
+	}
+
 	fn check_attributes_existing(
-		identity: &T::AccountId, 
-		list_of_attr_keys: &Vec<Vec<u8>>
+		identity: &T::AccountId,
+		list_of_attr_keys: &[AttrName<T>],
 	) -> bool {
 		for key in list_of_attr_keys.iter() {
 			if !<AttrOf<T>>::contains_key(&identity, &key) {
@@ -373,18 +998,149 @@ impl<T: Config> Pallet<T>{
 		true
 	}
 
-	fn remove_endorsements_per_attribute(
-		identity: &T::AccountId,
-		attr_key: &Vec<u8>,
-	) {
+	/// Removes every endorsement of an attribute and returns how many were removed.
+	fn remove_endorsements_per_attribute(identity: &T::AccountId, attr_key: &AttrName<T>) -> u32 {
 		// Assume that the attribute exists, so no checking code is present.
-		loop {
-			// Delete one item at once
-			let result = <EndorsementOf<T>>::clear_prefix((identity, attr_key), 1, None);
-			// Redo until no one exist
-			if result.maybe_cursor.is_none() {
-				break;
+		// Endorsements are bounded by `MaxEndorsements` so they are removed at once.
+		let mut removed = 0;
+		for (endorser, _) in <EndorsementOf<T>>::drain_prefix((identity, attr_key)) {
+			<EndorsedBy<T>>::remove(&endorser, (identity, attr_key));
+			removed += 1;
+		}
+		<EndorsementCount<T>>::remove(identity, attr_key);
+		removed
+	}
+
+	/// Removes the endorsement by `endorser` of an attribute of `identity`.
+	fn remove_endorsement(
+		identity: &T::AccountId,
+		attr_key: &AttrName<T>,
+		endorser: &T::AccountId,
+	) -> DispatchResult {
+		let endorsement_key = (identity, attr_key, endorser);
+		ensure!(
+			<EndorsementOf<T>>::contains_key(endorsement_key),
+			Error::<T>::EndorsementNotExists
+		);
+		<EndorsementOf<T>>::remove(endorsement_key);
+		<EndorsedBy<T>>::remove(endorser, (identity, attr_key));
+		<EndorsementCount<T>>::mutate_exists(identity, attr_key, |count| {
+			*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+		});
+		Ok(())
+	}
+
+	/// Detaches a policy from an object, along its priority and the index of `attached_by`.
+	fn remove_policy(object: &T::AccountId, policy: &T::AccountId, attached_by: &T::AccountId) {
+		<PolicyOf<T>>::remove(object, policy);
+		<AttachedBy<T>>::remove(attached_by, (object, policy));
+		<PolicyPriorityOf<T>>::mutate(object, |priorities| {
+			priorities.retain(|(_, attached)| attached != policy)
+		});
+	}
+
+	/// Removes at most `limit` entries of a draining iterator, handing each to `then`.
+	/// Returns how many entries were removed and whether the iterator is exhausted.
+	fn drain_limited<I: Iterator>(
+		entries: I,
+		limit: u32,
+		mut then: impl FnMut(I::Item),
+	) -> (u32, bool) {
+		let mut loops = 0;
+		entries.take(limit as usize).for_each(|entry| {
+			then(entry);
+			loops += 1;
+		});
+		(loops, loops < limit)
+	}
+
+	/// Removes the attributes, endorsements and policy attachments of the identities
+	/// deactivated in `pallet_did`, one after another, until `remaining_weight` is used up.
+	/// This covers the endorsements an identity made and the policies it attached to other
+	/// identities as well. An identity only leaves `PurgeQueue` once nothing is left about it.
+	fn purge_deactivated(remaining_weight: Weight) -> Weight {
+		let item_weight = <T as Config>::WeightInfo::purge_item();
+		let mut used = T::DbWeight::get().reads(1);
+
+		while used.saturating_add(item_weight) <= remaining_weight {
+			let identity = match <PurgeQueue<T>>::iter_keys().next() {
+				Some(identity) => identity,
+				None => break,
+			};
+			let mut limit = (remaining_weight.saturating_sub(used) / item_weight.max(1))
+				.min(u32::MAX as Weight) as u32;
+
+			// Endorsements go first so that none outlives its attribute.
+			let mut complete = true;
+			for step in 0..6 {
+				let (loops, done) = match step {
+					0 => Self::drain_limited(
+						<EndorsementOf<T>>::drain_prefix((&identity,)),
+						limit,
+						|((name, endorser), _)| {
+							<EndorsedBy<T>>::remove(&endorser, (&identity, &name))
+						},
+					),
+					1 => {
+						let result = <EndorsementCount<T>>::clear_prefix(&identity, limit, None);
+						(result.loops, result.maybe_cursor.is_none())
+					},
+					2 => {
+						let result = <AttrOf<T>>::clear_prefix(&identity, limit, None);
+						(result.loops, result.maybe_cursor.is_none())
+					},
+					3 => Self::drain_limited(
+						<PolicyOf<T>>::drain_prefix(&identity),
+						limit,
+						|(policy, attachment)| {
+							<AttachedBy<T>>::remove(&attachment.attached_by, (&identity, &policy))
+						},
+					),
+					// Then the endorsements of other identities it made.
+					4 => Self::drain_limited(
+						<EndorsedBy<T>>::drain_prefix(&identity),
+						limit,
+						|((target, name), _)| {
+							let _ = Self::remove_endorsement(&target, &name, &identity);
+						},
+					),
+					// And the policies it attached to other objects.
+					_ => Self::drain_limited(
+						<AttachedBy<T>>::drain_prefix(&identity),
+						limit,
+						|((object, policy), _)| {
+							if Self::policy_of(&object, &policy)
+								.map_or(false, |attachment| attachment.attached_by == identity)
+							{
+								Self::remove_policy(&object, &policy, &identity);
+							}
+						},
+					),
+				};
+				used = used.saturating_add(item_weight.saturating_mul(loops as Weight));
+				limit = limit.saturating_sub(loops);
+				if !done || limit == 0 {
+					complete = done && step == 5;
+					break
+				}
 			}
+			if !complete {
+				break
+			}
+
+			<PolicyPriorityOf<T>>::remove(&identity);
+			<CombiningAlgorithmOf<T>>::remove(&identity);
+			<PurgeQueue<T>>::remove(&identity);
+			Self::deposit_event(Event::IdentityPurged(identity));
 		}
+
+		used
+	}
+}
+
+/// Schedules the removal of everything held about a deactivated identity.
+impl<T: Config> pallet_did::OnDeactivate<T::AccountId> for Pallet<T> {
+	fn on_deactivate(identity: &T::AccountId) {
+		<PurgeQueue<T>>::insert(identity, ());
 	}
 }
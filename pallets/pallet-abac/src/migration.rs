@@ -0,0 +1,245 @@
+//! Storage migrations of pallet-abac.
+
+use crate::{
+	types::{Attr, Policy},
+	AttachedBy, AttrName, AttrOf, AttrValue, Config, EndorsedBy, EndorsementCount, EndorsementOf,
+	Moment, Pallet, PolicyOf, PolicyPriorityOf,
+};
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::{
+	pallet_prelude::*,
+	storage::{unhashed, StoragePrefixedMap},
+	traits::Get,
+	StorageHasher,
+};
+use sp_std::vec::Vec;
+
+/// Wrapper for all migrations of this pallet, based on `StorageVersion`.
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = 0;
+
+	if version < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	if version < 2 {
+		weight = weight.saturating_add(v2::migrate::<T>());
+		StorageVersion::new(2).put::<Pallet<T>>();
+	}
+
+	if version < 3 {
+		weight = weight.saturating_add(v3::migrate::<T>());
+		StorageVersion::new(3).put::<Pallet<T>>();
+	}
+
+	weight
+}
+
+/// Records the number of endorsements before `migrate`, when testing the upgrade with
+/// try-runtime.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+	let endorsements = <EndorsementOf<T>>::iter_keys().count() as u32;
+	<Pallet<T>>::set_temp_storage(endorsements, "endorsements");
+	Ok(())
+}
+
+/// Checks that the endorsements left after `migrate` are counted, bounded and indexed, and
+/// that every attribute and policy decodes.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	ensure!(StorageVersion::get::<Pallet<T>>() == 3, "the storage version is not 3");
+	let before: u32 =
+		<Pallet<T>>::get_temp_storage("endorsements").ok_or("pre_upgrade did not run")?;
+
+	let mut endorsements = 0u32;
+	for (identity, key, endorser) in <EndorsementOf<T>>::iter_keys() {
+		endorsements += 1;
+		ensure!(
+			<EndorsedBy<T>>::contains_key(&endorser, (&identity, &key)),
+			"unindexed endorsement"
+		);
+	}
+	ensure!(endorsements <= before, "endorsements were added");
+	let mut counted = 0u32;
+	for (_identity, _key, count) in <EndorsementCount<T>>::iter() {
+		ensure!(count <= T::MaxEndorsements::get(), "endorsements exceed MaxEndorsements");
+		counted += count;
+	}
+	ensure!(counted == endorsements, "endorsements are miscounted");
+
+	ensure!(
+		<AttrOf<T>>::iter_values().count() == <AttrOf<T>>::iter_keys().count(),
+		"attributes do not decode"
+	);
+	for (object, policy) in <PolicyOf<T>>::iter_keys() {
+		ensure!(
+			<PolicyPriorityOf<T>>::get(&object).iter().any(|(_, p)| *p == policy),
+			"attached policy without priority"
+		);
+	}
+	ensure!(
+		<PolicyOf<T>>::iter_values().count() == <PolicyOf<T>>::iter_keys().count(),
+		"policies do not decode"
+	);
+	Ok(())
+}
+
+/// V1: Attributes and policies are bounded by the `Config` limits and the number of
+/// endorsements per attribute is tracked by `EndorsementCount`.
+/// Attributes exceeding the limits are dropped together with their endorsements, and so are
+/// the endorsements of an attribute beyond `MaxEndorsements`. Endorsements hold no deposit.
+mod v1 {
+	use super::*;
+
+	type OldAttr<T> = Attr<Moment<T>, Vec<u8>, Vec<u8>>;
+	type OldPolicy<T> = Policy<<T as frame_system::Config>::AccountId, Moment<T>, Vec<u8>>;
+
+	/// Storage keyed by names of any length, which the bounded keys fail to decode.
+	mod unbounded {
+		use super::*;
+
+		#[frame_support::storage_alias]
+		pub type AttrOf<T: Config> = StorageDoubleMap<
+			Pallet<T>,
+			Blake2_128Concat,
+			<T as frame_system::Config>::AccountId,
+			Blake2_128Concat,
+			Vec<u8>,
+			OldAttr<T>,
+		>;
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated: Weight = 0;
+		let mut dropped = Vec::new();
+
+		let old_attrs: Vec<_> = unbounded::AttrOf::<T>::drain().collect();
+		for (identity, name, old) in old_attrs {
+			translated += 1;
+			let bounded: Option<(AttrName<T>, AttrValue<T>)> =
+				name.clone().try_into().ok().zip(old.value.try_into().ok());
+			match bounded {
+				Some((name, value)) => {
+					let attr = Attr { name: name.clone(), value, updated_time: old.updated_time };
+					<AttrOf<T>>::insert(&identity, &name, attr);
+				},
+				None => dropped.push((identity, name)),
+			}
+		}
+		for (identity, name) in dropped.iter() {
+			let prefix = [
+				&<EndorsementOf<T>>::final_prefix()[..],
+				&Blake2_128Concat::hash(&identity.encode()),
+				&Blake2_128Concat::hash(&name.encode()),
+			]
+			.concat();
+			let _ = unhashed::clear_prefix(&prefix, None, None);
+		}
+
+		<PolicyOf<T>>::translate::<OldPolicy<T>, _>(|_object, _policy, old| {
+			translated += 1;
+			let name = old.name.try_into().ok()?;
+			Some(Policy { name, attached_by: old.attached_by, attached_time: old.attached_time })
+		});
+
+		// Endorsements beyond `MaxEndorsements` are trimmed, removing the endorsements of an
+		// attribute at once relies on the bound.
+		let max_endorsements = T::MaxEndorsements::get();
+		let mut counted: Weight = 0;
+		let mut trimmed = Vec::new();
+		for (identity, key, endorser) in <EndorsementOf<T>>::iter_keys() {
+			counted += 1;
+			<EndorsementCount<T>>::mutate(&identity, &key, |count| {
+				if *count < max_endorsements {
+					*count += 1;
+				} else {
+					trimmed.push((identity.clone(), key.clone(), endorser));
+				}
+			});
+		}
+		for (identity, key, endorser) in trimmed.iter() {
+			<EndorsementOf<T>>::remove((identity, key, endorser));
+		}
+
+		if !dropped.is_empty() {
+			log::warn!(
+				target: "runtime::abac",
+				"Dropped {} attributes exceeding the storage limits",
+				dropped.len(),
+			);
+		}
+		if !trimmed.is_empty() {
+			log::warn!(
+				target: "runtime::abac",
+				"Dropped {} endorsements exceeding MaxEndorsements",
+				trimmed.len(),
+			);
+		}
+
+		T::DbWeight::get().reads_writes(
+			translated + counted * 2 + dropped.len() as Weight,
+			translated + counted + dropped.len() as Weight + trimmed.len() as Weight,
+		)
+	}
+}
+
+/// V2: Policies are evaluated in the order of `PolicyPriorityOf`, which is bounded by
+/// `MaxPolicies`. Attached policies get priority 0, so they keep their former order.
+/// Attachments beyond `MaxPolicies` are dropped, their object could not be evaluated anyway.
+mod v2 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut attached: Weight = 0;
+		let mut dropped = Vec::new();
+
+		for (object, policy) in <PolicyOf<T>>::iter_keys() {
+			attached += 1;
+			let result = <PolicyPriorityOf<T>>::try_mutate(&object, |priorities| {
+				priorities.try_push((0, policy.clone()))
+			});
+			if result.is_err() {
+				dropped.push((object, policy));
+			}
+		}
+		for (object, policy) in dropped.iter() {
+			<PolicyOf<T>>::remove(object, policy);
+		}
+
+		if !dropped.is_empty() {
+			log::warn!(
+				target: "runtime::abac",
+				"Dropped {} policy attachments exceeding MaxPolicies",
+				dropped.len(),
+			);
+		}
+
+		T::DbWeight::get().reads_writes(attached * 2, attached + dropped.len() as Weight)
+	}
+}
+
+/// V3: The endorsements an identity made and the policies it attached are indexed by
+/// `EndorsedBy` and `AttachedBy`, so that they are purged along with the identity.
+mod v3 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut endorsed: Weight = 0;
+		for (target, name, endorser) in <EndorsementOf<T>>::iter_keys() {
+			endorsed += 1;
+			<EndorsedBy<T>>::insert(&endorser, (&target, &name), ());
+		}
+
+		let mut attached: Weight = 0;
+		for (object, policy, attachment) in <PolicyOf<T>>::iter() {
+			attached += 1;
+			<AttachedBy<T>>::insert(&attachment.attached_by, (&object, &policy), ());
+		}
+
+		T::DbWeight::get().reads_writes(endorsed + attached, endorsed + attached)
+	}
+}
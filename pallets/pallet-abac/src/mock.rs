@@ -0,0 +1,188 @@
+use crate as pallet_abac;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+	weights::{constants::WEIGHT_PER_SECOND, Weight},
+};
+use frame_system as system;
+use pallet_contracts::{DefaultAddressGenerator, DefaultContractAccessWeight, Frame, Schedule};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+	AccountId32, MultiSignature, MultiSigner,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Free balance of the endowed accounts.
+pub const ENDOWMENT: u64 = 1_000_000_000_000;
+/// Gas limit of the calls to the contracts instantiated by the tests.
+pub const GAS_LIMIT: Weight = 100_000_000_000;
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([2u8; 32]);
+pub const CHARLIE: AccountId32 = AccountId32::new([3u8; 32]);
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
+		Contracts: pallet_contracts,
+		Did: pallet_did,
+		Abac: pallet_abac,
+	}
+);
+
+parameter_types! {
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::simple_max(2 * WEIGHT_PER_SECOND);
+	pub ContractsSchedule: Schedule<Test> = Default::default();
+	pub const PermissionDelegateTypes: &'static [&'static [u8]] =
+		&[pallet_did::DELEGATE_ATTRIBUTE_ADMIN, pallet_abac::DELEGATE_POLICY_ADMIN];
+}
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = BlockWeights;
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+impl Convert<Weight, u64> for Test {
+	fn convert(weight: Weight) -> u64 {
+		weight
+	}
+}
+
+impl pallet_contracts::Config for Test {
+	type Time = Timestamp;
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = frame_support::traits::Nothing;
+	type WeightPrice = Self;
+	type WeightInfo = ();
+	type ChainExtension = ();
+	type Schedule = ContractsSchedule;
+	type CallStack = [Frame<Self>; 31];
+	type DeletionQueueDepth = ConstU32<1024>;
+	type DeletionWeightLimit = ConstU64<500_000_000_000>;
+	type DepositPerByte = ConstU64<1>;
+	type DepositPerItem = ConstU64<2>;
+	type AddressGenerator = DefaultAddressGenerator;
+	type ContractAccessWeight = DefaultContractAccessWeight<BlockWeights>;
+	type MaxCodeLen = ConstU32<{ 128 * 1024 }>;
+	type RelaxedMaxCodeLen = ConstU32<{ 256 * 1024 }>;
+	type MaxStorageKeyLen = ConstU32<128>;
+}
+
+impl pallet_did::Config for Test {
+	type Event = Event;
+	type Public = MultiSigner;
+	type Signature = MultiSignature;
+	type Time = Timestamp;
+	type Currency = Balances;
+	type DepositPerItem = ConstU64<0>;
+	type DepositPerByte = ConstU64<0>;
+	type MaxDelegateTypeLength = ConstU32<64>;
+	type MaxNameLength = ConstU32<64>;
+	type MaxValueLength = ConstU32<128>;
+	type MaxOperations = ConstU32<4>;
+	type PruningGracePeriod = ConstU64<10>;
+	type MaxGuardians = ConstU32<4>;
+	type DelegatePermissions = pallet_did::AttributeAdmin;
+	type PermissionDelegateTypes = PermissionDelegateTypes;
+	type MaxControllers = ConstU32<4>;
+	type OuterOrigin = Origin;
+	type Proposal = Call;
+	type MaxHandleLength = ConstU32<32>;
+	type HandlePeriod = ConstU64<100>;
+	type OnDeactivate = Abac;
+	type WeightInfo = ();
+}
+
+impl pallet_abac::Config for Test {
+	type Event = Event;
+	type Time = Timestamp;
+	type MaxEndorsements = ConstU32<4>;
+	type MaxAttributesPerCall = ConstU32<4>;
+	type MaxAttrNameLength = ConstU32<32>;
+	type MaxAttrValueLength = ConstU32<32>;
+	type MaxPolicyNameLength = ConstU32<32>;
+	type MaxPolicies = ConstU32<4>;
+	type PolicyGasLimit = ConstU64<GAS_LIMIT>;
+	type MaxContextLength = ConstU32<64>;
+	type MaxEnumVariants = ConstU32<4>;
+	type SchemaOrigin = frame_system::EnsureRoot<AccountId32>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(Default::default())
+}
+
+/// Test externalities with the attributes and endorsements of `genesis` provisioned.
+pub fn new_test_ext_with(genesis: pallet_abac::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, ENDOWMENT), (BOB, ENDOWMENT), (CHARLIE, ENDOWMENT)],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	genesis.assimilate_storage(&mut storage).unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,793 @@
+use crate::{
+	mock::*, types::*, AttachedBy, AttrName, EndorsedBy, EndorsementCount, EndorsementOf,
+	EnumVariants, PolicyOf, DEFAULT_POLICY_PRIORITY,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
+	storage::{storage_prefix, unhashed},
+	traits::{OnRuntimeUpgrade, StorageVersion},
+	Blake2_128Concat, StorageHasher,
+};
+use pallet_contracts_primitives::Code;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	AccountId32, DispatchError,
+};
+
+/// Instantiates the policy contract of a fixture in `fixtures/` and attaches it to `ALICE`.
+/// The same fixture can be attached several times, each instance has its own salt.
+fn attach_policy(fixture: &str) -> AccountId32 {
+	let wasm = wat::parse_file(["fixtures/", fixture, ".wat"].concat()).unwrap();
+	let salt = Abac::policy_priorities(&ALICE).len().encode();
+	let policy = Contracts::bare_instantiate(
+		ALICE,
+		0,
+		GAS_LIMIT,
+		None,
+		Code::Upload(wasm),
+		Vec::new(),
+		salt,
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id;
+	assert_ok!(Abac::attach_policy(
+		Origin::signed(ALICE),
+		ALICE,
+		ALICE,
+		policy.clone(),
+		b"policy".to_vec()
+	));
+	policy
+}
+
+/// Decision of the policies of `ALICE` on `BOB` reading it.
+fn evaluate() -> AccessDecision<AccountId32> {
+	Abac::evaluate(&BOB, &ALICE, b"read", &[]).unwrap()
+}
+
+#[test]
+fn permitting_policy_grants_access() {
+	new_test_ext().execute_with(|| {
+		let policy = attach_policy("policy_permit");
+
+		let decision = evaluate();
+		assert!(decision.granted);
+		assert_eq!(decision.decision, PolicyDecision::Permit);
+		assert_eq!(decision.results[0].policy, policy);
+		assert_eq!(decision.results[0].decision, PolicyDecision::Permit);
+
+		assert_ok!(Abac::request_access(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Vec::new()
+		));
+		System::assert_last_event(Event::Abac(crate::Event::AccessGranted(
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+		)));
+	});
+}
+
+#[test]
+fn denying_policy_denies_access() {
+	new_test_ext().execute_with(|| {
+		let policy = attach_policy("policy_deny");
+
+		let decision = evaluate();
+		assert!(!decision.granted);
+		assert_eq!(decision.decision, PolicyDecision::Deny);
+
+		assert_ok!(Abac::request_access(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Vec::new()
+		));
+		System::assert_last_event(Event::Abac(crate::Event::AccessDenied(
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Some(policy),
+		)));
+	});
+}
+
+#[test]
+fn trapping_policy_is_indeterminate() {
+	new_test_ext().execute_with(|| {
+		let policy = attach_policy("policy_trap");
+
+		let decision = evaluate();
+		assert!(!decision.granted);
+		assert_eq!(decision.decision, PolicyDecision::Indeterminate);
+
+		assert_ok!(Abac::request_access(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Vec::new()
+		));
+		System::assert_last_event(Event::Abac(crate::Event::AccessDenied(
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Some(policy),
+		)));
+	});
+}
+
+#[test]
+fn deny_overrides_permit() {
+	new_test_ext().execute_with(|| {
+		attach_policy("policy_permit");
+		let policy = attach_policy("policy_deny");
+
+		let decision = evaluate();
+		assert_eq!(decision.algorithm, CombiningAlgorithm::DenyOverrides);
+		assert_eq!(decision.decision, PolicyDecision::Deny);
+		assert_eq!(decision.results.len(), 2);
+		assert!(decision.results.iter().any(|result| result.policy == policy));
+	});
+}
+
+/// Sets the combining algorithm of the policies of `ALICE`.
+fn set_combining_algorithm(algorithm: CombiningAlgorithm) {
+	assert_ok!(Abac::set_combining_algorithm(Origin::signed(ALICE), ALICE, ALICE, algorithm));
+}
+
+/// Decisions of the policies of `ALICE`, in evaluation order.
+fn decisions(decision: &AccessDecision<AccountId32>) -> Vec<PolicyDecision> {
+	decision.results.iter().map(|result| result.decision).collect()
+}
+
+#[test]
+fn policies_are_evaluated_in_priority_order() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::FirstApplicable);
+		let permit = attach_policy("policy_permit");
+		let deny = attach_policy("policy_deny");
+
+		// Policies of the same priority are evaluated in the order they were attached.
+		let decision = evaluate();
+		assert_eq!(decisions(&decision), vec![PolicyDecision::Permit, PolicyDecision::Deny]);
+		assert_eq!(decision.decision, PolicyDecision::Permit);
+
+		assert_ok!(Abac::set_policy_priority(
+			Origin::signed(ALICE),
+			ALICE,
+			ALICE,
+			permit.clone(),
+			DEFAULT_POLICY_PRIORITY + 1
+		));
+		let decision = evaluate();
+		assert_eq!(decision.results[0].policy, deny);
+		assert_eq!(decision.results[1].policy, permit);
+		assert_eq!(decision.decision, PolicyDecision::Deny);
+
+		assert_noop!(
+			Abac::set_policy_priority(Origin::signed(ALICE), ALICE, ALICE, BOB, 0),
+			crate::Error::<Test>::PolicyAttachmentNotExists
+		);
+	});
+}
+
+#[test]
+fn first_applicable_decision_wins() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::FirstApplicable);
+		attach_policy("policy_not_applicable");
+		attach_policy("policy_permit");
+		attach_policy("policy_deny");
+
+		// Every policy is evaluated, the decisions after the first applicable one are ignored.
+		let decision = evaluate();
+		assert_eq!(
+			decisions(&decision),
+			vec![PolicyDecision::NotApplicable, PolicyDecision::Permit, PolicyDecision::Deny]
+		);
+		assert_eq!(decision.algorithm, CombiningAlgorithm::FirstApplicable);
+		assert_eq!(decision.decision, PolicyDecision::Permit);
+		assert!(decision.granted);
+	});
+}
+
+#[test]
+fn first_applicable_without_applicable_decision_is_not_applicable() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::FirstApplicable);
+		attach_policy("policy_not_applicable");
+		attach_policy("policy_not_applicable");
+
+		let decision = evaluate();
+		assert_eq!(decision.decision, PolicyDecision::NotApplicable);
+		assert!(!decision.granted);
+	});
+}
+
+#[test]
+fn only_one_applicable_without_applicable_decision_is_not_applicable() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::OnlyOneApplicable);
+		assert_eq!(evaluate().decision, PolicyDecision::NotApplicable);
+
+		attach_policy("policy_not_applicable");
+		let decision = evaluate();
+		assert_eq!(decision.decision, PolicyDecision::NotApplicable);
+		assert!(!decision.granted);
+	});
+}
+
+#[test]
+fn only_one_applicable_takes_the_single_applicable_decision() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::OnlyOneApplicable);
+		attach_policy("policy_not_applicable");
+		attach_policy("policy_permit");
+		attach_policy("policy_not_applicable");
+
+		let decision = evaluate();
+		assert_eq!(decision.decision, PolicyDecision::Permit);
+		assert!(decision.granted);
+	});
+}
+
+#[test]
+fn only_one_applicable_with_several_applicable_decisions_is_indeterminate() {
+	new_test_ext().execute_with(|| {
+		set_combining_algorithm(CombiningAlgorithm::OnlyOneApplicable);
+		attach_policy("policy_permit");
+		attach_policy("policy_not_applicable");
+		attach_policy("policy_permit");
+
+		// Even when the applicable decisions agree.
+		let decision = evaluate();
+		assert_eq!(decision.decision, PolicyDecision::Indeterminate);
+		assert!(!decision.granted);
+
+		assert_ok!(Abac::request_access(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Vec::new()
+		));
+		System::assert_last_event(Event::Abac(crate::Event::AccessDenied(
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			None,
+		)));
+	});
+}
+
+#[test]
+fn writes_of_policies_are_rolled_back() {
+	new_test_ext().execute_with(|| {
+		let policy = attach_policy("policy_write");
+		let stored = || Contracts::get_storage(policy.clone(), vec![0u8; 32]).unwrap();
+		assert_eq!(stored(), Some(b"init".to_vec()));
+
+		// The policy overwrote its storage before permitting the request.
+		assert_eq!(evaluate().decision, PolicyDecision::Permit);
+		assert_ok!(Abac::request_access(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			b"read".to_vec(),
+			Vec::new()
+		));
+
+		assert_eq!(stored(), Some(b"init".to_vec()));
+	});
+}
+
+#[test]
+fn controllers_set_attributes_through_approved_proposals() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::set_controllers(Origin::signed(ALICE), ALICE, vec![BOB, CHARLIE], 2));
+		let attrs = vec![AttrInput { name: b"role".to_vec(), value: b"admin".to_vec() }];
+		assert_noop!(
+			Abac::set_attributes(Origin::signed(BOB), ALICE, attrs.clone()),
+			crate::Error::<Test>::ApprovalRequired
+		);
+
+		let proposal = Box::new(Call::Abac(crate::Call::set_attributes {
+			identity: ALICE,
+			list_of_attrs: attrs,
+		}));
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, proposal.clone()));
+		assert_ok!(Did::approve_proposal(
+			Origin::signed(CHARLIE),
+			ALICE,
+			BlakeTwo256::hash_of(&proposal)
+		));
+		assert_ok!(Did::execute_proposal(Origin::signed(CHARLIE), ALICE, proposal));
+
+		assert_eq!(Abac::attr_of(&ALICE, attr_name(b"role")).unwrap().value.to_vec(), b"admin");
+	});
+}
+
+/// Defines the schema of the attributes named `name` as root.
+fn set_attr_schema(
+	name: &[u8],
+	value_type: AttrType<EnumVariants<Test>>,
+	constraints: AttrConstraints,
+) -> DispatchResult {
+	Abac::set_attr_schema(Origin::root(), attr_name(name), value_type, constraints)
+}
+
+/// Sets an attribute of `ALICE`.
+fn set_attr(name: &[u8], value: Vec<u8>) -> DispatchResultWithPostInfo {
+	Abac::set_attributes(
+		Origin::signed(ALICE),
+		ALICE,
+		vec![AttrInput { name: name.to_vec(), value }],
+	)
+}
+
+fn enum_variants(variants: &[&[u8]]) -> EnumVariants<Test> {
+	variants
+		.iter()
+		.map(|variant| variant.to_vec().try_into().unwrap())
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
+#[test]
+fn values_of_each_type_round_trip_through_their_schema() {
+	new_test_ext().execute_with(|| {
+		let cases: Vec<(&[u8], AttrType<EnumVariants<Test>>, Vec<u8>, TypedValue<AccountId32>)> = vec![
+			(
+				&b"name"[..],
+				AttrType::String,
+				b"alice".to_vec(),
+				TypedValue::String(b"alice".to_vec()),
+			),
+			(&b"age"[..], AttrType::U64, 42u64.encode(), TypedValue::U64(42)),
+			(&b"balance"[..], AttrType::I64, (-42i64).encode(), TypedValue::I64(-42)),
+			(&b"verified"[..], AttrType::Bool, true.encode(), TypedValue::Bool(true)),
+			(&b"joined"[..], AttrType::Timestamp, 1_000u64.encode(), TypedValue::Timestamp(1_000)),
+			(
+				&b"role"[..],
+				AttrType::Enum(enum_variants(&[b"admin", b"user"])),
+				b"user".to_vec(),
+				TypedValue::Enum(b"user".to_vec()),
+			),
+			(&b"manager"[..], AttrType::Account, BOB.encode(), TypedValue::Account(BOB)),
+		];
+
+		for (name, value_type, value, typed) in cases {
+			assert_ok!(set_attr_schema(name, value_type, Default::default()));
+			System::assert_last_event(Event::Abac(crate::Event::AttrSchemaSet(name.to_vec())));
+			assert_ok!(set_attr(name, value));
+			assert_eq!(Abac::typed_attr(&ALICE, &attr_name(name)), Some(typed));
+		}
+	});
+}
+
+#[test]
+fn values_not_matching_their_schema_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let cases: Vec<(&[u8], AttrType<EnumVariants<Test>>, Vec<u8>)> = vec![
+			(&b"name"[..], AttrType::String, vec![0xff, 0xfe]),
+			(&b"age"[..], AttrType::U64, 42u32.encode()),
+			(&b"balance"[..], AttrType::I64, (-42i64, 0u8).encode()),
+			(&b"verified"[..], AttrType::Bool, vec![2]),
+			(&b"joined"[..], AttrType::Timestamp, Vec::new()),
+			(&b"role"[..], AttrType::Enum(enum_variants(&[b"admin", b"user"])), b"guest".to_vec()),
+			(&b"manager"[..], AttrType::Account, vec![1; 31]),
+		];
+
+		for (name, value_type, value) in cases {
+			assert_ok!(set_attr_schema(name, value_type, Default::default()));
+			assert_noop!(set_attr(name, value), crate::Error::<Test>::InvalidAttrValue);
+		}
+	});
+}
+
+#[test]
+fn values_out_of_the_constraints_of_their_schema_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let bounds = AttrConstraints { min: Some(-10), max: Some(10), max_length: None };
+		assert_ok!(set_attr_schema(b"score", AttrType::I64, bounds));
+		assert_ok!(set_attr(b"score", (-10i64).encode()));
+		assert_ok!(set_attr(b"score", 10i64.encode()));
+		assert_noop!(set_attr(b"score", 11i64.encode()), crate::Error::<Test>::InvalidAttrValue);
+		assert_noop!(set_attr(b"score", (-11i64).encode()), crate::Error::<Test>::InvalidAttrValue);
+
+		let length = AttrConstraints { max_length: Some(3), ..Default::default() };
+		assert_ok!(set_attr_schema(b"code", AttrType::String, length));
+		assert_ok!(set_attr(b"code", b"abc".to_vec()));
+		assert_noop!(set_attr(b"code", b"abcd".to_vec()), crate::Error::<Test>::InvalidAttrValue);
+	});
+}
+
+#[test]
+fn invalid_schemas_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let invalid_schema = crate::Error::<Test>::InvalidAttrSchema;
+		assert_noop!(
+			set_attr_schema(b"role", AttrType::Enum(enum_variants(&[])), Default::default()),
+			invalid_schema
+		);
+		assert_noop!(
+			set_attr_schema(
+				b"role",
+				AttrType::Enum(enum_variants(&[b"admin", b"admin"])),
+				Default::default()
+			),
+			invalid_schema
+		);
+		let bounds = AttrConstraints { min: Some(1), max: Some(0), max_length: None };
+		assert_noop!(set_attr_schema(b"age", AttrType::U64, bounds), invalid_schema);
+		let bounds = AttrConstraints { min: Some(0), ..Default::default() };
+		assert_noop!(set_attr_schema(b"name", AttrType::String, bounds), invalid_schema);
+		let length = AttrConstraints { max_length: Some(3), ..Default::default() };
+		assert_noop!(set_attr_schema(b"age", AttrType::U64, length), invalid_schema);
+
+		assert_noop!(
+			Abac::set_attr_schema(
+				Origin::signed(ALICE),
+				attr_name(b"age"),
+				AttrType::U64,
+				Default::default()
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn oversized_schemas_are_rejected_when_decoded() {
+	let decode = |value_type: AttrType<Vec<Vec<u8>>>| {
+		AttrType::<EnumVariants<Test>>::decode(&mut &value_type.encode()[..])
+	};
+	// Beyond the `MaxEnumVariants` and `MaxAttrValueLength` of the mock.
+	assert!(decode(AttrType::Enum(vec![b"variant".to_vec(); 5])).is_err());
+	assert!(decode(AttrType::Enum(vec![vec![b'v'; 33]])).is_err());
+
+	assert_eq!(
+		decode(AttrType::Enum(vec![b"admin".to_vec(), b"user".to_vec()])).unwrap(),
+		AttrType::Enum(enum_variants(&[b"admin", b"user"]))
+	);
+}
+
+#[test]
+fn set_attributes_stores_nothing_if_a_value_does_not_match_its_schema() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr_schema(b"age", AttrType::U64, Default::default()));
+
+		assert_noop!(
+			Abac::set_attributes(
+				Origin::signed(ALICE),
+				ALICE,
+				vec![
+					AttrInput { name: b"name".to_vec(), value: b"alice".to_vec() },
+					AttrInput { name: b"age".to_vec(), value: b"42".to_vec() },
+				],
+			),
+			crate::Error::<Test>::InvalidAttrValue
+		);
+		assert!(Abac::attr_of(&ALICE, attr_name(b"name")).is_none());
+	});
+}
+
+#[test]
+fn redefined_schemas_retype_the_attributes_already_set() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr_schema(b"level", AttrType::U64, Default::default()));
+		assert_ok!(set_attr(b"level", 42u64.encode()));
+		assert_eq!(Abac::typed_attr(&ALICE, &attr_name(b"level")), Some(TypedValue::U64(42)));
+
+		assert_ok!(set_attr_schema(b"level", AttrType::I64, Default::default()));
+		assert_eq!(Abac::typed_attr(&ALICE, &attr_name(b"level")), Some(TypedValue::I64(42)));
+
+		// A value out of the new constraints is kept, but untyped.
+		let bounds = AttrConstraints { max: Some(10), ..Default::default() };
+		assert_ok!(set_attr_schema(b"level", AttrType::I64, bounds));
+		assert_eq!(
+			Abac::typed_attr(&ALICE, &attr_name(b"level")),
+			Some(TypedValue::Bytes(42u64.encode()))
+		);
+		assert_noop!(set_attr(b"level", 42i64.encode()), crate::Error::<Test>::InvalidAttrValue);
+		assert_ok!(set_attr(b"level", 7i64.encode()));
+		assert_eq!(Abac::typed_attr(&ALICE, &attr_name(b"level")), Some(TypedValue::I64(7)));
+	});
+}
+
+#[test]
+fn attributes_set_before_their_schema_are_kept_untyped_until_they_match() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr(b"age", b"old".to_vec()));
+		assert_eq!(
+			Abac::typed_attr(&ALICE, &attr_name(b"age")),
+			Some(TypedValue::Bytes(b"old".to_vec()))
+		);
+
+		assert_ok!(set_attr_schema(b"age", AttrType::U64, Default::default()));
+		assert_eq!(
+			Abac::typed_attr(&ALICE, &attr_name(b"age")),
+			Some(TypedValue::Bytes(b"old".to_vec()))
+		);
+		assert_ok!(set_attr(b"age", 42u64.encode()));
+		assert_eq!(Abac::typed_attr(&ALICE, &attr_name(b"age")), Some(TypedValue::U64(42)));
+
+		assert_ok!(Abac::remove_attr_schema(Origin::root(), attr_name(b"age")));
+		System::assert_last_event(Event::Abac(crate::Event::AttrSchemaRemoved(b"age".to_vec())));
+		assert_eq!(
+			Abac::typed_attr(&ALICE, &attr_name(b"age")),
+			Some(TypedValue::Bytes(42u64.encode()))
+		);
+		assert_noop!(
+			Abac::remove_attr_schema(Origin::root(), attr_name(b"age")),
+			crate::Error::<Test>::AttrSchemaNotExists
+		);
+	});
+}
+
+/// Endorses the attributes `names` of `ALICE` on behalf of `endorser`.
+fn endorse(endorser: &AccountId32, names: &[&[u8]]) -> DispatchResult {
+	Abac::endorse_attributes(
+		Origin::signed(endorser.clone()),
+		endorser.clone(),
+		ALICE,
+		names.iter().map(|name| name.to_vec()).collect(),
+		None,
+	)
+}
+
+fn endorsed(endorser: &AccountId32, name: &[u8]) -> bool {
+	Abac::endorsement_of((&ALICE, &attr_name(name), endorser)).is_some() &&
+		EndorsedBy::<Test>::contains_key(endorser, (ALICE, attr_name(name)))
+}
+
+#[test]
+fn unendorse_attributes_only_withdraws_the_endorsements_of_the_endorser() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr(b"role", b"admin".to_vec()));
+		assert_ok!(endorse(&BOB, &[b"role"]));
+		assert_ok!(endorse(&CHARLIE, &[b"role"]));
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 2);
+
+		assert_ok!(Abac::unendorse_attributes(
+			Origin::signed(BOB),
+			BOB,
+			ALICE,
+			vec![b"role".to_vec()]
+		));
+		System::assert_last_event(Event::Abac(crate::Event::AttributesUnendorsed(
+			BOB,
+			BOB,
+			ALICE,
+			vec![b"role".to_vec()],
+		)));
+		assert!(!endorsed(&BOB, b"role"));
+		assert!(endorsed(&CHARLIE, b"role"));
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 1);
+
+		assert_noop!(
+			Abac::unendorse_attributes(Origin::signed(BOB), BOB, ALICE, vec![b"role".to_vec()]),
+			crate::Error::<Test>::EndorsementNotExists
+		);
+		assert_noop!(
+			Abac::unendorse_attributes(Origin::signed(BOB), BOB, ALICE, vec![b"level".to_vec()]),
+			crate::Error::<Test>::InvalidAttributes
+		);
+	});
+}
+
+#[test]
+fn reject_endorsements_removes_the_endorsements_of_an_endorser() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr(b"role", b"admin".to_vec()));
+		assert_ok!(set_attr(b"level", b"high".to_vec()));
+		assert_ok!(endorse(&BOB, &[b"role", b"level"]));
+		assert_ok!(endorse(&CHARLIE, &[b"role"]));
+
+		// Only the owner of the attributes rejects their endorsements.
+		assert_noop!(
+			Abac::reject_endorsements(Origin::signed(CHARLIE), ALICE, BOB, vec![b"role".to_vec()]),
+			crate::Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Abac::reject_endorsements(
+			Origin::signed(ALICE),
+			ALICE,
+			BOB,
+			vec![b"role".to_vec(), b"level".to_vec()]
+		));
+		System::assert_last_event(Event::Abac(crate::Event::EndorsementsRejected(
+			ALICE,
+			ALICE,
+			BOB,
+			vec![b"role".to_vec(), b"level".to_vec()],
+		)));
+		assert!(!endorsed(&BOB, b"role"));
+		assert!(!endorsed(&BOB, b"level"));
+		assert!(endorsed(&CHARLIE, b"role"));
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 1);
+		assert!(!EndorsementCount::<Test>::contains_key(ALICE, attr_name(b"level")));
+
+		assert_noop!(
+			Abac::reject_endorsements(Origin::signed(ALICE), ALICE, BOB, vec![b"role".to_vec()]),
+			crate::Error::<Test>::EndorsementNotExists
+		);
+	});
+}
+
+#[test]
+fn rejected_endorsements_free_room_for_others() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(set_attr(b"role", b"admin".to_vec()));
+		// The `MaxEndorsements` of the mock.
+		let endorsers: Vec<AccountId32> = (0..4).map(|i| AccountId32::new([10 + i; 32])).collect();
+		for endorser in &endorsers {
+			assert_ok!(endorse(endorser, &[b"role"]));
+		}
+		// Endorsing again only renews an endorsement.
+		assert_ok!(endorse(&endorsers[0], &[b"role"]));
+		assert_noop!(endorse(&BOB, &[b"role"]), crate::Error::<Test>::TooManyEndorsements);
+
+		assert_ok!(Abac::reject_endorsements(
+			Origin::signed(ALICE),
+			ALICE,
+			endorsers[0].clone(),
+			vec![b"role".to_vec()]
+		));
+		assert_ok!(endorse(&BOB, &[b"role"]));
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 4);
+	});
+}
+
+#[test]
+fn genesis_provisions_attributes_and_their_endorsements() {
+	let genesis = crate::GenesisConfig::<Test> {
+		attributes: vec![(ALICE, b"role".to_vec(), b"admin".to_vec())],
+		// An endorsement listed twice is counted once.
+		endorsements: vec![
+			(BOB, ALICE, b"role".to_vec()),
+			(CHARLIE, ALICE, b"role".to_vec()),
+			(BOB, ALICE, b"role".to_vec()),
+		],
+	};
+	new_test_ext_with(genesis).execute_with(|| {
+		let name = attr_name(b"role");
+		assert_eq!(Abac::attr_of(ALICE, &name).unwrap().value.into_inner(), b"admin");
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, &name), 2);
+		for endorser in [BOB, CHARLIE] {
+			let endorsement = Abac::endorsement_of((&ALICE, &name, &endorser)).unwrap();
+			assert_eq!(endorsement.validity, u64::from(u32::MAX));
+			assert!(EndorsedBy::<Test>::contains_key(endorser, (ALICE, &name)));
+		}
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis endorsements are of genesis attributes")]
+fn genesis_fails_with_endorsements_of_unknown_attributes() {
+	let genesis = crate::GenesisConfig::<Test> {
+		endorsements: vec![(BOB, ALICE, b"role".to_vec())],
+		..Default::default()
+	};
+	new_test_ext_with(genesis);
+}
+
+#[test]
+#[should_panic(expected = "genesis endorsements exceed MaxEndorsements")]
+fn genesis_fails_with_endorsements_beyond_max_endorsements() {
+	// One more than the `MaxEndorsements` of the mock.
+	let endorsements = (0..5)
+		.map(|i| (AccountId32::new([10 + i; 32]), ALICE, b"role".to_vec()))
+		.collect();
+	let genesis = crate::GenesisConfig::<Test> {
+		attributes: vec![(ALICE, b"role".to_vec(), b"admin".to_vec())],
+		endorsements,
+	};
+	new_test_ext_with(genesis);
+}
+
+/// Stores a value in the format preceding the storage version 1, under the keys of a map of
+/// the pallet given as their encodings.
+fn insert_legacy<V: Encode>(storage: &[u8], keys: &[Vec<u8>], value: &V) {
+	let mut key = storage_prefix(b"Abac", storage).to_vec();
+	for encoded in keys {
+		key.extend(Blake2_128Concat::hash(encoded));
+	}
+	unhashed::put(&key, value);
+}
+
+/// Stores the attribute `name` of `ALICE` endorsed by `endorsers` in the legacy format.
+fn insert_legacy_attribute(name: &[u8], endorsers: u8) {
+	let attribute = Attr { name: name.to_vec(), value: b"value".to_vec(), updated_time: 0u64 };
+	insert_legacy(b"AttrOf", &[ALICE.encode(), name.encode()], &attribute);
+	for i in 0..endorsers {
+		let endorser = AccountId32::new([10 + i; 32]);
+		let endorsement = Endorsement { validity: u64::MAX, endorsed_time: 0u64 };
+		insert_legacy(
+			b"EndorsementOf",
+			&[ALICE.encode(), name.encode(), endorser.encode()],
+			&endorsement,
+		);
+	}
+}
+
+fn attr_name(name: &[u8]) -> AttrName<Test> {
+	name.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn migration_counts_and_indexes_the_endorsements() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Abac>();
+		insert_legacy_attribute(b"role", 2);
+
+		Abac::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Abac>(), 3);
+		assert_eq!(Abac::attr_of(ALICE, attr_name(b"role")).unwrap().value.into_inner(), b"value");
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 2);
+		for i in 0..2 {
+			let endorser = AccountId32::new([10 + i; 32]);
+			assert!(EndorsedBy::<Test>::contains_key(endorser, (ALICE, attr_name(b"role"))));
+		}
+	});
+}
+
+#[test]
+fn migration_trims_endorsements_beyond_max_endorsements() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Abac>();
+		insert_legacy_attribute(b"role", 6);
+
+		Abac::on_runtime_upgrade();
+
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 4);
+		assert_eq!(EndorsementOf::<Test>::iter_keys().count(), 4);
+		// Only the endorsements kept are indexed.
+		assert_eq!(EndorsedBy::<Test>::iter_keys().count(), 4);
+		for (identity, name, endorser) in EndorsementOf::<Test>::iter_keys() {
+			assert!(EndorsedBy::<Test>::contains_key(endorser, (identity, name)));
+		}
+	});
+}
+
+#[test]
+fn migration_drops_oversized_attributes_with_their_endorsements() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Abac>();
+		insert_legacy_attribute(b"role", 1);
+		insert_legacy_attribute(&[b'a'; 33], 2);
+
+		Abac::on_runtime_upgrade();
+
+		assert_eq!(crate::AttrOf::<Test>::iter_keys().count(), 1);
+		assert_eq!(EndorsementOf::<Test>::iter_keys().count(), 1);
+		assert_eq!(EndorsedBy::<Test>::iter_keys().count(), 1);
+		assert_eq!(EndorsementCount::<Test>::get(ALICE, attr_name(b"role")), 1);
+	});
+}
+
+#[test]
+fn migration_prioritizes_and_indexes_the_attached_policies() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Abac>();
+		let policy = Policy { name: b"policy".to_vec(), attached_by: BOB, attached_time: 0u64 };
+		insert_legacy(b"PolicyOf", &[ALICE.encode(), CHARLIE.encode()], &policy);
+
+		Abac::on_runtime_upgrade();
+
+		assert_eq!(PolicyOf::<Test>::get(ALICE, CHARLIE).unwrap().attached_by, BOB);
+		assert_eq!(Abac::policy_priorities(ALICE).into_inner(), vec![(0, CHARLIE)]);
+		assert!(AttachedBy::<Test>::contains_key(BOB, (ALICE, CHARLIE)));
+	});
+}
@@ -1,4 +1,4 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::sp_std::vec::Vec;
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
@@ -6,11 +6,21 @@ use sp_core::RuntimeDebug;
 /// Access control attribute.
 /// This is only the type of record in pallet runtime storage.
 #[derive(
-	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, TypeInfo, RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	Default,
+	TypeInfo,
+	RuntimeDebug,
+	MaxEncodedLen,
 )]
-pub struct Attr<Moment> {
-	pub name: Vec<u8>,
-	pub value: Vec<u8>,
+pub struct Attr<Moment, Name, Value> {
+	pub name: Name,
+	pub value: Value,
 	pub updated_time: Moment,
 }
 
@@ -24,10 +34,68 @@ pub struct AttrInput {
   pub value: Vec<u8>,
 }
 
+/// Type of the value of an attribute, as defined by its schema.
+/// Values are stored as raw UTF-8 for `String`, the variant itself for `Enum`, and
+/// SCALE encoded otherwise, `Timestamp` in milliseconds like `pallet_timestamp`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+pub enum AttrType<Variants> {
+	String,
+	U64,
+	I64,
+	Bool,
+	Timestamp,
+	/// One of the listed variants.
+	Enum(Variants),
+	Account,
+}
+
+/// Optional constraints on the values of an attribute, beyond its type.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+pub struct AttrConstraints {
+	/// Lower bound of numbers and timestamps, inclusive.
+	pub min: Option<i128>,
+	/// Upper bound of numbers and timestamps, inclusive.
+	pub max: Option<i128>,
+	/// Maximum length of strings in bytes.
+	pub max_length: Option<u32>,
+}
+
+/// Schema of the attributes of a name, whichever identity holds them.
+/// This is only the type of record in pallet runtime storage.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+pub struct AttrSchema<Variants> {
+	pub value_type: AttrType<Variants>,
+	pub constraints: AttrConstraints,
+}
+
+/// Value of an attribute decoded according to its schema.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum TypedValue<AccountId> {
+	/// Value of an attribute without schema, or set before its schema was defined.
+	Bytes(Vec<u8>),
+	String(Vec<u8>),
+	U64(u64),
+	I64(i64),
+	Bool(bool),
+	Timestamp(u64),
+	Enum(Vec<u8>),
+	Account(AccountId),
+}
+
 /// Endorsements for an access control attribute
 /// This is only the type of record in pallet runtime storage.
 #[derive(
-	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, TypeInfo, RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	Default,
+	TypeInfo,
+	RuntimeDebug,
+	MaxEncodedLen,
 )]
 pub struct Endorsement<BlockNumber, Moment> {
 	pub validity: BlockNumber,
@@ -37,10 +105,106 @@ pub struct Endorsement<BlockNumber, Moment> {
 /// Policy associated with an identity (role as access control object).
 /// This is only the type of record in pallet runtime storage.
 #[derive(
-	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, TypeInfo, RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	Default,
+	TypeInfo,
+	RuntimeDebug,
+	MaxEncodedLen,
 )]
-pub struct Policy<AccountId, Moment> {
-	pub name: Vec<u8>,
+pub struct Policy<AccountId, Moment, Name> {
+	pub name: Name,
 	pub attached_by: AccountId,
 	pub attached_time: Moment,
 }
+
+/// Decision of a policy contract, or of the policies of an object once combined.
+/// `Deny` and `Permit` are encoded like `false` and `true`, so policies returning a
+/// `bool` keep working.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+pub enum PolicyDecision {
+	Deny,
+	Permit,
+	/// The policy does not apply to the request.
+	NotApplicable,
+	/// The policy could not be evaluated, it failed, reverted or returned garbage.
+	Indeterminate,
+}
+
+/// How the decisions of the policies attached to an object are combined.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, RuntimeDebug, MaxEncodedLen)]
+pub enum CombiningAlgorithm {
+	/// Any `Deny` wins, then any `Indeterminate`, then any `Permit`.
+	DenyOverrides,
+	/// Any `Permit` wins, then any `Indeterminate`, then any `Deny`.
+	PermitOverrides,
+	/// The first applicable decision in priority order wins.
+	FirstApplicable,
+	/// The single applicable decision wins, several applicable ones are `Indeterminate`.
+	OnlyOneApplicable,
+}
+
+impl Default for CombiningAlgorithm {
+	fn default() -> Self {
+		Self::DenyOverrides
+	}
+}
+
+impl CombiningAlgorithm {
+	/// Combines the decisions of the policies of an object, given in priority order.
+	/// Without any applicable decision the result is `NotApplicable`.
+	pub fn combine(&self, decisions: &[PolicyDecision]) -> PolicyDecision {
+		use PolicyDecision::*;
+		let any = |decision: PolicyDecision| decisions.contains(&decision);
+		match self {
+			Self::DenyOverrides if any(Deny) => Deny,
+			Self::PermitOverrides if any(Permit) => Permit,
+			Self::DenyOverrides | Self::PermitOverrides if any(Indeterminate) => Indeterminate,
+			Self::DenyOverrides if any(Permit) => Permit,
+			Self::PermitOverrides if any(Deny) => Deny,
+			Self::DenyOverrides | Self::PermitOverrides => NotApplicable,
+			Self::FirstApplicable => decisions
+				.iter()
+				.copied()
+				.find(|decision| *decision != NotApplicable)
+				.unwrap_or(NotApplicable),
+			Self::OnlyOneApplicable => {
+				let mut applicable =
+					decisions.iter().filter(|decision| **decision != NotApplicable);
+				match (applicable.next(), applicable.next()) {
+					(None, _) => NotApplicable,
+					(Some(decision), None) => *decision,
+					(Some(_), Some(_)) => Indeterminate,
+				}
+			},
+		}
+	}
+}
+
+/// Result of the evaluation of a policy contract for an access request.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct PolicyResult<AccountId> {
+	pub policy: AccountId,
+	pub decision: PolicyDecision,
+	pub gas_consumed: u64,
+}
+
+/// Access decision of the policies attached to an object.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct AccessDecision<AccountId> {
+	/// Whether the combined decision is `Permit`.
+	pub granted: bool,
+	/// Decision of the policies combined by `algorithm`.
+	pub decision: PolicyDecision,
+	/// Combining algorithm of the object.
+	pub algorithm: CombiningAlgorithm,
+	/// Results of the policies, in priority order.
+	pub results: Vec<PolicyResult<AccountId>>,
+	/// Gas consumed by all the evaluations.
+	pub gas_consumed: u64,
+}
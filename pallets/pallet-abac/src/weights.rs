@@ -0,0 +1,381 @@
+//! Weights for pallet_abac
+//!
+//! These weights were not measured. They are conservative estimates: round execution times
+//! and the storage reads and writes of the most expensive path of each extrinsic. The gas of
+//! the policies called by `request_access` is not included, the call adds `PolicyGasLimit`
+//! for each of `MaxPolicies` and refunds what they did not consume. Replace this file with
+//! the output of the benchmarks on reference hardware:
+//!
+//! ./target/release/node-template benchmark pallet --chain=dev --steps=50 --repeat=20
+//!   --pallet=pallet_abac --extrinsic=* --execution=wasm --wasm-execution=compiled
+//!   --heap-pages=4096 --output=./pallets/pallet-abac/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_abac.
+pub trait WeightInfo {
+	fn set_attributes(n: u32, e: u32, ) -> Weight;
+	fn clear_attributes(n: u32, ) -> Weight;
+	fn endorse_attributes(n: u32, ) -> Weight;
+	fn unendorse_attributes(n: u32, ) -> Weight;
+	fn reject_endorsements(n: u32, ) -> Weight;
+	fn attach_policy() -> Weight;
+	fn detach_policy() -> Weight;
+	fn purge_item() -> Weight;
+	fn request_access(p: u32, ) -> Weight;
+	fn set_combining_algorithm() -> Weight;
+	fn set_policy_priority() -> Weight;
+	fn set_attr_schema() -> Weight;
+	fn remove_attr_schema() -> Weight;
+	fn typed_attr() -> Weight;
+}
+
+/// Weights for pallet_abac using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:0)
+	// Storage: PalletAbac AttrOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:0 w:1)
+	// For each of the `e` endorsements removed:
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn set_attributes(n: u32, e: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(e as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:1)
+	fn clear_attributes(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn endorse_attributes(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn unendorse_attributes(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn reject_endorsements(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: Contracts ContractInfoOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletAbac AttachedBy (r:0 w:1)
+	fn attach_policy() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:1)
+	// Storage: PalletAbac AttachedBy (r:0 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	fn detach_policy() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletAbac PurgeQueue (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:1 w:1)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:0 w:1)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:0 w:1)
+	fn purge_item() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:0)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `p` policies, besides the `PolicyGasLimit` its call may consume:
+	// Storage: Contracts ContractInfoOf (r:1 w:0)
+	// Storage: Contracts CodeStorage (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	fn request_access(p: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:0 w:1)
+	fn set_combining_algorithm() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:0)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	fn set_policy_priority() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrSchemaOf (r:0 w:1)
+	fn set_attr_schema() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:1)
+	fn remove_attr_schema() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:0)
+	fn typed_attr() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:0)
+	// Storage: PalletAbac AttrOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:0 w:1)
+	// For each of the `e` endorsements removed:
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn set_attributes(n: u32, e: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(e as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(e as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:1)
+	fn clear_attributes(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn endorse_attributes(n: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn unendorse_attributes(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// For each of the `n` attributes:
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:0 w:1)
+	fn reject_endorsements(n: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: Contracts ContractInfoOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletAbac AttachedBy (r:0 w:1)
+	fn attach_policy() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:1)
+	// Storage: PalletAbac AttachedBy (r:0 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	fn detach_policy() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletAbac PurgeQueue (r:1 w:1)
+	// Storage: PalletAbac EndorsedBy (r:1 w:1)
+	// Storage: PalletAbac EndorsementOf (r:1 w:1)
+	// Storage: PalletAbac EndorsementCount (r:1 w:1)
+	// Storage: PalletAbac PolicyPriorityOf (r:0 w:1)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:0 w:1)
+	fn purge_item() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:0)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// For each of the `p` policies, besides the `PolicyGasLimit` its call may consume:
+	// Storage: Contracts ContractInfoOf (r:1 w:0)
+	// Storage: Contracts CodeStorage (r:1 w:0)
+	// Storage: System Account (r:1 w:0)
+	fn request_access(p: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((50_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletAbac CombiningAlgorithmOf (r:0 w:1)
+	fn set_combining_algorithm() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:2 w:0)
+	// Storage: PalletDid OwnerOf (r:2 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletAbac PolicyOf (r:1 w:0)
+	// Storage: PalletAbac PolicyPriorityOf (r:1 w:1)
+	fn set_policy_priority() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrSchemaOf (r:0 w:1)
+	fn set_attr_schema() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:1)
+	fn remove_attr_schema() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletAbac AttrOf (r:1 w:0)
+	// Storage: PalletAbac AttrSchemaOf (r:1 w:0)
+	fn typed_attr() -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+	}
+}
@@ -0,0 +1,51 @@
+//! Runtime API definition required by DID RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding DID access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_did::types::{AttributeRecord, DelegateRecord, DidDocument, DocumentVersion};
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The API to resolve identities of `pallet_did`.
+	pub trait DidApi<AccountId, BlockNumber, Moment> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Resolves an identity into its W3C DID document.
+		fn resolve(identity: AccountId) -> DidDocument<AccountId, BlockNumber>;
+
+		/// Returns the nonce expected by the next off-chain signed transaction of an identity.
+		fn signing_nonce(identity: AccountId) -> u64;
+
+		/// Lists the attributes of an identity, expired ones only if `include_expired` is set.
+		fn attributes(
+			identity: AccountId,
+			include_expired: bool,
+		) -> Vec<AttributeRecord<BlockNumber, Moment>>;
+
+		/// Lists the delegates of an identity, optionally of a single type.
+		fn delegates(
+			identity: AccountId,
+			delegate_type: Option<Vec<u8>>,
+		) -> Vec<DelegateRecord<AccountId, BlockNumber>>;
+
+		/// Returns the latest document version of an identity, none if it never changed.
+		fn document_version(identity: AccountId) -> Option<DocumentVersion<BlockNumber, Moment>>;
+
+		/// Returns the identity controlled by an Ethereum address.
+		fn ethereum_account(address: H160) -> AccountId;
+
+		/// Resolves the identity keyed by an Ethereum address into its W3C DID document.
+		fn resolve_ethereum(address: H160) -> DidDocument<AccountId, BlockNumber>;
+
+		/// Returns the identity a handle points to, if registered and not expired.
+		fn resolve_handle(handle: Vec<u8>) -> Option<AccountId>;
+	}
+}
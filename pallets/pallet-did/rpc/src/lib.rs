@@ -0,0 +1,493 @@
+//! Node-specific RPC methods for interaction with DID.
+//! Documents are folded by the runtime through `DidApi` and rendered here as JSON-LD.
+//! Former versions of a document are resolved from historical state, which requires a node
+//! keeping the state of the blocks in question, e.g. an archive node.
+//!
+//! Identities are `did:substrate:<ss58>`, except the ones keyed by an Ethereum address which
+//! are `did:ethr:0x<address>`. Their account is derived from the address by
+//! `pallet_did::Pallet::ethereum_account` and is listed in `alsoKnownAs` as a `did:substrate`
+//! DID. Accounts of Ethereum addresses are listed as `EcdsaSecp256k1RecoveryMethod2020`
+//! methods whose `blockchainAccountId` is the CAIP-10 id of the address.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_did::types::{AttributeRecord, DelegateRecord, DidDocument, PublicKey};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, hexdisplay::HexDisplay, H160};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor, One, Saturating},
+};
+
+pub use pallet_did_rpc_runtime_api::DidApi as DidRuntimeApi;
+
+const RUNTIME_ERROR: i32 = 1;
+const INVALID_OPTIONS: i32 = 2;
+const VERSION_NOT_FOUND: i32 = 3;
+const HANDLE_NOT_FOUND: i32 = 4;
+
+/// DID method prefix of the identities managed by `pallet_did`.
+pub const DID_METHOD: &str = "did:substrate";
+/// DID method prefix of the identities keyed by an Ethereum address.
+pub const ETHR_DID_METHOD: &str = "did:ethr";
+/// CAIP-2 chain of the Ethereum addresses given as `blockchainAccountId`. Ethereum signatures
+/// are bound to this chain by its genesis hash rather than a chain id, so the addresses are
+/// given on mainnet, the default network of `did:ethr`.
+pub const ETHEREUM_CHAIN: &str = "eip155:1";
+/// URI scheme of the handles listed in `alsoKnownAs`.
+pub const HANDLE_SCHEME: &str = "handle";
+
+/// Options of a DID resolution, selecting a former version of the document.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ResolutionOptions {
+	/// Version to resolve, as given by `versionId` in the document metadata.
+	pub version_id: Option<String>,
+	/// Timestamp in milliseconds, the version current at that time is resolved.
+	pub version_time: Option<u64>,
+}
+
+/// DID RPC methods.
+#[rpc(client, server)]
+pub trait DidApi<BlockHash, AccountId, BlockNumber, Moment> {
+	/// Resolves an identity into its W3C DID document at a given block.
+	///
+	/// With `versionId` or `versionTime` options, the version of the document selected among
+	/// the changes up to that block is resolved instead.
+	/// Returns a DID resolution result, the document itself is JSON-LD.
+	#[method(name = "did_resolve")]
+	fn resolve(
+		&self,
+		identity: AccountId,
+		at: Option<BlockHash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value>;
+
+	/// Returns the nonce expected by the next off-chain signed transaction of an identity.
+	#[method(name = "did_signingNonce")]
+	fn signing_nonce(&self, identity: AccountId, at: Option<BlockHash>) -> RpcResult<u64>;
+
+	/// Lists the attributes of an identity with their validity status at a given block.
+	///
+	/// Expired and revoked attributes are only listed if `include_expired` is set.
+	#[method(name = "did_attributes")]
+	fn attributes(
+		&self,
+		identity: AccountId,
+		include_expired: bool,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Value>>;
+
+	/// Lists the delegates of an identity with their validity status at a given block.
+	///
+	/// Only delegates of `delegate_type` are listed if it is given.
+	#[method(name = "did_delegates")]
+	fn delegates(
+		&self,
+		identity: AccountId,
+		delegate_type: Option<String>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Value>>;
+
+	/// Resolves the identity a handle points to, as `did_resolve` does.
+	#[method(name = "did_resolveHandle")]
+	fn resolve_handle(
+		&self,
+		handle: String,
+		at: Option<BlockHash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value>;
+
+	/// Returns the SS58 address of the identity controlled by an Ethereum address.
+	#[method(name = "did_ethereumAccount")]
+	fn ethereum_account(&self, address: H160, at: Option<BlockHash>) -> RpcResult<String>;
+
+	/// Resolves the `did:ethr` identity of an Ethereum address, as `did_resolve` does.
+	#[method(name = "did_resolveEthereum")]
+	fn resolve_ethereum(
+		&self,
+		address: H160,
+		at: Option<BlockHash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value>;
+}
+
+/// Implementation of the DID RPC methods.
+pub struct Did<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Did<C, B> {
+	/// Create new `Did` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Moment>
+	DidApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Moment> for Did<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DidRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+	AccountId: Codec + Clone + PartialEq + Ss58Codec,
+	BlockNumber: Codec + Into<u64> + Into<NumberFor<Block>>,
+	Moment: Codec + Into<u64>,
+{
+	fn resolve(
+		&self,
+		identity: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value> {
+		let api = self.client.runtime_api();
+		let mut at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		if let Some(options) = options {
+			at = self.version_at(at, &identity, options)?;
+		}
+
+		let document = api.resolve(&at, identity).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(resolution_result(document))
+	}
+
+	fn signing_nonce(
+		&self,
+		identity: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<u64> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.signing_nonce(&at, identity).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn attributes(
+		&self,
+		identity: AccountId,
+		include_expired: bool,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Value>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let attributes = api
+			.attributes(&at, identity, include_expired)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(attributes.into_iter().map(attribute_record).collect())
+	}
+
+	fn delegates(
+		&self,
+		identity: AccountId,
+		delegate_type: Option<String>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Value>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let delegates = api
+			.delegates(&at, identity, delegate_type.map(String::into_bytes))
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(delegates.into_iter().map(delegate_record).collect())
+	}
+
+	fn resolve_handle(
+		&self,
+		handle: String,
+		at: Option<<Block as BlockT>::Hash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let identity = api
+			.resolve_handle(&BlockId::hash(at_hash), handle.into_bytes())
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| rpc_error(HANDLE_NOT_FOUND, "Handle not found"))?;
+
+		self.resolve(identity, Some(at_hash), options)
+	}
+
+	fn ethereum_account(
+		&self,
+		address: H160,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<String> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let identity = api.ethereum_account(&at, address).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(identity.to_ss58check())
+	}
+
+	fn resolve_ethereum(
+		&self,
+		address: H160,
+		at: Option<<Block as BlockT>::Hash>,
+		options: Option<ResolutionOptions>,
+	) -> RpcResult<Value> {
+		let api = self.client.runtime_api();
+		let mut at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		if let Some(options) = options {
+			let identity =
+				api.ethereum_account(&at, address).map_err(runtime_error_into_rpc_err)?;
+			at = self.version_at(at, &identity, options)?;
+		}
+
+		let document = api.resolve_ethereum(&at, address).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(resolution_result(document))
+	}
+}
+
+/// Version of a document requested by the resolution options.
+#[derive(Clone, Copy)]
+enum VersionTarget {
+	Id(u64),
+	Time(u64),
+}
+
+impl<C, Block> Did<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	/// Finds the block whose state holds the requested version of a document.
+	///
+	/// The previous change pointers of the document are followed back from `at`, one runtime
+	/// call against the state of each change block, until the requested version is reached.
+	fn version_at<AccountId, BlockNumber, Moment>(
+		&self,
+		at: BlockId<Block>,
+		identity: &AccountId,
+		options: ResolutionOptions,
+	) -> RpcResult<BlockId<Block>>
+	where
+		C::Api: DidRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+		AccountId: Codec + Clone,
+		BlockNumber: Codec + Into<NumberFor<Block>>,
+		Moment: Codec + Into<u64>,
+	{
+		let target = match (options.version_id, options.version_time) {
+			(Some(version_id), None) => VersionTarget::Id(
+				version_id
+					.parse()
+					.map_err(|_| rpc_error(INVALID_OPTIONS, "Invalid versionId"))?,
+			),
+			(None, Some(version_time)) => VersionTarget::Time(version_time),
+			(None, None) => return Ok(at),
+			(Some(_), Some(_)) =>
+				return Err(rpc_error(INVALID_OPTIONS, "versionId and versionTime are exclusive")),
+		};
+
+		let mut at = at;
+		loop {
+			let version = self
+				.client
+				.runtime_api()
+				.document_version(&at, identity.clone())
+				.map_err(runtime_error_into_rpc_err)?;
+
+			// Without any recorded change, the document is the same at every former block.
+			let version = match version {
+				Some(version) => version,
+				None => match target {
+					VersionTarget::Id(0) | VersionTarget::Time(_) => return Ok(at),
+					VersionTarget::Id(_) =>
+						return Err(rpc_error(VERSION_NOT_FOUND, "Unknown versionId")),
+				},
+			};
+
+			let time: u64 = version.time.into();
+			match target {
+				VersionTarget::Id(version_id) if version.version_id == version_id => return Ok(at),
+				// Versions superseded within their own block have no state of their own.
+				VersionTarget::Id(version_id) if version.version_id < version_id =>
+					return Err(rpc_error(VERSION_NOT_FOUND, "Unknown versionId")),
+				VersionTarget::Time(version_time) if time <= version_time => return Ok(at),
+				_ => (),
+			}
+
+			// Before the first change, the state of the parent block holds the initial version.
+			let block: NumberFor<Block> = version.block.into();
+			let previous: NumberFor<Block> = match version.previous_change {
+				Some(previous) => previous.into(),
+				None => block.saturating_sub(One::one()),
+			};
+			if previous >= block {
+				return Err(rpc_error(VERSION_NOT_FOUND, "Version not found"))
+			}
+			at = BlockId::Number(previous);
+		}
+	}
+}
+
+/// Renders a folded document as a W3C DID resolution result.
+fn resolution_result<AccountId, BlockNumber>(document: DidDocument<AccountId, BlockNumber>) -> Value
+where
+	AccountId: PartialEq + Ss58Codec,
+	BlockNumber: Into<u64>,
+{
+	let did = document
+		.ethereum_address
+		.as_ref()
+		.map_or_else(|| did_of(&document.id), ethr_did);
+	// The identity itself is referred to by the DID it is resolved as.
+	let did_of =
+		|account: &AccountId| if *account == document.id { did.clone() } else { did_of(account) };
+
+	let verification_method: Vec<Value> = document
+		.verification_method
+		.iter()
+		.map(|method| {
+			let mut value = json!({
+				"id": reference(&did, &method.id),
+				"type": to_string(&method.method_type),
+				"controller": did_of(&method.controller),
+			});
+			let (property, key) = match &method.public_key {
+				PublicKey::BlockchainAccount(account) =>
+					("blockchainAccountId", account.to_ss58check()),
+				PublicKey::Hex(key) => ("publicKeyHex", format!("{}", HexDisplay::from(key))),
+				PublicKey::Base64(key) => ("publicKeyBase64", base64::encode(key)),
+				PublicKey::Base58(key) => ("publicKeyBase58", bs58::encode(key).into_string()),
+				PublicKey::EthereumAddress(address) => (
+					"blockchainAccountId",
+					format!("{}:0x{}", ETHEREUM_CHAIN, HexDisplay::from(&address.as_bytes())),
+				),
+			};
+			value[property] = Value::String(key);
+			value
+		})
+		.collect();
+
+	let service: Vec<Value> = document
+		.service
+		.iter()
+		.map(|service| {
+			json!({
+				"id": reference(&did, &service.id),
+				"type": to_string(&service.service_type),
+				"serviceEndpoint": to_string(&service.endpoint),
+			})
+		})
+		.collect();
+
+	let mut metadata = json!({ "versionId": document.version_id.to_string() });
+	if let Some(updated) = document.updated {
+		let updated: u64 = updated.into();
+		metadata["updated"] = json!(updated);
+	}
+	if document.deactivated {
+		metadata["deactivated"] = json!(true);
+	}
+
+	let mut result = json!({
+		"didDocument": {
+			"@context": "https://www.w3.org/ns/did/v1",
+			"id": did,
+			"controller": did_of(&document.controller),
+			"verificationMethod": verification_method,
+			"authentication": references(&did, &document.authentication),
+			"assertionMethod": references(&did, &document.assertion_method),
+			"keyAgreement": references(&did, &document.key_agreement),
+			"service": service,
+		},
+		"didDocumentMetadata": metadata,
+		"didResolutionMetadata": { "contentType": "application/did+ld+json" },
+	});
+	let mut also_known_as = Vec::new();
+	if let Some(handle) = &document.handle {
+		also_known_as.push(format!("{}:{}", HANDLE_SCHEME, to_string(handle)));
+	}
+	if document.ethereum_address.is_some() {
+		also_known_as.push(format!("{}:{}", DID_METHOD, document.id.to_ss58check()));
+	}
+	if !also_known_as.is_empty() {
+		result["didDocument"]["alsoKnownAs"] = json!(also_known_as);
+	}
+	result
+}
+
+/// Renders an attribute record, its value as hex since it may not be text.
+fn attribute_record<BlockNumber, Moment>(record: AttributeRecord<BlockNumber, Moment>) -> Value
+where
+	BlockNumber: Into<u64>,
+	Moment: Into<u64>,
+{
+	let attribute = record.attribute;
+	let validity: u64 = attribute.validity.into();
+	let creation: u64 = attribute.creation.into();
+	json!({
+		"name": to_string(&attribute.name),
+		"value": format!("0x{}", HexDisplay::from(&attribute.value)),
+		"validity": validity,
+		"creation": creation,
+		"nonce": attribute.nonce,
+		"valid": record.valid,
+	})
+}
+
+fn delegate_record<AccountId, BlockNumber>(record: DelegateRecord<AccountId, BlockNumber>) -> Value
+where
+	AccountId: Ss58Codec,
+	BlockNumber: Into<u64>,
+{
+	let validity: u64 = record.validity.into();
+	json!({
+		"delegateType": to_string(&record.delegate_type),
+		"delegate": record.delegate.to_ss58check(),
+		"validity": validity,
+		"valid": record.valid,
+	})
+}
+
+fn did_of<AccountId: Ss58Codec>(account: &AccountId) -> String {
+	format!("{}:{}", DID_METHOD, account.to_ss58check())
+}
+
+fn ethr_did(address: &H160) -> String {
+	format!("{}:0x{}", ETHR_DID_METHOD, HexDisplay::from(&address.as_bytes()))
+}
+
+fn reference(did: &str, fragment: &[u8]) -> String {
+	format!("{}#{}", did, to_string(fragment))
+}
+
+fn references(did: &str, fragments: &[Vec<u8>]) -> Vec<String> {
+	fragments.iter().map(|fragment| reference(did, fragment)).collect()
+}
+
+fn to_string(bytes: &[u8]) -> String {
+	String::from_utf8_lossy(bytes).into_owned()
+}
+
+fn rpc_error(code: i32, message: &str) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(code, message, None::<()>)).into()
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Runtime error",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
@@ -0,0 +1,678 @@
+//! Benchmarking setup for pallet-did
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Did;
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
+use frame_system::RawOrigin;
+use sp_core::{ecdsa, sr25519};
+use sp_runtime::{traits::Bounded, KeyTypeId};
+use sp_std::{boxed::Box, vec};
+
+const SEED: u32 = 0;
+/// Key type of the keys generated to sign off-chain transactions.
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"did!");
+
+/// Creates an account able to pay any storage deposit.
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
+
+/// Longest attribute name accepted by the pallet.
+fn attribute_name<T: Config>() -> Vec<u8> {
+	vec![b'n'; T::MaxNameLength::get() as usize]
+}
+
+/// Longest attribute value accepted by the pallet.
+fn attribute_value<T: Config>() -> Vec<u8> {
+	vec![0u8; T::MaxValueLength::get() as usize]
+}
+
+/// Longest handle accepted by the pallet.
+fn handle<T: Config>() -> Vec<u8> {
+	vec![b'h'; T::MaxHandleLength::get() as usize]
+}
+
+/// Registers the longest handle for an identity.
+fn set_up_handle<T: Config>(identity: &T::AccountId) -> Result<(), BenchmarkError> {
+	Did::<T>::register_handle(
+		RawOrigin::Signed(identity.clone()).into(),
+		identity.clone(),
+		handle::<T>(),
+	)?;
+	Ok(())
+}
+
+fn guardians<T: Config>(g: u32) -> Vec<T::AccountId> {
+	(0..g).map(|i| account("guardian", i, SEED)).collect()
+}
+
+/// Registers `g` guardians of an identity, any of them can complete a recovery at once.
+fn set_up_guardians<T: Config>(
+	identity: &T::AccountId,
+	g: u32,
+) -> Result<Vec<T::AccountId>, BenchmarkError> {
+	let guardians = guardians::<T>(g);
+	Did::<T>::set_guardians(
+		RawOrigin::Signed(identity.clone()).into(),
+		identity.clone(),
+		guardians.clone(),
+		1,
+		0u32.into(),
+	)?;
+	Ok(guardians)
+}
+
+/// Hands an identity to `c` funded controllers, all of them needed to execute a proposal.
+fn set_up_controllers<T: Config>(
+	identity: &T::AccountId,
+	c: u32,
+) -> Result<Vec<T::AccountId>, BenchmarkError> {
+	let controllers: Vec<T::AccountId> =
+		(0..c).map(|i| funded_account::<T>("controller", i)).collect();
+	Did::<T>::set_controllers(
+		RawOrigin::Signed(identity.clone()).into(),
+		identity.clone(),
+		controllers.clone(),
+		c,
+	)?;
+	Ok(controllers)
+}
+
+/// Call proposed to the controllers of an identity.
+fn proposal<T: Config>() -> Box<T::Proposal> {
+	Box::new(frame_system::Call::<T>::remark { remark: vec![] }.into())
+}
+
+/// Creates a funded identity controlled by a freshly generated sr25519 key.
+fn signing_identity<T: Config>() -> (sr25519::Public, T::AccountId)
+where
+	T::Public: From<sr25519::Public>,
+{
+	let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+	let identity: T::AccountId = T::Public::from(public).into_account();
+	T::Currency::make_free_balance_be(&identity, BalanceOf::<T>::max_value() / 2u32.into());
+	(public, identity)
+}
+
+/// Signs the payload of an off-chain transaction with a key generated by `signing_identity`.
+fn sign<T: Config>(
+	public: &sr25519::Public,
+	payload: &[u8],
+) -> Result<SignatureOf<T>, BenchmarkError>
+where
+	T::Signature: From<sr25519::Signature>,
+{
+	sp_io::crypto::sr25519_sign(KEY_TYPE, public, payload)
+		.map(|signature| DidSignature::Native(signature.into()))
+		.ok_or(BenchmarkError::Stop("failed to sign the transaction"))
+}
+
+/// Placeholder signature replaced once the transaction payload is known.
+fn unsigned<T: Config>() -> SignatureOf<T>
+where
+	T::Signature: From<sr25519::Signature>,
+{
+	DidSignature::Native(sr25519::Signature::from_raw([0u8; 64]).into())
+}
+
+/// Generates a secp256k1 key and returns it along with its Ethereum address.
+fn ethereum_key() -> Result<(ecdsa::Public, H160), BenchmarkError> {
+	let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+	let hash = [0u8; 32];
+	let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &hash)
+		.ok_or(BenchmarkError::Stop("failed to sign with the ethereum key"))?;
+	let uncompressed = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &hash)
+		.map_err(|_| BenchmarkError::Stop("failed to recover the ethereum key"))?;
+	let address = H160::from_slice(&keccak_256(&uncompressed)[12..]);
+	Ok((public, address))
+}
+
+benchmarks! {
+	where_clause { where
+		T::Signature: From<sr25519::Signature>,
+		T::Public: From<sr25519::Public>,
+	}
+
+	change_owner {
+		let caller = funded_account::<T>("caller", 0);
+		let new_owner = funded_account::<T>("new_owner", 0);
+		// The identity holds a deposit that has to follow it to the new owner.
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), new_owner.clone())
+	verify {
+		assert_eq!(Did::<T>::owner_of(&caller), Some(new_owner));
+	}
+
+	add_delegate {
+		let caller = funded_account::<T>("caller", 0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		caller.clone(),
+		delegate.clone(),
+		DELEGATE_SIGNATURE_AUTH.to_vec(),
+		None
+	)
+	verify {
+		assert!(Did::<T>::valid_delegate(&caller, DELEGATE_SIGNATURE_AUTH, &delegate).is_ok());
+	}
+
+	revoke_delegate {
+		let caller = funded_account::<T>("caller", 0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		Did::<T>::add_delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			delegate.clone(),
+			DELEGATE_SIGNATURE_AUTH.to_vec(),
+			None,
+		)?;
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		caller.clone(),
+		DELEGATE_SIGNATURE_AUTH.to_vec(),
+		delegate.clone()
+	)
+	verify {
+		assert!(Did::<T>::valid_delegate(&caller, DELEGATE_SIGNATURE_AUTH, &delegate).is_err());
+	}
+
+	add_attribute {
+		let v in 0 .. T::MaxValueLength::get();
+		let caller = funded_account::<T>("caller", 0);
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		caller.clone(),
+		attribute_name::<T>(),
+		vec![0u8; v as usize],
+		None
+	)
+	verify {
+		let value = vec![0u8; v as usize];
+		assert!(Did::<T>::valid_attribute(&caller, &attribute_name::<T>(), &value).is_ok());
+	}
+
+	revoke_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), attribute_name::<T>())
+	verify {
+		let value = attribute_value::<T>();
+		assert!(Did::<T>::valid_attribute(&caller, &attribute_name::<T>(), &value).is_err());
+	}
+
+	delete_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), attribute_name::<T>())
+	verify {
+		assert!(Did::<T>::attribute_and_id(&caller, &attribute_name::<T>()).is_none());
+	}
+
+	execute {
+		let v in 0 .. T::MaxValueLength::get();
+		// The signature is verified against a real key so its cost is measured.
+		let public = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let identity: T::AccountId = T::Public::from(public).into_account();
+		T::Currency::make_free_balance_be(&identity, BalanceOf::<T>::max_value() / 2u32.into());
+
+		let mut transaction = AttributeTransaction {
+			signature: unsigned::<T>(),
+			name: attribute_name::<T>(),
+			value: vec![0u8; v as usize],
+			validity: 1000,
+			signer: identity.clone(),
+			identity: identity.clone(),
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		let payload = Did::<T>::attribute_transaction_payload(&transaction);
+		transaction.signature = sign::<T>(&public, &payload)?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		let value = vec![0u8; v as usize];
+		assert!(Did::<T>::valid_attribute(&identity, &attribute_name::<T>(), &value).is_ok());
+	}
+
+	change_owner_signed {
+		let (public, identity) = signing_identity::<T>();
+		let new_owner = funded_account::<T>("new_owner", 0);
+		// The identity holds a deposit that has to follow it to the new owner.
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(identity.clone()).into(),
+			identity.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+		let mut transaction = ChangeOwnerTransaction {
+			signature: unsigned::<T>(),
+			signer: identity.clone(),
+			identity: identity.clone(),
+			new_owner: new_owner.clone(),
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		transaction.signature = sign::<T>(&public, &Did::<T>::change_owner_payload(&transaction))?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		assert_eq!(Did::<T>::owner_of(&identity), Some(new_owner));
+	}
+
+	add_delegate_signed {
+		let (public, identity) = signing_identity::<T>();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		let mut transaction = AddDelegateTransaction {
+			signature: unsigned::<T>(),
+			signer: identity.clone(),
+			identity: identity.clone(),
+			delegate: delegate.clone(),
+			delegate_type: DELEGATE_SIGNATURE_AUTH.to_vec(),
+			valid_for: None,
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		transaction.signature = sign::<T>(&public, &Did::<T>::add_delegate_payload(&transaction))?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		assert!(Did::<T>::valid_delegate(&identity, DELEGATE_SIGNATURE_AUTH, &delegate).is_ok());
+	}
+
+	revoke_delegate_signed {
+		let (public, identity) = signing_identity::<T>();
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		Did::<T>::add_delegate(
+			RawOrigin::Signed(identity.clone()).into(),
+			identity.clone(),
+			delegate.clone(),
+			DELEGATE_SIGNATURE_AUTH.to_vec(),
+			None,
+		)?;
+		let mut transaction = RevokeDelegateTransaction {
+			signature: unsigned::<T>(),
+			signer: identity.clone(),
+			identity: identity.clone(),
+			delegate_type: DELEGATE_SIGNATURE_AUTH.to_vec(),
+			delegate: delegate.clone(),
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		let payload = Did::<T>::revoke_delegate_payload(&transaction);
+		transaction.signature = sign::<T>(&public, &payload)?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		assert!(Did::<T>::valid_delegate(&identity, DELEGATE_SIGNATURE_AUTH, &delegate).is_err());
+	}
+
+	revoke_attribute_signed {
+		let (public, identity) = signing_identity::<T>();
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(identity.clone()).into(),
+			identity.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+		let mut transaction = RevokeAttributeTransaction {
+			signature: unsigned::<T>(),
+			signer: identity.clone(),
+			identity: identity.clone(),
+			name: attribute_name::<T>(),
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		let payload = Did::<T>::revoke_attribute_payload(&transaction);
+		transaction.signature = sign::<T>(&public, &payload)?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		let value = attribute_value::<T>();
+		assert!(Did::<T>::valid_attribute(&identity, &attribute_name::<T>(), &value).is_err());
+	}
+
+	delete_attribute_signed {
+		let (public, identity) = signing_identity::<T>();
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(identity.clone()).into(),
+			identity.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			None,
+		)?;
+		let mut transaction = DeleteAttributeTransaction {
+			signature: unsigned::<T>(),
+			signer: identity.clone(),
+			identity: identity.clone(),
+			name: attribute_name::<T>(),
+			nonce: Did::<T>::signing_nonce_of(&identity),
+			expiry: u32::MAX,
+		};
+		let payload = Did::<T>::delete_attribute_payload(&transaction);
+		transaction.signature = sign::<T>(&public, &payload)?;
+	}: _(RawOrigin::Signed(identity.clone()), transaction)
+	verify {
+		assert!(Did::<T>::attribute_and_id(&identity, &attribute_name::<T>()).is_none());
+	}
+
+	prune_delegate {
+		let caller = funded_account::<T>("caller", 0);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		Did::<T>::add_delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			delegate.clone(),
+			DELEGATE_SIGNATURE_AUTH.to_vec(),
+			Some(1u32.into()),
+		)?;
+		let now = T::PruningGracePeriod::get() + 100u32.into();
+	}: {
+		Did::<T>::prune_expired(now, Weight::MAX);
+	}
+	verify {
+		assert!(Did::<T>::delegates(&caller, None).is_empty());
+	}
+
+	prune_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		Did::<T>::add_attribute(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			attribute_name::<T>(),
+			attribute_value::<T>(),
+			Some(1u32.into()),
+		)?;
+		let now = T::PruningGracePeriod::get() + 100u32.into();
+	}: {
+		Did::<T>::prune_expired(now, Weight::MAX);
+	}
+	verify {
+		assert!(Did::<T>::attributes(&caller, true).is_empty());
+	}
+
+	set_guardians {
+		let g in 1 .. T::MaxGuardians::get();
+		let caller = funded_account::<T>("caller", 0);
+		// Replacing a guardian set releases the deposit of the previous one.
+		Did::<T>::set_guardians(
+			RawOrigin::Signed(caller.clone()).into(),
+			caller.clone(),
+			guardians::<T>(T::MaxGuardians::get()),
+			1,
+			0u32.into(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), guardians::<T>(g), g, 10u32.into())
+	verify {
+		assert_eq!(Did::<T>::recovery_config_of(&caller).map(|config| config.threshold), Some(g));
+	}
+
+	remove_guardians {
+		let caller = funded_account::<T>("caller", 0);
+		set_up_guardians::<T>(&caller, T::MaxGuardians::get())?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(Did::<T>::recovery_config_of(&caller).is_none());
+	}
+
+	initiate_recovery {
+		let caller = funded_account::<T>("caller", 0);
+		let guardians = set_up_guardians::<T>(&caller, T::MaxGuardians::get())?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+	}: _(RawOrigin::Signed(guardians[0].clone()), caller.clone(), new_owner)
+	verify {
+		assert!(Did::<T>::active_recovery_of(&caller).is_some());
+	}
+
+	approve_recovery {
+		let g in 2 .. T::MaxGuardians::get();
+		let caller = funded_account::<T>("caller", 0);
+		let guardians = set_up_guardians::<T>(&caller, g)?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+		Did::<T>::initiate_recovery(
+			RawOrigin::Signed(guardians[0].clone()).into(),
+			caller.clone(),
+			new_owner,
+		)?;
+		// Every guardian but the last one has approved.
+		for guardian in guardians[1..guardians.len() - 1].iter() {
+			Did::<T>::approve_recovery(RawOrigin::Signed(guardian.clone()).into(), caller.clone())?;
+		}
+		let last = guardians[guardians.len() - 1].clone();
+	}: _(RawOrigin::Signed(last), caller.clone())
+	verify {
+		let approvals = Did::<T>::active_recovery_of(&caller).map(|r| r.approvals.len() as u32);
+		assert_eq!(approvals, Some(g));
+	}
+
+	cancel_recovery {
+		let caller = funded_account::<T>("caller", 0);
+		let guardians = set_up_guardians::<T>(&caller, T::MaxGuardians::get())?;
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+		Did::<T>::initiate_recovery(
+			RawOrigin::Signed(guardians[0].clone()).into(),
+			caller.clone(),
+			new_owner,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(Did::<T>::active_recovery_of(&caller).is_none());
+	}
+
+	complete_recovery {
+		let caller = funded_account::<T>("caller", 0);
+		let guardians = set_up_guardians::<T>(&caller, 1)?;
+		let new_owner = funded_account::<T>("new_owner", 0);
+		Did::<T>::initiate_recovery(
+			RawOrigin::Signed(guardians[0].clone()).into(),
+			caller.clone(),
+			new_owner.clone(),
+		)?;
+	}: _(RawOrigin::Signed(guardians[0].clone()), caller.clone())
+	verify {
+		assert_eq!(Did::<T>::owner_of(&caller), Some(new_owner));
+	}
+
+	set_controllers {
+		let c in 1 .. T::MaxControllers::get();
+		let caller = funded_account::<T>("caller", 0);
+		let controllers: Vec<T::AccountId> =
+			(0..c).map(|i| account("controller", i, SEED)).collect();
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), controllers, c)
+	verify {
+		assert_eq!(Did::<T>::controllers_of(&caller).map(|set| set.threshold), Some(c));
+	}
+
+	remove_controllers {
+		let caller = funded_account::<T>("caller", 0);
+		set_up_controllers::<T>(&caller, T::MaxControllers::get())?;
+		// The call only succeeds from the origin of an approved proposal.
+	}: _(Did::<T>::identity_origin(caller.clone()), caller.clone())
+	verify {
+		assert!(Did::<T>::controllers_of(&caller).is_none());
+	}
+
+	propose {
+		let caller = funded_account::<T>("caller", 0);
+		let controllers = set_up_controllers::<T>(&caller, T::MaxControllers::get())?;
+		let proposal_hash = T::Hashing::hash_of(&proposal::<T>());
+	}: _(RawOrigin::Signed(controllers[0].clone()), caller.clone(), proposal::<T>())
+	verify {
+		assert!(Did::<T>::proposal_of(&caller, &proposal_hash).is_some());
+	}
+
+	approve_proposal {
+		let c in 2 .. T::MaxControllers::get();
+		let caller = funded_account::<T>("caller", 0);
+		let controllers = set_up_controllers::<T>(&caller, c)?;
+		let proposal_hash = T::Hashing::hash_of(&proposal::<T>());
+		Did::<T>::propose(
+			RawOrigin::Signed(controllers[0].clone()).into(),
+			caller.clone(),
+			proposal::<T>(),
+		)?;
+		// Every controller but the last one has approved.
+		for controller in controllers[1..controllers.len() - 1].iter() {
+			Did::<T>::approve_proposal(
+				RawOrigin::Signed(controller.clone()).into(),
+				caller.clone(),
+				proposal_hash,
+			)?;
+		}
+		let last = controllers[controllers.len() - 1].clone();
+	}: _(RawOrigin::Signed(last), caller.clone(), proposal_hash)
+	verify {
+		let approvals = Did::<T>::proposal_of(&caller, &proposal_hash)
+			.map(|proposal| proposal.approvals.len() as u32);
+		assert_eq!(approvals, Some(c));
+	}
+
+	cancel_proposal {
+		let caller = funded_account::<T>("caller", 0);
+		let controllers = set_up_controllers::<T>(&caller, 1)?;
+		let proposal_hash = T::Hashing::hash_of(&proposal::<T>());
+		Did::<T>::propose(
+			RawOrigin::Signed(controllers[0].clone()).into(),
+			caller.clone(),
+			proposal::<T>(),
+		)?;
+	}: _(RawOrigin::Signed(controllers[0].clone()), caller.clone(), proposal_hash)
+	verify {
+		assert!(Did::<T>::proposal_of(&caller, &proposal_hash).is_none());
+	}
+
+	execute_proposal {
+		let c in 1 .. T::MaxControllers::get();
+		let caller = funded_account::<T>("caller", 0);
+		let controllers = set_up_controllers::<T>(&caller, c)?;
+		let proposal_hash = T::Hashing::hash_of(&proposal::<T>());
+		Did::<T>::propose(
+			RawOrigin::Signed(controllers[0].clone()).into(),
+			caller.clone(),
+			proposal::<T>(),
+		)?;
+		for controller in controllers[1..].iter() {
+			Did::<T>::approve_proposal(
+				RawOrigin::Signed(controller.clone()).into(),
+				caller.clone(),
+				proposal_hash,
+			)?;
+		}
+	}: _(RawOrigin::Signed(controllers[0].clone()), caller.clone(), proposal::<T>())
+	verify {
+		assert!(Did::<T>::proposal_of(&caller, &proposal_hash).is_none());
+	}
+
+	link_ethereum_account {
+		let caller = funded_account::<T>("caller", 0);
+		let (public, address) = ethereum_key()?;
+		let payload = Did::<T>::link_account_payload(&address, &caller);
+		let signature =
+			sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &Did::<T>::eip191_hash(&payload))
+				.ok_or("failed to sign the link")?;
+	}: _(RawOrigin::Signed(caller.clone()), address, signature)
+	verify {
+		let identity = Did::<T>::ethereum_account(&address);
+		assert_eq!(Did::<T>::ethereum_link(&identity), Some((address, caller)));
+	}
+
+	update_document {
+		let n in 1 .. T::MaxOperations::get();
+		let b in 0 .. T::MaxOperations::get() * T::MaxValueLength::get();
+		let caller = funded_account::<T>("caller", 0);
+		// Attributes with the longest name, told apart by the index of the operation, sharing
+		// `b` bytes of value as evenly as the longest value allows.
+		let value_length = (b / n).min(T::MaxValueLength::get()) as usize;
+		let operations: Vec<DidOperationFor<T>> = (0..n)
+			.map(|i| {
+				let mut name = attribute_name::<T>();
+				name[..4].copy_from_slice(&i.to_le_bytes());
+				DidOperation::AddAttribute { name, value: vec![0u8; value_length], valid_for: None }
+			})
+			.collect();
+		let operations: DidOperations<T> =
+			operations.try_into().expect("operations fit the limit");
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), operations)
+	verify {
+		assert_eq!(Did::<T>::document_version(&caller).map(|version| version.version_id), Some(1));
+	}
+
+	register_handle {
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone(), handle::<T>())
+	verify {
+		assert_eq!(Did::<T>::handle_identity(&handle::<T>()), Some(caller));
+	}
+
+	renew_handle {
+		let caller = funded_account::<T>("caller", 0);
+		set_up_handle::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), handle::<T>())
+	verify {
+		assert_eq!(Did::<T>::identity_handle(&caller), Some(handle::<T>()));
+	}
+
+	transfer_handle {
+		let caller = funded_account::<T>("caller", 0);
+		let new_identity: T::AccountId = account("new_identity", 0, SEED);
+		set_up_handle::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), handle::<T>(), new_identity.clone())
+	verify {
+		assert_eq!(Did::<T>::handle_identity(&handle::<T>()), Some(new_identity));
+	}
+
+	release_handle {
+		let caller = funded_account::<T>("caller", 0);
+		set_up_handle::<T>(&caller)?;
+	}: _(RawOrigin::Signed(caller.clone()), handle::<T>())
+	verify {
+		assert!(Did::<T>::identity_handle(&caller).is_none());
+	}
+
+	// Read by the policies of `pallet_abac` through the runtime chain extension.
+	handle_identity {
+		let caller = funded_account::<T>("caller", 0);
+		set_up_handle::<T>(&caller)?;
+	}: {
+		assert_eq!(Did::<T>::handle_identity(&handle::<T>()).as_ref(), Some(&caller));
+	}
+
+	deactivate {
+		let caller = funded_account::<T>("caller", 0);
+		// Guardians with an open recovery and controllers are all removed.
+		let guardians = set_up_guardians::<T>(&caller, T::MaxGuardians::get())?;
+		Did::<T>::initiate_recovery(
+			RawOrigin::Signed(guardians[0].clone()).into(),
+			caller.clone(),
+			account("new_owner", 0, SEED),
+		)?;
+		// So is the handle.
+		set_up_handle::<T>(&caller)?;
+		set_up_controllers::<T>(&caller, T::MaxControllers::get())?;
+	}: _(Did::<T>::identity_origin(caller.clone()), caller.clone())
+	verify {
+		assert!(Did::<T>::is_deactivated(&caller));
+		assert_eq!(Did::<T>::handle_identity(&handle::<T>()), None);
+	}
+
+	impl_benchmark_test_suite!(Did, crate::mock::new_test_ext(), crate::mock::Test);
+}
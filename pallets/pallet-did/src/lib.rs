@@ -2,23 +2,196 @@
 #![recursion_limit = "256"]
 use crate::types::*;
 
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{
+	DispatchResult, DispatchResultWithPostInfo, GetDispatchInfo, PostDispatchInfo,
+};
 pub use pallet::*;
 
+pub mod migration;
 pub mod types;
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 use codec::{Decode, Encode};
-use frame_support::{pallet_prelude::*, sp_std::vec::Vec, traits::Time};
+use frame_support::{
+	pallet_prelude::*,
+	sp_std::{boxed::Box, vec::Vec},
+	traits::{ConstU32, Currency, ReservableCurrency, Time},
+};
 use frame_system::pallet_prelude::*;
-use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{IdentifyAccount, Member, Verify};
+use sp_core::{ecdsa, H160};
+use sp_io::hashing::{blake2_256, keccak_256};
+use sp_runtime::traits::{
+	Dispatchable, Hash as HashT, IdentifyAccount, Member, Saturating, TrailingZeroInput, Verify,
+	Zero,
+};
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+pub type SignatureOf<T> = DidSignature<<T as Config>::Signature>;
+pub type DelegateType<T> = BoundedVec<u8, <T as Config>::MaxDelegateTypeLength>;
+pub type AttributeName<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+pub type AttributeValue<T> = BoundedVec<u8, <T as Config>::MaxValueLength>;
+pub type Guardians<T> =
+	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxGuardians>;
+pub type RecoveryConfigFor<T> =
+	RecoveryConfig<Guardians<T>, <T as frame_system::Config>::BlockNumber>;
+pub type ActiveRecoveryFor<T> = ActiveRecovery<
+	<T as frame_system::Config>::AccountId,
+	Guardians<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+pub type Controllers<T> =
+	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::MaxControllers>;
+pub type ControllerSetFor<T> = ControllerSet<Controllers<T>>;
+pub type PendingProposalFor<T> = PendingProposal<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	Controllers<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+pub type DocumentVersionFor<T> =
+	DocumentVersion<<T as frame_system::Config>::BlockNumber, MomentOf<T>>;
+pub type Handle<T> = BoundedVec<u8, <T as Config>::MaxHandleLength>;
+pub type HandleRecordFor<T> = HandleRecord<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+pub type DidOperationFor<T> =
+	DidOperation<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+pub type DidOperations<T> = BoundedVec<DidOperationFor<T>, <T as Config>::MaxOperations>;
+/// Raw storage key the pruning sweep resumes from.
+pub type RawKey = BoundedVec<u8, ConstU32<512>>;
+pub type AttributeFor<T> = Attribute<
+	<T as frame_system::Config>::BlockNumber,
+	MomentOf<T>,
+	AttributeName<T>,
+	AttributeValue<T>,
+>;
+
+/// Delegate type of a key listed as assertion method in the DID document.
+pub const DELEGATE_VERIFICATION_KEY: &[u8] = b"veriKey";
+/// Delegate type of a key listed as both assertion and authentication method in the DID document.
+pub const DELEGATE_SIGNATURE_AUTH: &[u8] = b"sigAuth";
+/// Delegate types whose delegates may sign assertions such as credentials on behalf of an
+/// identity, the ones whose verification method kind serves the assertion purpose.
+pub const ASSERTING_DELEGATE_TYPES: &[&[u8]] = &[
+	DELEGATE_VERIFICATION_KEY,
+	DELEGATE_SIGNATURE_AUTH,
+	b"Ed25519/veriKey",
+	b"Ed25519/sigAuth",
+	b"Sr25519/veriKey",
+	b"Sr25519/sigAuth",
+	b"Secp256k1/veriKey",
+	b"Secp256k1/sigAuth",
+];
+/// Verification method type of the owner account.
+pub const ACCOUNT_METHOD_TYPE: &[u8] = b"Sr25519VerificationKey2020";
+/// Verification method type of the accounts of Ethereum addresses, whose keys are recovered
+/// from their signatures.
+pub const ETHEREUM_METHOD_TYPE: &[u8] = b"EcdsaSecp256k1RecoveryMethod2020";
+/// Prefix of the attributes named `did/pub/<algorithm>/<purpose>/<encoding>` holding public keys.
+pub const PUBLIC_KEY_PREFIX: &[u8] = b"did/pub/";
+/// Domains prefixing the signing payloads of off-chain transactions, one per kind.
+/// None is a prefix of another so a signature is only valid for a single kind.
+pub const DOMAIN_SET_ATTRIBUTE: &[u8] = b"did:setAttribute:";
+pub const DOMAIN_CHANGE_OWNER: &[u8] = b"did:changeOwner:";
+pub const DOMAIN_ADD_DELEGATE: &[u8] = b"did:addDelegate:";
+pub const DOMAIN_REVOKE_DELEGATE: &[u8] = b"did:revokeDelegate:";
+pub const DOMAIN_REVOKE_ATTRIBUTE: &[u8] = b"did:revokeAttribute:";
+pub const DOMAIN_DELETE_ATTRIBUTE: &[u8] = b"did:deleteAttribute:";
+pub const DOMAIN_LINK_ACCOUNT: &[u8] = b"did:linkAccount:";
+/// Prefix hashed with an Ethereum address into the account of its identity.
+pub const ETHEREUM_ACCOUNT_PREFIX: &[u8] = b"did:ethr:";
+/// EIP-712 domain of the typed data signed by Ethereum accounts, salted with the genesis hash.
+pub const EIP712_DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,bytes32 salt)";
+pub const EIP712_DOMAIN_NAME: &[u8] = b"Substrate DID";
+pub const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+/// EIP-712 types of the off-chain transactions, one per kind with the fields of its payload.
+/// Accounts are the `bytes` of their SCALE encoding, names and delegate types are `string`s.
+pub const EIP712_SET_ATTRIBUTE_TYPE: &[u8] = b"SetAttribute(bytes identity,string name,bytes value,uint32 validity,uint64 nonce,uint32 expiry)";
+pub const EIP712_CHANGE_OWNER_TYPE: &[u8] =
+	b"ChangeOwner(bytes signer,bytes identity,bytes newOwner,uint64 nonce,uint32 expiry)";
+pub const EIP712_ADD_DELEGATE_TYPE: &[u8] = b"AddDelegate(bytes signer,bytes identity,bytes delegate,string delegateType,bool expires,uint32 validFor,uint64 nonce,uint32 expiry)";
+pub const EIP712_REVOKE_DELEGATE_TYPE: &[u8] = b"RevokeDelegate(bytes signer,bytes identity,string delegateType,bytes delegate,uint64 nonce,uint32 expiry)";
+pub const EIP712_REVOKE_ATTRIBUTE_TYPE: &[u8] =
+	b"RevokeAttribute(bytes signer,bytes identity,string name,uint64 nonce,uint32 expiry)";
+pub const EIP712_DELETE_ATTRIBUTE_TYPE: &[u8] =
+	b"DeleteAttribute(bytes signer,bytes identity,string name,uint64 nonce,uint32 expiry)";
+/// EIP-712 type of a message signed on behalf of an identity, see `valid_signer`.
+pub const EIP712_MESSAGE_TYPE: &[u8] = b"Message(bytes message)";
+/// Minimum length of a handle.
+pub const MIN_HANDLE_LENGTH: usize = 3;
+/// Delegate type whose valid delegates may add, revoke and delete the attributes of an identity
+/// when the runtime uses the `AttributeAdmin` permissions. It grants no right over delegates.
+pub const DELEGATE_ATTRIBUTE_ADMIN: &[u8] = b"AttributeAdmin";
+
+/// Write operations a delegate can be permitted to perform on behalf of the identity owner.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Operation {
+	AddDelegate,
+	RevokeDelegate,
+	AddAttribute,
+	RevokeAttribute,
+	DeleteAttribute,
+}
+
+/// Delegate types permitted to perform each write operation besides the identity owner.
+pub trait DelegatePermissions {
+	/// Returns the delegate types whose valid delegates may perform `operation`.
+	fn delegate_types(operation: Operation) -> &'static [&'static [u8]];
+}
+
+/// Only the identity owner performs write operations.
+impl DelegatePermissions for () {
+	fn delegate_types(_operation: Operation) -> &'static [&'static [u8]] {
+		&[]
+	}
+}
+
+/// Lets `AttributeAdmin` delegates add, revoke and delete the attributes of an identity.
+pub struct AttributeAdmin;
+
+impl DelegatePermissions for AttributeAdmin {
+	fn delegate_types(operation: Operation) -> &'static [&'static [u8]] {
+		match operation {
+			Operation::AddAttribute | Operation::RevokeAttribute | Operation::DeleteAttribute =>
+				&[DELEGATE_ATTRIBUTE_ADMIN],
+			Operation::AddDelegate | Operation::RevokeDelegate => &[],
+		}
+	}
+}
+
+/// Handler of the deactivation of an identity, for pallets holding data about it.
+/// It runs within the `deactivate` call so it should only schedule any heavy cleanup.
+pub trait OnDeactivate<AccountId> {
+	fn on_deactivate(identity: &AccountId);
+}
+
+impl<AccountId> OnDeactivate<AccountId> for () {
+	fn on_deactivate(_identity: &AccountId) {}
+}
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
-	#[pallet::without_storage_info] // Only for DEVELOPMENT or DEMONSTRATION
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
 
@@ -28,6 +201,60 @@ pub mod pallet {
 		type Public: IdentifyAccount<AccountId = Self::AccountId>;
 		type Signature: Verify<Signer = Self::Public> + Member + Decode + Encode + TypeInfo;
 		type Time: Time;
+		/// The currency in which the storage deposits are reserved.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The amount reserved for each delegate or attribute stored.
+		#[pallet::constant]
+		type DepositPerItem: Get<BalanceOf<Self>>;
+		/// The amount reserved for each byte of a delegate type or an attribute name and value.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+		/// The maximum length of a delegate type.
+		#[pallet::constant]
+		type MaxDelegateTypeLength: Get<u32>;
+		/// The maximum length of an attribute name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// The maximum length of an attribute value.
+		#[pallet::constant]
+		type MaxValueLength: Get<u32>;
+		/// The maximum number of operations applied by a single document update.
+		#[pallet::constant]
+		type MaxOperations: Get<u32>;
+		/// The number of blocks an expired delegate or attribute is kept before being pruned.
+		#[pallet::constant]
+		type PruningGracePeriod: Get<Self::BlockNumber>;
+		/// The maximum number of guardians able to recover an identity.
+		#[pallet::constant]
+		type MaxGuardians: Get<u32>;
+		/// Delegate types allowed to perform write operations on behalf of the owner.
+		type DelegatePermissions: DelegatePermissions;
+		/// Free-form delegate types which can be added, such as the permissions of
+		/// `DelegatePermissions` and of other pallets. Other types name a verification method.
+		type PermissionDelegateTypes: Get<&'static [&'static [u8]]>;
+		/// The maximum number of controllers jointly owning an identity.
+		#[pallet::constant]
+		type MaxControllers: Get<u32>;
+		/// The overarching origin, which carries the identity origin of approved proposals.
+		type OuterOrigin: From<RawOrigin<Self::AccountId>>
+			+ From<OriginFor<Self>>
+			+ Into<OriginFor<Self>>
+			+ Into<Result<RawOrigin<Self::AccountId>, Self::OuterOrigin>>;
+		/// The calls the controllers of an identity can propose and execute as its owner.
+		type Proposal: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+		/// The maximum length of a handle.
+		#[pallet::constant]
+		type MaxHandleLength: Get<u32>;
+		/// The number of blocks a handle is registered for, from its registration or renewal.
+		#[pallet::constant]
+		type HandlePeriod: Get<Self::BlockNumber>;
+		/// Handler notified when an identity is deactivated.
+		type OnDeactivate: OnDeactivate<Self::AccountId>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	/// Identity delegates stored by type.
@@ -38,7 +265,7 @@ pub mod pallet {
 		_,
 		(
 			NMapKey<Blake2_128Concat, T::AccountId>,
-			NMapKey<Blake2_128Concat, Vec<u8>>,
+			NMapKey<Blake2_128Concat, DelegateType<T>>,
 			NMapKey<Blake2_128Concat, T::AccountId>,
 		),
 		T::BlockNumber,
@@ -55,15 +282,22 @@ pub mod pallet {
 		T::AccountId,
 		Blake2_128Concat,
 		[u8; 32],
-		Attribute<T::BlockNumber, <<T as Config>::Time as Time>::Moment>,
+		AttributeFor<T>,
 		ValueQuery,
 	>;
 
 	/// Attribute nonce used to generate a unique hash even if the attribute is deleted and recreated.
 	#[pallet::storage]
 	#[pallet::getter(fn nonce_of)]
-	pub(super) type AttributeNonce<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, Vec<u8>, u64, ValueQuery>;
+	pub(super) type AttributeNonce<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		AttributeName<T>,
+		u64,
+		ValueQuery,
+	>;
 
 	/// Identity owner.
 	#[pallet::storage]
@@ -71,16 +305,110 @@ pub mod pallet {
 	pub(super) type OwnerOf<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
 
+	/// Nonce of the off-chain signed transactions of an identity.
+	/// Each executed transaction consumes the current nonce so it cannot be replayed.
+	#[pallet::storage]
+	#[pallet::getter(fn signing_nonce_of)]
+	pub(super) type SigningNonce<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Total storage deposit held for an identity.
+	/// It is always reserved from the current identity owner.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_of)]
+	pub(super) type DepositOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// Position of the sweep pruning expired delegates and attributes.
+	#[pallet::storage]
+	#[pallet::getter(fn pruning_position)]
+	pub(super) type PruningPosition<T: Config> = StorageValue<_, PruningCursor<RawKey>, ValueQuery>;
+
+	/// Guardians able to recover the ownership of an identity.
+	#[pallet::storage]
+	#[pallet::getter(fn recovery_config_of)]
+	pub(super) type RecoveryConfigOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RecoveryConfigFor<T>, OptionQuery>;
+
+	/// Recovery opened by the guardians of an identity.
+	#[pallet::storage]
+	#[pallet::getter(fn active_recovery_of)]
+	pub(super) type ActiveRecoveryOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ActiveRecoveryFor<T>, OptionQuery>;
+
+	/// Controllers jointly owning an identity in place of its single owner.
+	#[pallet::storage]
+	#[pallet::getter(fn controllers_of)]
+	pub(super) type ControllersOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ControllerSetFor<T>, OptionQuery>;
+
+	/// Calls proposed to the controllers of an identity, by hash.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_of)]
+	pub(super) type ProposalOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		T::Hash,
+		PendingProposalFor<T>,
+		OptionQuery,
+	>;
+
+	/// Block number at which an identity was deactivated for good.
+	/// Its delegates and attributes are no longer valid from then on.
+	#[pallet::storage]
+	#[pallet::getter(fn deactivated_at)]
+	pub(super) type DeactivatedAt<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+	/// Address of an Ethereum account and the native account paying the deposits of the
+	/// identities it owns.
+	#[pallet::storage]
+	#[pallet::getter(fn ethereum_link)]
+	pub(super) type EthereumLinkOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (H160, T::AccountId), OptionQuery>;
+
+	/// Ethereum address of the accounts derived from one by `ethereum_account`, recorded once
+	/// the address signed for its account. The account is a hash of the address, so the
+	/// address cannot be told from the account otherwise.
+	#[pallet::storage]
+	#[pallet::getter(fn ethereum_address_of)]
+	pub(super) type EthereumAddressOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, H160, OptionQuery>;
+
+	/// Registered handles, expired ones until registered again or released.
+	#[pallet::storage]
+	#[pallet::getter(fn handle_of)]
+	pub(super) type HandleOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, Handle<T>, HandleRecordFor<T>, OptionQuery>;
+
+	/// Handle of an identity, the reverse of `HandleOf`.
+	#[pallet::storage]
+	#[pallet::getter(fn handle_by_identity)]
+	pub(super) type HandleByIdentity<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Handle<T>, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn updated_by)]
 	pub(super) type UpdatedBy<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		(T::AccountId, T::BlockNumber, <<T as Config>::Time as Time>::Moment),
+		(T::AccountId, T::BlockNumber, MomentOf<T>),
 		OptionQuery,
 	>;
 
+	/// Latest version of the DID document of an identity, pointing to its previous change.
+	#[pallet::storage]
+	#[pallet::getter(fn document_version)]
+	pub(super) type DocumentVersionOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DocumentVersionFor<T>, OptionQuery>;
+
+	/// Origin of the proposals approved by the controllers of an identity.
+	#[pallet::origin]
+	pub type Origin<T> = RawOrigin<<T as frame_system::Config>::AccountId>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -90,7 +418,49 @@ pub mod pallet {
 		AttributeAdded(T::AccountId, Vec<u8>, Option<T::BlockNumber>),
 		AttributeRevoked(T::AccountId, Vec<u8>, T::BlockNumber),
 		AttributeDeleted(T::AccountId, Vec<u8>, T::BlockNumber),
-		AttributeTransactionExecuted(AttributeTransaction<T::Signature, T::AccountId>),
+		AttributeTransactionExecuted(AttributeTransaction<SignatureOf<T>, T::AccountId>),
+		/// An expired delegate was pruned. \[identity, delegate_type, delegate\]
+		DelegatePruned(T::AccountId, Vec<u8>, T::AccountId),
+		/// An expired attribute was pruned. \[identity, name\]
+		AttributePruned(T::AccountId, Vec<u8>),
+		/// The guardians of an identity were set. \[identity, guardians, threshold, delay\]
+		GuardiansSet(T::AccountId, Vec<T::AccountId>, u32, T::BlockNumber),
+		/// The guardians of an identity were removed. \[identity\]
+		GuardiansRemoved(T::AccountId),
+		/// A guardian opened a recovery. \[identity, guardian, new_owner\]
+		RecoveryInitiated(T::AccountId, T::AccountId, T::AccountId),
+		/// A guardian approved the open recovery. \[identity, guardian\]
+		RecoveryApproved(T::AccountId, T::AccountId),
+		/// The owner cancelled the open recovery. \[identity, owner\]
+		RecoveryCancelled(T::AccountId, T::AccountId),
+		/// The ownership of an identity was recovered. \[identity, new_owner\]
+		RecoveryCompleted(T::AccountId, T::AccountId),
+		/// The controllers of an identity were set. \[identity, controllers, threshold\]
+		ControllersSet(T::AccountId, Vec<T::AccountId>, u32),
+		/// The controllers of an identity were removed. \[identity\]
+		ControllersRemoved(T::AccountId),
+		/// A controller proposed a call. \[identity, proposal_hash, proposer\]
+		ProposalCreated(T::AccountId, T::Hash, T::AccountId),
+		/// A controller approved a proposal. \[identity, proposal_hash, controller\]
+		ProposalApproved(T::AccountId, T::Hash, T::AccountId),
+		/// The proposer cancelled a proposal. \[identity, proposal_hash\]
+		ProposalCancelled(T::AccountId, T::Hash),
+		/// An approved proposal was executed. \[identity, proposal_hash, result\]
+		ProposalExecuted(T::AccountId, T::Hash, DispatchResult),
+		/// An identity was deactivated for good. \[identity, who\]
+		IdentityDeactivated(T::AccountId, T::AccountId),
+		/// A document was updated by a batch of operations. \[identity, who, operations\]
+		DocumentUpdated(T::AccountId, T::AccountId, u32),
+		/// \[address, ethereum account, native account\]
+		EthereumAccountLinked(H160, T::AccountId, T::AccountId),
+		/// A handle was registered. \[handle, identity, expiry\]
+		HandleRegistered(Vec<u8>, T::AccountId, T::BlockNumber),
+		/// A handle was renewed. \[handle, expiry\]
+		HandleRenewed(Vec<u8>, T::BlockNumber),
+		/// A handle was transferred to another identity. \[handle, from, to\]
+		HandleTransferred(Vec<u8>, T::AccountId, T::AccountId),
+		/// A handle was released. \[handle, identity\]
+		HandleReleased(Vec<u8>, T::AccountId),
 	}
 
 	#[pallet::error]
@@ -105,41 +475,150 @@ pub mod pallet {
 		InvalidAttribute,
 		Overflow,
 		BadTransaction,
+		TransactionReplayed,
+		TransactionExpired,
+		InsufficientDeposit,
+		/// The guardian set is empty, too large, duplicated or its threshold is unreachable.
+		InvalidGuardians,
+		/// The identity has no guardians.
+		NoGuardians,
+		/// The caller is not a guardian of the identity.
+		NotGuardian,
+		/// A recovery is already open for the identity.
+		RecoveryInProgress,
+		/// No recovery is open for the identity.
+		NoRecovery,
+		/// The guardian or controller already approved the recovery or proposal.
+		AlreadyApproved,
+		/// The open recovery lacks approvals or is still within its delay.
+		RecoveryNotReady,
+		/// The controller set is empty, too large, duplicated or its threshold is unreachable.
+		InvalidControllers,
+		/// The identity has no controllers.
+		NoControllers,
+		/// The caller is not a controller of the identity.
+		NotController,
+		/// The identity is owned by its controllers, the operation has to be proposed to them.
+		ApprovalRequired,
+		/// The call is already proposed.
+		ProposalExists,
+		/// No such call is proposed.
+		UnknownProposal,
+		/// The caller did not create the proposal.
+		NotProposer,
+		/// The proposal lacks approvals.
+		ThresholdNotReached,
+		/// The identity is deactivated and can no longer be updated.
+		Deactivated,
+		/// The delegate type names an unknown verification method kind or one that cannot sign.
+		InvalidVerificationMethod,
+		/// The delegate type is neither a verification method nor a registered permission.
+		UnknownDelegateType,
+		/// The public key attribute has an unknown kind or encoding, or malformed key material.
+		InvalidPublicKey,
+		/// The document update has no operations.
+		NoOperations,
+		/// The document update has more operations than `MaxOperations`.
+		TooManyOperations,
+		/// The Ethereum account is linked to a native account already.
+		AlreadyLinked,
+		/// The Ethereum account holds reserved funds, which its native account could not release.
+		AccountHasDeposits,
+		/// The handle is too short or too long, or not made of lowercase labels separated by dots.
+		InvalidHandle,
+		/// The handle is registered and not expired.
+		HandleTaken,
+		/// The handle is not registered.
+		HandleNotFound,
+		/// The handle is expired, it has to be registered again.
+		HandleExpired,
+		/// The identity has a registered handle already.
+		IdentityHasHandle,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Identities owned by another account, `(identity, owner)`.
+		pub owners: Vec<(T::AccountId, T::AccountId)>,
+		/// Delegates valid forever, `(identity, delegate_type, delegate)`.
+		pub delegates: Vec<(T::AccountId, Vec<u8>, T::AccountId)>,
+		/// Attributes valid forever, `(identity, name, value)`.
+		pub attributes: Vec<(T::AccountId, Vec<u8>, Vec<u8>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { owners: Vec::new(), delegates: Vec::new(), attributes: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			// Provisioned items go through the checks and deposits of the calls,
+			// owners first so that the deposits are reserved from them.
+			for (identity, owner) in &self.owners {
+				Pallet::<T>::update_owner(identity, identity, owner)
+					.expect("genesis owners can reserve the deposits of their identity");
+			}
+			for (identity, delegate_type, delegate) in &self.delegates {
+				let owner = Pallet::<T>::identity_owner(identity);
+				Pallet::<T>::do_add_delegate(
+					&Caller::Account(owner),
+					identity.clone(),
+					delegate.clone(),
+					delegate_type.clone(),
+					None,
+				)
+				.expect("genesis delegates are valid and their deposit can be reserved");
+			}
+			for (identity, name, value) in &self.attributes {
+				let owner = Pallet::<T>::identity_owner(identity);
+				Pallet::<T>::create_attribute(identity, name, value, None)
+					.expect("genesis attributes are valid and their deposit can be reserved");
+				Pallet::<T>::record_change(identity, &owner);
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migration::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migration::post_upgrade::<T>()
+		}
+
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_expired(now, remaining_weight)
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Transfers ownership of an identity.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::change_owner())]
 		pub fn change_owner(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			new_owner: T::AccountId,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			Self::is_owner(&identity, &who)?;
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
 
-			let now_timestamp = T::Time::now();
-			let now_block_number = <frame_system::Pallet<T>>::block_number();
-
-			if <OwnerOf<T>>::contains_key(&identity) {
-				// Update to new owner.
-				<OwnerOf<T>>::mutate(&identity, |o| *o = Some(new_owner.clone()));
-			} else {
-				// Add to new owner.
-				<OwnerOf<T>>::insert(&identity, &new_owner);
-			}
-
-			// Save the update time and block.
-			<UpdatedBy<T>>::insert(&identity, (&who, &now_block_number, &now_timestamp));
-
-			Self::deposit_event(Event::OwnerChanged(identity, who, new_owner, now_block_number));
-
-			Ok(())
+			Self::update_owner(&who, &identity, &new_owner)
 		}
 
 		/// Creates a new delegate with an expiration period and for a specific purpose.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_delegate())]
 		pub fn add_delegate(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
@@ -147,51 +626,25 @@ pub mod pallet {
 			delegate_type: Vec<u8>,
 			valid_for: Option<T::BlockNumber>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(delegate_type.len() <= 64, Error::<T>::InvalidDelegate);
-
-			Self::create_delegate(&who, &identity, &delegate, &delegate_type, valid_for)?;
-
-			let now_timestamp = T::Time::now();
-			let now_block_number = <frame_system::Pallet<T>>::block_number();
-			<UpdatedBy<T>>::insert(&identity, (who, now_block_number, now_timestamp));
-
-			Self::deposit_event(Event::DelegateAdded(identity, delegate_type, delegate, valid_for));
-
-			Ok(())
+			let caller = Self::ensure_caller(origin)?;
+			Self::do_add_delegate(&caller, identity, delegate, delegate_type, valid_for)
 		}
 
 		/// Revokes an identity's delegate by setting its expiration to the current block number.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::revoke_delegate())]
 		pub fn revoke_delegate(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			delegate_type: Vec<u8>,
 			delegate: T::AccountId,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			Self::is_owner(&identity, &who)?;
-			Self::valid_listed_delegate(&identity, &delegate_type, &delegate)?;
-			ensure!(delegate_type.len() <= 64, Error::<T>::InvalidDelegate);
-
-			let now_timestamp = T::Time::now();
-			let now_block_number = <frame_system::Pallet<T>>::block_number();
-
-			// Update only the validity period to revoke the delegate.
-			<DelegateOf<T>>::mutate((&identity, &delegate_type, &delegate), |b| {
-				*b = Some(now_block_number);
-			});
-
-			<UpdatedBy<T>>::insert(&identity, (who, now_block_number, now_timestamp));
-
-			Self::deposit_event(Event::DelegateRevoked(identity, delegate_type, delegate));
-
-			Ok(())
+			let caller = Self::ensure_caller(origin)?;
+			Self::do_revoke_delegate(&caller, identity, delegate_type, delegate)
 		}
 
 		/// Creates a new attribute as part of an identity.
 		/// Sets its expiration period.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::add_attribute(value.len() as u32))]
 		pub fn add_attribute(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
@@ -199,10 +652,15 @@ pub mod pallet {
 			value: Vec<u8>,
 			valid_for: Option<T::BlockNumber>,
 		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
-			ensure!(name.len() <= 64, Error::<T>::AttributeCreationFailed);
+			let caller = Self::ensure_caller(origin)?;
+			ensure!(
+				name.len() <= T::MaxNameLength::get() as usize,
+				Error::<T>::AttributeCreationFailed
+			);
 
-			Self::create_attribute(&who, &identity, &name, &value, valid_for)?;
+			let who = Self::authorize(&caller, &identity, Some(Operation::AddAttribute))?;
+			Self::create_attribute(&identity, &name, &value, valid_for)?;
+			Self::record_change(&identity, &who);
 
 			Self::deposit_event(Event::AttributeAdded(identity, name, valid_for));
 
@@ -211,82 +669,1011 @@ pub mod pallet {
 
 		/// Revokes an attribute/property an identity.
 		/// Sets its expiration period to the actual block number.
-		#[pallet::weight(0)]
+		#[pallet::weight(T::WeightInfo::revoke_attribute())]
 		pub fn revoke_attribute(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
 			name: Vec<u8>,
+		) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			Self::do_revoke_attribute(&caller, identity, name)
+		}
+
+		/// Removes an attribute from an identity. This attribute/property becomes unavailable.
+		#[pallet::weight(T::WeightInfo::delete_attribute())]
+		pub fn delete_attribute(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			name: Vec<u8>,
+		) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			Self::do_delete_attribute(&caller, identity, name)
+		}
+
+		/// Applies a batch of delegate and attribute operations to the document of an identity,
+		/// each authorized as the call of its own. Either all of them are applied or, if any
+		/// fails, none, and the document gets a single new version.
+		/// The weight grows with the number of operations and their encoded size.
+		#[pallet::weight(T::WeightInfo::update_document(
+			operations.len() as u32,
+			operations.encoded_size() as u32,
+		))]
+		pub fn update_document(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			operations: DidOperations<T>,
+		) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			ensure!(!operations.is_empty(), Error::<T>::NoOperations);
+
+			// Dispatched calls are transactional, the changes of a failing batch are discarded.
+			for operation in operations.iter() {
+				Self::apply_operation(&caller, &identity, operation)?;
+			}
+			let who = caller.account().clone();
+
+			Self::record_change(&identity, &who);
+
+			Self::deposit_event(Event::DocumentUpdated(identity, who, operations.len() as u32));
+
+			Ok(())
+		}
+
+		/// Executes off-chain signed transaction.
+		#[pallet::weight(T::WeightInfo::execute(transaction.value.len() as u32))]
+		pub fn execute(
+			origin: OriginFor<T>,
+			transaction: AttributeTransaction<SignatureOf<T>, T::AccountId>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(name.len() <= 64, Error::<T>::AttributeRemovalFailed);
 
-			Self::reset_attribute(&who, &identity, &name)?;
+			let encoded = Self::attribute_transaction_payload(&transaction);
 
-			Self::deposit_event(Event::AttributeRevoked(
-				identity,
-				name,
-				<frame_system::Pallet<T>>::block_number(),
-			));
+			// Execute the storage update if the signer is valid.
+			Self::signed_attribute(who, &encoded, &transaction)?;
+
+			Self::deposit_event(Event::AttributeTransactionExecuted(transaction));
 
 			Ok(())
 		}
 
-		/// Removes an attribute from an identity. This attribute/property becomes unavailable.
-		#[pallet::weight(0)]
-		pub fn delete_attribute(
+		/// Transfers ownership of an identity on behalf of its owner,
+		/// who signed the transaction off-chain.
+		#[pallet::weight(T::WeightInfo::change_owner_signed())]
+		pub fn change_owner_signed(
+			origin: OriginFor<T>,
+			transaction: ChangeOwnerTransaction<SignatureOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let signer = &transaction.signer;
+			Self::is_owner(&transaction.identity, signer)?;
+			Self::check_transaction(
+				&transaction.identity,
+				signer,
+				&transaction.signature,
+				&Self::change_owner_payload(&transaction),
+				&Self::change_owner_struct_hash(&transaction),
+				transaction.nonce,
+				transaction.expiry,
+			)?;
+
+			Self::update_owner(signer, &transaction.identity, &transaction.new_owner)
+		}
+
+		/// Creates a new delegate on behalf of a signer who signed the transaction off-chain.
+		#[pallet::weight(T::WeightInfo::add_delegate_signed())]
+		pub fn add_delegate_signed(
+			origin: OriginFor<T>,
+			transaction: AddDelegateTransaction<SignatureOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::check_transaction(
+				&transaction.identity,
+				&transaction.signer,
+				&transaction.signature,
+				&Self::add_delegate_payload(&transaction),
+				&Self::add_delegate_struct_hash(&transaction),
+				transaction.nonce,
+				transaction.expiry,
+			)?;
+
+			Self::do_add_delegate(
+				&Caller::Account(transaction.signer),
+				transaction.identity,
+				transaction.delegate,
+				transaction.delegate_type,
+				transaction.valid_for.map(Into::into),
+			)
+		}
+
+		/// Revokes a delegate on behalf of a signer who signed the transaction off-chain.
+		#[pallet::weight(T::WeightInfo::revoke_delegate_signed())]
+		pub fn revoke_delegate_signed(
+			origin: OriginFor<T>,
+			transaction: RevokeDelegateTransaction<SignatureOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::check_transaction(
+				&transaction.identity,
+				&transaction.signer,
+				&transaction.signature,
+				&Self::revoke_delegate_payload(&transaction),
+				&Self::revoke_delegate_struct_hash(&transaction),
+				transaction.nonce,
+				transaction.expiry,
+			)?;
+
+			Self::do_revoke_delegate(
+				&Caller::Account(transaction.signer),
+				transaction.identity,
+				transaction.delegate_type,
+				transaction.delegate,
+			)
+		}
+
+		/// Revokes an attribute on behalf of a signer who signed the transaction off-chain.
+		#[pallet::weight(T::WeightInfo::revoke_attribute_signed())]
+		pub fn revoke_attribute_signed(
+			origin: OriginFor<T>,
+			transaction: RevokeAttributeTransaction<SignatureOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::check_transaction(
+				&transaction.identity,
+				&transaction.signer,
+				&transaction.signature,
+				&Self::revoke_attribute_payload(&transaction),
+				&Self::revoke_attribute_struct_hash(&transaction),
+				transaction.nonce,
+				transaction.expiry,
+			)?;
+
+			Self::do_revoke_attribute(
+				&Caller::Account(transaction.signer),
+				transaction.identity,
+				transaction.name,
+			)
+		}
+
+		/// Deletes an attribute on behalf of a signer who signed the transaction off-chain.
+		#[pallet::weight(T::WeightInfo::delete_attribute_signed())]
+		pub fn delete_attribute_signed(
+			origin: OriginFor<T>,
+			transaction: DeleteAttributeTransaction<SignatureOf<T>, T::AccountId>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::check_transaction(
+				&transaction.identity,
+				&transaction.signer,
+				&transaction.signature,
+				&Self::delete_attribute_payload(&transaction),
+				&Self::delete_attribute_struct_hash(&transaction),
+				transaction.nonce,
+				transaction.expiry,
+			)?;
+
+			Self::do_delete_attribute(
+				&Caller::Account(transaction.signer),
+				transaction.identity,
+				transaction.name,
+			)
+		}
+
+		/// Links the Ethereum account of `address` to the caller, who proves it by an EIP-191
+		/// signature of `link_account_payload`. The caller then pays the deposits of the
+		/// identities owned by the Ethereum account, so the link is permanent.
+		#[pallet::weight(T::WeightInfo::link_ethereum_account())]
+		pub fn link_ethereum_account(
+			origin: OriginFor<T>,
+			address: H160,
+			signature: ecdsa::Signature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let account = Self::ethereum_account(&address);
+			ensure!(!<EthereumLinkOf<T>>::contains_key(&account), Error::<T>::AlreadyLinked);
+			ensure!(
+				T::Currency::reserved_balance(&account).is_zero(),
+				Error::<T>::AccountHasDeposits
+			);
+
+			let hash = Self::eip191_hash(&Self::link_account_payload(&address, &who));
+			ensure!(
+				Self::ethereum_signer(&signature, &hash) == Some(address),
+				Error::<T>::BadSignature
+			);
+
+			<EthereumLinkOf<T>>::insert(&account, (address, &who));
+			<EthereumAddressOf<T>>::insert(&account, address);
+
+			Self::deposit_event(Event::EthereumAccountLinked(address, account, who));
+
+			Ok(())
+		}
+
+		/// Registers a handle for an identity, for `HandlePeriod` blocks.
+		/// An expired handle can be registered again by anyone, the deposit of its previous
+		/// registration being returned.
+		#[pallet::weight(T::WeightInfo::register_handle())]
+		pub fn register_handle(
 			origin: OriginFor<T>,
 			identity: T::AccountId,
-			name: Vec<u8>,
+			handle: Vec<u8>,
 		) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+			let bounded_handle = Self::valid_handle(&handle)?;
+
+			if let Some(record) = Self::handle_of(&bounded_handle) {
+				ensure!(Self::is_expired(&record), Error::<T>::HandleTaken);
+				Self::clear_handle(&bounded_handle, record);
+			}
+			if let Some(previous) = Self::handle_by_identity(&identity) {
+				match Self::handle_of(&previous) {
+					Some(record) if !Self::is_expired(&record) =>
+						return Err(Error::<T>::IdentityHasHandle.into()),
+					Some(record) => Self::clear_handle(&previous, record),
+					None => <HandleByIdentity<T>>::remove(&identity),
+				}
+			}
+
+			let deposit = Self::storage_deposit(handle.len());
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			let expiry = <frame_system::Pallet<T>>::block_number() + T::HandlePeriod::get();
+			let record = HandleRecord {
+				identity: identity.clone(),
+				depositor: who.clone(),
+				deposit,
+				expiry,
+			};
+			<HandleOf<T>>::insert(&bounded_handle, record);
+			<HandleByIdentity<T>>::insert(&identity, &bounded_handle);
+			Self::record_change(&identity, &who);
+
+			Self::deposit_event(Event::HandleRegistered(handle, identity, expiry));
+
+			Ok(())
+		}
+
+		/// Renews a handle for `HandlePeriod` blocks from now, even if it expired but was not
+		/// registered again.
+		#[pallet::weight(T::WeightInfo::renew_handle())]
+		pub fn renew_handle(origin: OriginFor<T>, handle: Vec<u8>) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			let bounded_handle: Handle<T> =
+				handle.clone().try_into().map_err(|_| Error::<T>::HandleNotFound)?;
+			let mut record = Self::handle_of(&bounded_handle).ok_or(Error::<T>::HandleNotFound)?;
+			Self::authorize(&caller, &record.identity, None)?;
+
+			record.expiry = <frame_system::Pallet<T>>::block_number() + T::HandlePeriod::get();
+			let expiry = record.expiry;
+			<HandleOf<T>>::insert(&bounded_handle, record);
+
+			Self::deposit_event(Event::HandleRenewed(handle, expiry));
+
+			Ok(())
+		}
+
+		/// Points a handle to another identity, which must not have a handle yet.
+		/// The handle keeps its expiry and its deposit stays reserved from its depositor.
+		#[pallet::weight(T::WeightInfo::transfer_handle())]
+		pub fn transfer_handle(
+			origin: OriginFor<T>,
+			handle: Vec<u8>,
+			new_identity: T::AccountId,
+		) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			let bounded_handle: Handle<T> =
+				handle.clone().try_into().map_err(|_| Error::<T>::HandleNotFound)?;
+			let mut record = Self::handle_of(&bounded_handle).ok_or(Error::<T>::HandleNotFound)?;
+			let who = Self::authorize(&caller, &record.identity, None)?;
+			ensure!(!Self::is_expired(&record), Error::<T>::HandleExpired);
+			ensure!(!Self::is_deactivated(&new_identity), Error::<T>::Deactivated);
+			ensure!(Self::identity_handle(&new_identity).is_none(), Error::<T>::IdentityHasHandle);
+			if let Some(previous) = Self::handle_by_identity(&new_identity) {
+				if let Some(expired) = Self::handle_of(&previous) {
+					Self::clear_handle(&previous, expired);
+				}
+			}
+
+			let identity = record.identity.clone();
+			record.identity = new_identity.clone();
+			<HandleOf<T>>::insert(&bounded_handle, record);
+			<HandleByIdentity<T>>::remove(&identity);
+			<HandleByIdentity<T>>::insert(&new_identity, &bounded_handle);
+			Self::record_change(&identity, &who);
+			Self::record_change(&new_identity, &who);
+
+			Self::deposit_event(Event::HandleTransferred(handle, identity, new_identity));
+
+			Ok(())
+		}
+
+		/// Releases a handle and returns its deposit.
+		/// Anyone can release an expired handle, its deposit still going back to its depositor.
+		#[pallet::weight(T::WeightInfo::release_handle())]
+		pub fn release_handle(origin: OriginFor<T>, handle: Vec<u8>) -> DispatchResult {
+			let caller = Self::ensure_caller(origin)?;
+			let bounded_handle: Handle<T> =
+				handle.clone().try_into().map_err(|_| Error::<T>::HandleNotFound)?;
+			let record = Self::handle_of(&bounded_handle).ok_or(Error::<T>::HandleNotFound)?;
+			if !Self::is_expired(&record) {
+				Self::authorize(&caller, &record.identity, None)?;
+			}
+
+			let identity = record.identity.clone();
+			Self::clear_handle(&bounded_handle, record);
+			Self::record_change(&identity, caller.account());
+
+			Self::deposit_event(Event::HandleReleased(handle, identity));
+
+			Ok(())
+		}
+
+		/// Sets the guardians able to recover the ownership of an identity.
+		/// `threshold` guardians have to approve a recovery, which the owner can cancel
+		/// for `delay` blocks after it is opened.
+		#[pallet::weight(T::WeightInfo::set_guardians(guardians.len() as u32))]
+		pub fn set_guardians(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			guardians: Vec<T::AccountId>,
+			threshold: u32,
+			delay: T::BlockNumber,
+		) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+			ensure!(
+				!<ActiveRecoveryOf<T>>::contains_key(&identity),
+				Error::<T>::RecoveryInProgress
+			);
+
+			let mut sorted = guardians.clone();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == guardians.len(), Error::<T>::InvalidGuardians);
+			ensure!(
+				threshold > 0 && threshold as usize <= guardians.len(),
+				Error::<T>::InvalidGuardians
+			);
+			let bounded: Guardians<T> =
+				guardians.clone().try_into().map_err(|_| Error::<T>::InvalidGuardians)?;
+
+			// The deposit follows the size of the guardian set.
+			if let Some(config) = Self::recovery_config_of(&identity) {
+				let deposit = Self::storage_deposit(config.guardians.encoded_size());
+				Self::release_deposit(&identity, deposit);
+			}
+			Self::reserve_deposit(&identity, Self::storage_deposit(bounded.encoded_size()))?;
+			<RecoveryConfigOf<T>>::insert(
+				&identity,
+				RecoveryConfig { guardians: bounded, threshold, delay },
+			);
+
+			Self::record_change(&identity, &who);
+
+			Self::deposit_event(Event::GuardiansSet(identity, guardians, threshold, delay));
+
+			Ok(())
+		}
+
+		/// Removes the guardians of an identity.
+		#[pallet::weight(T::WeightInfo::remove_guardians())]
+		pub fn remove_guardians(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+			ensure!(
+				!<ActiveRecoveryOf<T>>::contains_key(&identity),
+				Error::<T>::RecoveryInProgress
+			);
+
+			let config = <RecoveryConfigOf<T>>::take(&identity).ok_or(Error::<T>::NoGuardians)?;
+			let deposit = Self::storage_deposit(config.guardians.encoded_size());
+			Self::release_deposit(&identity, deposit);
+
+			Self::record_change(&identity, &who);
+
+			Self::deposit_event(Event::GuardiansRemoved(identity));
+
+			Ok(())
+		}
+
+		/// Opens a recovery moving the ownership of an identity to `new_owner`.
+		/// The initiating guardian approves it.
+		#[pallet::weight(T::WeightInfo::initiate_recovery())]
+		pub fn initiate_recovery(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let config = Self::recovery_config_of(&identity).ok_or(Error::<T>::NoGuardians)?;
+			ensure!(config.guardians.contains(&who), Error::<T>::NotGuardian);
+			ensure!(
+				!<ActiveRecoveryOf<T>>::contains_key(&identity),
+				Error::<T>::RecoveryInProgress
+			);
+
+			let mut approvals = Guardians::<T>::default();
+			approvals.try_push(who.clone()).map_err(|_| Error::<T>::InvalidGuardians)?;
+			<ActiveRecoveryOf<T>>::insert(
+				&identity,
+				ActiveRecovery {
+					new_owner: new_owner.clone(),
+					opened: <frame_system::Pallet<T>>::block_number(),
+					approvals,
+				},
+			);
+
+			Self::deposit_event(Event::RecoveryInitiated(identity, who, new_owner));
+
+			Ok(())
+		}
+
+		/// Approves the recovery open for an identity.
+		#[pallet::weight(T::WeightInfo::approve_recovery(T::MaxGuardians::get()))]
+		pub fn approve_recovery(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let config = Self::recovery_config_of(&identity).ok_or(Error::<T>::NoGuardians)?;
+			ensure!(config.guardians.contains(&who), Error::<T>::NotGuardian);
+
+			<ActiveRecoveryOf<T>>::try_mutate(&identity, |recovery| -> DispatchResult {
+				let recovery = recovery.as_mut().ok_or(Error::<T>::NoRecovery)?;
+				ensure!(!recovery.approvals.contains(&who), Error::<T>::AlreadyApproved);
+				recovery
+					.approvals
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::InvalidGuardians)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RecoveryApproved(identity, who));
+
+			Ok(())
+		}
+
+		/// Cancels the recovery open for an identity.
+		#[pallet::weight(T::WeightInfo::cancel_recovery())]
+		pub fn cancel_recovery(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+			<ActiveRecoveryOf<T>>::take(&identity).ok_or(Error::<T>::NoRecovery)?;
+
+			Self::deposit_event(Event::RecoveryCancelled(identity, who));
+
+			Ok(())
+		}
+
+		/// Completes the recovery open for an identity once it has enough approvals and
+		/// its delay has passed. The ownership moves as with `change_owner`.
+		#[pallet::weight(T::WeightInfo::complete_recovery())]
+		pub fn complete_recovery(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			Self::is_owner(&identity, &who)?;
-			ensure!(name.len() <= 64, Error::<T>::AttributeRemovalFailed);
+			let config = Self::recovery_config_of(&identity).ok_or(Error::<T>::NoGuardians)?;
+			let recovery = Self::active_recovery_of(&identity).ok_or(Error::<T>::NoRecovery)?;
+
+			let now_block_number = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				recovery.approvals.len() as u32 >= config.threshold &&
+					now_block_number >= recovery.opened.saturating_add(config.delay),
+				Error::<T>::RecoveryNotReady
+			);
+
+			<ActiveRecoveryOf<T>>::remove(&identity);
+			Self::update_owner(&who, &identity, &recovery.new_owner)?;
+
+			Self::deposit_event(Event::RecoveryCompleted(identity, recovery.new_owner));
+
+			Ok(())
+		}
+
+		/// Hands the ownership of an identity to a set of controllers.
+		/// Owner operations then have to be proposed and approved by `threshold` of them.
+		/// The owner keeps holding the storage deposit of the identity.
+		#[pallet::weight(T::WeightInfo::set_controllers(controllers.len() as u32))]
+		pub fn set_controllers(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			controllers: Vec<T::AccountId>,
+			threshold: u32,
+		) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+
+			let mut sorted = controllers.clone();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == controllers.len(), Error::<T>::InvalidControllers);
+			ensure!(
+				threshold > 0 && threshold as usize <= controllers.len(),
+				Error::<T>::InvalidControllers
+			);
+			let bounded: Controllers<T> =
+				controllers.clone().try_into().map_err(|_| Error::<T>::InvalidControllers)?;
+
+			// The deposit follows the size of the controller set.
+			if let Some(set) = Self::controllers_of(&identity) {
+				let deposit = Self::storage_deposit(set.controllers.encoded_size());
+				Self::release_deposit(&identity, deposit);
+			}
+			Self::reserve_deposit(&identity, Self::storage_deposit(bounded.encoded_size()))?;
+			<ControllersOf<T>>::insert(
+				&identity,
+				ControllerSet { controllers: bounded, threshold },
+			);
+
+			Self::record_change(&identity, &who);
+
+			Self::deposit_event(Event::ControllersSet(identity, controllers, threshold));
+
+			Ok(())
+		}
+
+		/// Removes the controllers of an identity, handing it back to its single owner.
+		#[pallet::weight(T::WeightInfo::remove_controllers())]
+		pub fn remove_controllers(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+			Self::clear_controllers(&identity)?;
+
+			Self::record_change(&identity, &who);
+
+			Ok(())
+		}
+
+		/// Proposes a call to the controllers of an identity, approving it at once.
+		/// A deposit is reserved from the proposer until the proposal is executed or cancelled.
+		#[pallet::weight(T::WeightInfo::propose())]
+		pub fn propose(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			proposal: Box<T::Proposal>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let set = Self::controllers_of(&identity).ok_or(Error::<T>::NoControllers)?;
+			ensure!(set.controllers.contains(&who), Error::<T>::NotController);
+
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			ensure!(
+				!<ProposalOf<T>>::contains_key(&identity, &proposal_hash),
+				Error::<T>::ProposalExists
+			);
+
+			let deposit = Self::storage_deposit(PendingProposalFor::<T>::max_encoded_len());
+			T::Currency::reserve(&who, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			let mut approvals = Controllers::<T>::default();
+			approvals.try_push(who.clone()).map_err(|_| Error::<T>::InvalidControllers)?;
+			<ProposalOf<T>>::insert(
+				&identity,
+				&proposal_hash,
+				PendingProposal {
+					proposer: who.clone(),
+					deposit,
+					approvals,
+					created: <frame_system::Pallet<T>>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::ProposalCreated(identity, proposal_hash, who));
+
+			Ok(())
+		}
+
+		/// Approves a call proposed to the controllers of an identity.
+		#[pallet::weight(T::WeightInfo::approve_proposal(T::MaxControllers::get()))]
+		pub fn approve_proposal(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			proposal_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let set = Self::controllers_of(&identity).ok_or(Error::<T>::NoControllers)?;
+			ensure!(set.controllers.contains(&who), Error::<T>::NotController);
+
+			<ProposalOf<T>>::try_mutate(&identity, &proposal_hash, |proposal| -> DispatchResult {
+				let proposal = proposal.as_mut().ok_or(Error::<T>::UnknownProposal)?;
+				ensure!(!proposal.approvals.contains(&who), Error::<T>::AlreadyApproved);
+				proposal
+					.approvals
+					.try_push(who.clone())
+					.map_err(|_| Error::<T>::InvalidControllers)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ProposalApproved(identity, proposal_hash, who));
+
+			Ok(())
+		}
+
+		/// Cancels a proposal and returns its deposit to the proposer.
+		#[pallet::weight(T::WeightInfo::cancel_proposal())]
+		pub fn cancel_proposal(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			proposal_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let proposal =
+				Self::proposal_of(&identity, &proposal_hash).ok_or(Error::<T>::UnknownProposal)?;
+			ensure!(proposal.proposer == who, Error::<T>::NotProposer);
+
+			<ProposalOf<T>>::remove(&identity, &proposal_hash);
+			T::Currency::unreserve(&proposal.proposer, proposal.deposit);
+
+			Self::deposit_event(Event::ProposalCancelled(identity, proposal_hash));
+
+			Ok(())
+		}
+
+		/// Executes a proposal approved by `threshold` of the current controllers of an identity.
+		/// The call is dispatched from the identity origin, acting as the owner of the identity.
+		/// The call result is reported by `ProposalExecuted`.
+		#[pallet::weight({
+			let dispatch_info = proposal.get_dispatch_info();
+			(
+				T::WeightInfo::execute_proposal(T::MaxControllers::get())
+					.saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn execute_proposal(
+			origin: OriginFor<T>,
+			identity: T::AccountId,
+			proposal: Box<T::Proposal>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let set = Self::controllers_of(&identity).ok_or(Error::<T>::NoControllers)?;
+			ensure!(set.controllers.contains(&who), Error::<T>::NotController);
+
+			let proposal_hash = T::Hashing::hash_of(&proposal);
+			let pending =
+				Self::proposal_of(&identity, &proposal_hash).ok_or(Error::<T>::UnknownProposal)?;
+			// Approvals of former controllers no longer count.
+			let approvals =
+				pending.approvals.iter().filter(|a| set.controllers.contains(*a)).count() as u32;
+			ensure!(approvals >= set.threshold, Error::<T>::ThresholdNotReached);
+
+			<ProposalOf<T>>::remove(&identity, &proposal_hash);
+			T::Currency::unreserve(&pending.proposer, pending.deposit);
+
+			let result = (*proposal).dispatch(Self::identity_origin(identity.clone()));
+
+			let call_weight = match &result {
+				Ok(post_info) => post_info.actual_weight,
+				Err(err) => err.post_info.actual_weight,
+			};
+			Self::deposit_event(Event::ProposalExecuted(
+				identity,
+				proposal_hash,
+				result.map(|_| ()).map_err(|e| e.error),
+			));
+
+			let base_weight = T::WeightInfo::execute_proposal(set.controllers.len() as u32);
+			Ok(call_weight.map(|w| base_weight.saturating_add(w)).into())
+		}
+
+		/// Deactivates an identity for good, as W3C `deactivated: true`.
+		/// Its delegates and attributes become invalid at once and are pruned later on,
+		/// its guardians and controllers are removed, its handle is released and no further
+		/// write is accepted.
+		#[pallet::weight(T::WeightInfo::deactivate())]
+		pub fn deactivate(origin: OriginFor<T>, identity: T::AccountId) -> DispatchResult {
+			let who = Self::authorize(&Self::ensure_caller(origin)?, &identity, None)?;
+
+			<ActiveRecoveryOf<T>>::remove(&identity);
+			if let Some(config) = <RecoveryConfigOf<T>>::take(&identity) {
+				let deposit = Self::storage_deposit(config.guardians.encoded_size());
+				Self::release_deposit(&identity, deposit);
+			}
+			if <ControllersOf<T>>::contains_key(&identity) {
+				Self::clear_controllers(&identity)?;
+			}
+			// A deactivated identity no longer answers to its handle.
+			if let Some(handle) = Self::handle_by_identity(&identity) {
+				if let Some(record) = Self::handle_of(&handle) {
+					Self::clear_handle(&handle, record);
+					let handle = handle.into_inner();
+					Self::deposit_event(Event::HandleReleased(handle, identity.clone()));
+				}
+			}
+
+			<DeactivatedAt<T>>::insert(&identity, <frame_system::Pallet<T>>::block_number());
+			Self::record_change(&identity, &who);
+
+			T::OnDeactivate::on_deactivate(&identity);
+
+			Self::deposit_event(Event::IdentityDeactivated(identity, who));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Validates if the AccountId 'actual_owner' owns the identity.
+	/// No account owns an identity with controllers, which acts through the identity origin
+	/// of their approved proposals instead, see `authorize`.
+	pub fn is_owner(identity: &T::AccountId, actual_owner: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_deactivated(identity), Error::<T>::Deactivated);
+		if let Some(set) = Self::controllers_of(identity) {
+			ensure!(set.controllers.contains(actual_owner), Error::<T>::NotOwner);
+			return Err(Error::<T>::ApprovalRequired.into())
+		}
+		let owner = Self::identity_owner(identity);
+		match owner == *actual_owner {
+			true => Ok(()),
+			false => Err(Error::<T>::NotOwner.into()),
+		}
+	}
+
+	/// Returns the caller of a call signed by an account or dispatched from the identity origin.
+	pub fn ensure_caller(origin: OriginFor<T>) -> Result<Caller<T::AccountId>, DispatchError> {
+		let origin: Result<RawOrigin<T::AccountId>, T::OuterOrigin> =
+			T::OuterOrigin::from(origin).into();
+		match origin {
+			Ok(RawOrigin::Identity(identity)) => Ok(Caller::Identity(identity)),
+			Err(origin) => {
+				let origin: OriginFor<T> = origin.into();
+				Ok(Caller::Account(ensure_signed(origin)?))
+			},
+		}
+	}
+
+	/// Returns the origin of a call dispatched on behalf of an identity, as its owner.
+	pub fn identity_origin(identity: T::AccountId) -> OriginFor<T> {
+		T::OuterOrigin::from(RawOrigin::Identity(identity)).into()
+	}
+
+	/// Checks that `caller` may perform `operation` on an identity, or act as its owner if
+	/// none. An identity origin acts as the owner of its own identity, an account or another
+	/// identity as checked by `is_owner` and `ensure_authorized`.
+	/// Returns the account recorded as the author of the change.
+	pub fn authorize(
+		caller: &Caller<T::AccountId>,
+		identity: &T::AccountId,
+		operation: Option<Operation>,
+	) -> Result<T::AccountId, DispatchError> {
+		match (caller, operation) {
+			(Caller::Identity(own), _) if own == identity =>
+				ensure!(!Self::is_deactivated(identity), Error::<T>::Deactivated),
+			(caller, Some(operation)) =>
+				Self::ensure_authorized(identity, caller.account(), operation)?,
+			(caller, None) => Self::is_owner(identity, caller.account())?,
+		}
+		Ok(caller.account().clone())
+	}
+
+	/// Moves the ownership of an identity and its storage deposit to `new_owner`.
+	/// `who` is recorded as the author of the update.
+	fn update_owner(
+		who: &T::AccountId,
+		identity: &T::AccountId,
+		new_owner: &T::AccountId,
+	) -> DispatchResult {
+		// The new owner takes over the identity alone.
+		if <ControllersOf<T>>::contains_key(identity) {
+			Self::clear_controllers(identity)?;
+		}
+
+		// The storage deposit follows the identity to its new owner.
+		let owner = Self::identity_owner(identity);
+		Self::transfer_deposit(identity, &owner, new_owner)?;
+
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+
+		if <OwnerOf<T>>::contains_key(identity) {
+			// Update to new owner.
+			<OwnerOf<T>>::mutate(identity, |o| *o = Some(new_owner.clone()));
+		} else {
+			// Add to new owner.
+			<OwnerOf<T>>::insert(identity, new_owner);
+		}
+
+		// Save the update time and block.
+		Self::record_change(identity, who);
+
+		Self::deposit_event(Event::OwnerChanged(
+			identity.clone(),
+			who.clone(),
+			new_owner.clone(),
+			now_block_number,
+		));
+
+		Ok(())
+	}
+
+	/// Creates a new delegate for an identity on behalf of `caller`.
+	fn do_add_delegate(
+		caller: &Caller<T::AccountId>,
+		identity: T::AccountId,
+		delegate: T::AccountId,
+		delegate_type: Vec<u8>,
+		valid_for: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		let bounded_type: DelegateType<T> =
+			delegate_type.clone().try_into().map_err(|_| Error::<T>::InvalidDelegate)?;
+
+		Self::create_delegate(caller, &identity, &delegate, &bounded_type, valid_for)?;
+
+		Self::record_change(&identity, caller.account());
+
+		Self::deposit_event(Event::DelegateAdded(identity, delegate_type, delegate, valid_for));
+
+		Ok(())
+	}
+
+	/// Revokes a delegate of an identity on behalf of `caller`
+	/// by setting its expiration to the current block number.
+	fn do_revoke_delegate(
+		caller: &Caller<T::AccountId>,
+		identity: T::AccountId,
+		delegate_type: Vec<u8>,
+		delegate: T::AccountId,
+	) -> DispatchResult {
+		let who = Self::authorize(caller, &identity, Some(Operation::RevokeDelegate))?;
+		Self::expire_delegate(&identity, &delegate_type, &delegate)?;
+
+		Self::record_change(&identity, &who);
+
+		Self::deposit_event(Event::DelegateRevoked(identity, delegate_type, delegate));
+
+		Ok(())
+	}
+
+	/// Revokes an attribute of an identity on behalf of `caller`.
+	fn do_revoke_attribute(
+		caller: &Caller<T::AccountId>,
+		identity: T::AccountId,
+		name: Vec<u8>,
+	) -> DispatchResult {
+		ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::AttributeRemovalFailed);
+
+		let who = Self::authorize(caller, &identity, Some(Operation::RevokeAttribute))?;
+		Self::reset_attribute(&identity, &name)?;
+		Self::record_change(&identity, &who);
+
+		Self::deposit_event(Event::AttributeRevoked(
+			identity,
+			name,
+			<frame_system::Pallet<T>>::block_number(),
+		));
+
+		Ok(())
+	}
+
+	/// Deletes an attribute of an identity on behalf of `caller` and releases its deposit.
+	fn do_delete_attribute(
+		caller: &Caller<T::AccountId>,
+		identity: T::AccountId,
+		name: Vec<u8>,
+	) -> DispatchResult {
+		let who = Self::authorize(caller, &identity, Some(Operation::DeleteAttribute))?;
+		ensure!(name.len() <= T::MaxNameLength::get() as usize, Error::<T>::AttributeRemovalFailed);
+
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		Self::remove_attribute(&identity, &name)?;
+
+		Self::record_change(&identity, &who);
+
+		Self::deposit_event(Event::AttributeDeleted(identity, name, now_block_number));
+
+		Ok(())
+	}
+
+	/// Applies an operation of a document update on behalf of `caller`,
+	/// leaving the change to be recorded once for the whole update.
+	fn apply_operation(
+		caller: &Caller<T::AccountId>,
+		identity: &T::AccountId,
+		operation: &DidOperationFor<T>,
+	) -> DispatchResult {
+		match operation {
+			DidOperation::AddDelegate { delegate_type, delegate, valid_for } => {
+				let bounded_type: DelegateType<T> =
+					delegate_type.clone().try_into().map_err(|_| Error::<T>::InvalidDelegate)?;
+				Self::create_delegate(caller, identity, delegate, &bounded_type, *valid_for)
+			},
+			DidOperation::RevokeDelegate { delegate_type, delegate } => {
+				Self::authorize(caller, identity, Some(Operation::RevokeDelegate))?;
+				Self::expire_delegate(identity, delegate_type, delegate)
+			},
+			DidOperation::AddAttribute { name, value, valid_for } => {
+				Self::authorize(caller, identity, Some(Operation::AddAttribute))?;
+				Self::create_attribute(identity, name, value, *valid_for)
+			},
+			DidOperation::RevokeAttribute { name } => {
+				Self::authorize(caller, identity, Some(Operation::RevokeAttribute))?;
+				Self::reset_attribute(identity, name)
+			},
+			DidOperation::DeleteAttribute { name } => {
+				Self::authorize(caller, identity, Some(Operation::DeleteAttribute))?;
+				Self::remove_attribute(identity, name)
+			},
+		}
+	}
+
+	/// Validates the syntax of a handle: labels of lowercase letters, digits and hyphens,
+	/// separated by single dots, like a domain name.
+	fn valid_handle(handle: &[u8]) -> Result<Handle<T>, DispatchError> {
+		let valid = handle.len() >= MIN_HANDLE_LENGTH &&
+			handle.split(|c| *c == b'.').all(|label| {
+				!label.is_empty() &&
+					label.first() != Some(&b'-') &&
+					label.last() != Some(&b'-') &&
+					label
+						.iter()
+						.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-')
+			});
+		ensure!(valid, Error::<T>::InvalidHandle);
+		handle.to_vec().try_into().map_err(|_| Error::<T>::InvalidHandle.into())
+	}
 
-			let now_block_number = <frame_system::Pallet<T>>::block_number();
-			let result = Self::attribute_and_id(&identity, &name);
+	/// Whether a handle registration expired.
+	fn is_expired(record: &HandleRecordFor<T>) -> bool {
+		record.expiry <= <frame_system::Pallet<T>>::block_number()
+	}
 
-			match result {
-				Some((_, id)) => <AttributeOf<T>>::remove(&identity, &id),
-				None => return Err(Error::<T>::AttributeRemovalFailed.into()),
-			};
+	/// Removes a handle registration and returns its deposit.
+	fn clear_handle(handle: &Handle<T>, record: HandleRecordFor<T>) {
+		T::Currency::unreserve(&record.depositor, record.deposit);
+		<HandleOf<T>>::remove(handle);
+		if Self::handle_by_identity(&record.identity).as_ref() == Some(handle) {
+			<HandleByIdentity<T>>::remove(&record.identity);
+		}
+	}
 
-			<UpdatedBy<T>>::insert(&identity, (&who, &now_block_number, T::Time::now()));
+	/// Returns the identity a handle points to, if registered and not expired.
+	pub fn handle_identity(handle: &[u8]) -> Option<T::AccountId> {
+		let handle: Handle<T> = handle.to_vec().try_into().ok()?;
+		Self::handle_of(&handle)
+			.filter(|record| !Self::is_expired(record))
+			.map(|record| record.identity)
+	}
 
-			Self::deposit_event(Event::AttributeDeleted(identity, name, now_block_number));
+	/// Returns the handle of an identity, if registered and not expired.
+	pub fn identity_handle(identity: &T::AccountId) -> Option<Vec<u8>> {
+		let handle = Self::handle_by_identity(identity)?;
+		Self::handle_of(&handle)
+			.filter(|record| !Self::is_expired(record))
+			.map(|_| handle.into_inner())
+	}
 
-			Ok(())
-		}
+	/// Records a change of an identity made by `who`, bumping its document version.
+	/// Identities changed before versions were recorded point back to their latest update.
+	fn record_change(identity: &T::AccountId, who: &T::AccountId) {
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		let now_timestamp = T::Time::now();
 
-		/// Executes off-chain signed transaction.
-		#[pallet::weight(0)]
-		pub fn execute(
-			origin: OriginFor<T>,
-			transaction: AttributeTransaction<T::Signature, T::AccountId>,
-		) -> DispatchResult {
-			let who = ensure_signed(origin)?;
+		let (version_id, previous_change) = match Self::document_version(identity) {
+			Some(version) => (version.version_id.saturating_add(1), Some(version.block)),
+			None => (1, Self::updated_by(identity).map(|(_, block_number, _)| block_number)),
+		};
+		<DocumentVersionOf<T>>::insert(
+			identity,
+			DocumentVersion {
+				version_id,
+				block: now_block_number,
+				time: now_timestamp,
+				previous_change,
+			},
+		);
+		<UpdatedBy<T>>::insert(identity, (who, now_block_number, now_timestamp));
+	}
 
-			let mut encoded = transaction.name.encode();
-			encoded.extend(transaction.value.encode());
-			encoded.extend(transaction.validity.encode());
-			encoded.extend(transaction.identity.encode());
+	/// Removes the controllers of an identity and releases their deposit.
+	/// Pending proposals are kept but can no longer be executed.
+	fn clear_controllers(identity: &T::AccountId) -> DispatchResult {
+		let set = <ControllersOf<T>>::take(identity).ok_or(Error::<T>::NoControllers)?;
+		Self::release_deposit(identity, Self::storage_deposit(set.controllers.encoded_size()));
 
-			// Execute the storage update if the signer is valid.
-			Self::signed_attribute(who, &encoded, &transaction)?;
+		Self::deposit_event(Event::ControllersRemoved(identity.clone()));
 
-			Self::deposit_event(Event::AttributeTransactionExecuted(transaction));
+		Ok(())
+	}
 
-			Ok(())
-		}
+	/// Whether an identity is deactivated.
+	pub fn is_deactivated(identity: &T::AccountId) -> bool {
+		<DeactivatedAt<T>>::contains_key(identity)
 	}
-}
 
-impl<T: Config> Pallet<T> {
-	/// Validates if the AccountId 'actual_owner' owns the identity.
-	pub fn is_owner(identity: &T::AccountId, actual_owner: &T::AccountId) -> DispatchResult {
-		let owner = Self::identity_owner(identity);
-		match owner == *actual_owner {
-			true => Ok(()),
-			false => Err(Error::<T>::NotOwner.into()),
+	/// Caps the validity of a delegate or attribute at the deactivation of its identity.
+	fn capped_validity(identity: &T::AccountId, validity: T::BlockNumber) -> T::BlockNumber {
+		match Self::deactivated_at(identity) {
+			Some(deactivated) => validity.min(deactivated),
+			None => validity,
 		}
 	}
 
@@ -306,10 +1693,11 @@ impl<T: Config> Pallet<T> {
 		delegate_type: &[u8],
 		delegate: &T::AccountId,
 	) -> DispatchResult {
-		ensure!(delegate_type.len() <= 64, Error::<T>::InvalidDelegate);
+		let delegate_type: DelegateType<T> =
+			delegate_type.to_vec().try_into().map_err(|_| Error::<T>::InvalidDelegate)?;
 		ensure!(
-			Self::valid_listed_delegate(identity, delegate_type, delegate).is_ok()
-				|| Self::is_owner(identity, delegate).is_ok(),
+			Self::valid_listed_delegate(identity, &delegate_type, delegate).is_ok() ||
+				Self::is_owner(identity, delegate).is_ok(),
 			Error::<T>::InvalidDelegate
 		);
 		Ok(())
@@ -318,7 +1706,7 @@ impl<T: Config> Pallet<T> {
 	/// Validates that a delegate contains_key for specific purpose and remains valid at this block high.
 	fn valid_listed_delegate(
 		identity: &T::AccountId,
-		delegate_type: &[u8],
+		delegate_type: &DelegateType<T>,
 		delegate: &T::AccountId,
 	) -> DispatchResult {
 		ensure!(
@@ -326,7 +1714,8 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::InvalidDelegate
 		);
 
-		let validity = Self::delegate_of((identity, delegate_type, delegate));
+		let validity = Self::delegate_of((identity, delegate_type, delegate))
+			.map(|validity| Self::capped_validity(identity, validity));
 		match validity > Some(<frame_system::Pallet<T>>::block_number()) {
 			true => Ok(()),
 			false => Err(Error::<T>::InvalidDelegate.into()),
@@ -335,14 +1724,27 @@ impl<T: Config> Pallet<T> {
 
 	/// Creates a new delegate for an identity.
 	fn create_delegate(
-		who: &T::AccountId,
+		caller: &Caller<T::AccountId>,
 		identity: &T::AccountId,
 		delegate: &T::AccountId,
-		delegate_type: &[u8],
+		delegate_type: &DelegateType<T>,
 		valid_for: Option<T::BlockNumber>,
 	) -> DispatchResult {
-		Self::is_owner(&identity, who)?;
-		ensure!(who != delegate, Error::<T>::InvalidDelegate);
+		let who = Self::authorize(caller, identity, Some(Operation::AddDelegate))?;
+		ensure!(who != *delegate, Error::<T>::InvalidDelegate);
+		// Typed delegate types have to name a signing method, free-form ones a permission.
+		if delegate_type.contains(&b'/') {
+			ensure!(
+				Self::delegate_method_kind(delegate_type).is_some(),
+				Error::<T>::InvalidVerificationMethod
+			);
+		} else {
+			ensure!(
+				Self::delegate_method_kind(delegate_type).is_some() ||
+					T::PermissionDelegateTypes::get().contains(&&delegate_type[..]),
+				Error::<T>::UnknownDelegateType
+			);
+		}
 		ensure!(
 			!Self::valid_listed_delegate(identity, delegate_type, delegate).is_ok(),
 			Error::<T>::ValidDelegateExists
@@ -354,46 +1756,212 @@ impl<T: Config> Pallet<T> {
 			None => u32::MAX.into(),
 		};
 
+		// An expired delegate being renewed already holds its deposit.
+		if !<DelegateOf<T>>::contains_key((identity, delegate_type, delegate)) {
+			Self::reserve_deposit(identity, Self::storage_deposit(delegate_type.len()))?;
+		}
+
 		<DelegateOf<T>>::insert((&identity, delegate_type, delegate), &validity);
 
 		Ok(())
 	}
 
-	/// Checks if a signature is valid. Used to validate off-chain transactions.
+	/// Revokes a valid delegate of an identity by setting its expiration to the current block.
+	/// The caller is expected to be authorized already.
+	fn expire_delegate(
+		identity: &T::AccountId,
+		delegate_type: &[u8],
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		let bounded_type: DelegateType<T> =
+			delegate_type.to_vec().try_into().map_err(|_| Error::<T>::InvalidDelegate)?;
+		Self::valid_listed_delegate(identity, &bounded_type, delegate)?;
+
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+
+		// Update only the validity period to revoke the delegate.
+		<DelegateOf<T>>::mutate((identity, &bounded_type, delegate), |b| {
+			*b = Some(now_block_number);
+		});
+
+		Ok(())
+	}
+
+	/// Checks that `signer` signed `msg`, natively or as the account of an Ethereum address.
+	/// EIP-712 signatures sign the typed data whose `hashStruct` is `struct_hash` instead.
+	/// Returns the address of an Ethereum signature.
 	fn check_signature(
-		signature: &T::Signature,
+		signature: &SignatureOf<T>,
 		msg: &[u8],
+		struct_hash: &[u8; 32],
 		signer: &T::AccountId,
+	) -> Result<Option<H160>, DispatchError> {
+		let address = match signature {
+			DidSignature::Native(signature) => {
+				ensure!(signature.verify(msg, signer), Error::<T>::BadSignature);
+				return Ok(None)
+			},
+			DidSignature::Eip191(signature) =>
+				Self::ethereum_signer(signature, &Self::eip191_hash(msg)),
+			DidSignature::Eip712(signature) =>
+				Self::ethereum_signer(signature, &Self::eip712_hash(struct_hash)),
+		};
+		match address {
+			Some(address) if Self::ethereum_account(&address) == *signer => Ok(Some(address)),
+			_ => Err(Error::<T>::BadSignature.into()),
+		}
+	}
+
+	/// Records the Ethereum address a signer signed with, if any, so that its account is
+	/// resolved as `did:ethr:<address>`.
+	fn note_ethereum_address(signer: &T::AccountId, address: Option<H160>) {
+		if let Some(address) = address {
+			if !<EthereumAddressOf<T>>::contains_key(signer) {
+				<EthereumAddressOf<T>>::insert(signer, address);
+			}
+		}
+	}
+
+	/// Returns the account of the identity keyed by an Ethereum address, resolved as
+	/// `did:ethr:<address>`. It is the BLAKE2-256 hash of `did:ethr:` followed by the 20 bytes
+	/// of the address, decoded as an account, so no native key controls it and only signatures
+	/// recovering to the address act for it.
+	pub fn ethereum_account(address: &H160) -> T::AccountId {
+		let mut data = ETHEREUM_ACCOUNT_PREFIX.to_vec();
+		data.extend_from_slice(address.as_bytes());
+		let hash = blake2_256(&data);
+		T::AccountId::decode(&mut TrailingZeroInput::new(&hash))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Recovers the Ethereum address which signed a message hash.
+	fn ethereum_signer(signature: &ecdsa::Signature, hash: &[u8; 32]) -> Option<H160> {
+		let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, hash).ok()?;
+		Some(H160::from_slice(&keccak_256(&public)[12..]))
+	}
+
+	/// Returns the hash of the EIP-191 personal message of a payload.
+	pub fn eip191_hash(payload: &[u8]) -> [u8; 32] {
+		// The length of the payload is appended in decimal, as the index of a fragment.
+		let mut message = Self::fragment(b"\x19Ethereum Signed Message:\n", payload.len() as u32);
+		message.extend_from_slice(payload);
+		keccak_256(&message)
+	}
+
+	/// Returns the hash of the EIP-712 typed data whose `hashStruct` is `struct_hash`,
+	/// in the domain of this chain.
+	pub fn eip712_hash(struct_hash: &[u8; 32]) -> [u8; 32] {
+		let mut salt = [0u8; 32];
+		let genesis_hash = <frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero());
+		let genesis_hash = genesis_hash.as_ref();
+		let len = genesis_hash.len().min(32);
+		salt[..len].copy_from_slice(&genesis_hash[..len]);
+
+		let domain_separator = keccak_256(
+			&[
+				keccak_256(EIP712_DOMAIN_TYPE),
+				keccak_256(EIP712_DOMAIN_NAME),
+				keccak_256(EIP712_DOMAIN_VERSION),
+				salt,
+			]
+			.concat(),
+		);
+		keccak_256(&[&b"\x19\x01"[..], &domain_separator, &struct_hash[..]].concat())
+	}
+
+	/// Returns the EIP-712 `hashStruct` of typed data from its type and encoded members.
+	fn eip712_struct(type_: &[u8], members: &[[u8; 32]]) -> [u8; 32] {
+		let mut encoded = keccak_256(type_).to_vec();
+		members.iter().for_each(|member| encoded.extend_from_slice(member));
+		keccak_256(&encoded)
+	}
+
+	/// Encodes a `bytes` or `string` member of EIP-712 typed data.
+	fn eip712_bytes(value: &[u8]) -> [u8; 32] {
+		keccak_256(value)
+	}
+
+	/// Encodes an unsigned integer or `bool` member of EIP-712 typed data, big-endian.
+	fn eip712_uint(value: u64) -> [u8; 32] {
+		let mut word = [0u8; 32];
+		word[24..].copy_from_slice(&value.to_be_bytes());
+		word
+	}
+
+	/// Validates that `who` owns an identity or is a valid delegate of a type
+	/// permitted to perform `operation` by `DelegatePermissions`.
+	pub fn ensure_authorized(
+		identity: &T::AccountId,
+		who: &T::AccountId,
+		operation: Operation,
 	) -> DispatchResult {
-		if signature.verify(msg, signer) {
-			Ok(())
-		} else {
-			Err(Error::<T>::BadSignature.into())
+		ensure!(!Self::is_deactivated(identity), Error::<T>::Deactivated);
+		if Self::is_owner(identity, who).is_ok() {
+			return Ok(())
 		}
+		let permitted = T::DelegatePermissions::delegate_types(operation)
+			.iter()
+			.any(|delegate_type| Self::valid_delegate(identity, delegate_type, who).is_ok());
+		ensure!(permitted, Error::<T>::NotOwner);
+		Ok(())
 	}
 
-	/// Checks if a signature is valid. Used to validate off-chain transactions.
-	#[allow(dead_code)]
-	fn valid_signer(
+	/// Checks that `signer` may perform `operation` on an identity and signed `msg`, or the
+	/// typed data of `struct_hash`. Used to validate off-chain transactions.
+	fn authorized_signer(
+		identity: &T::AccountId,
+		signature: &SignatureOf<T>,
+		msg: &[u8],
+		struct_hash: &[u8; 32],
+		signer: &T::AccountId,
+		operation: Operation,
+	) -> DispatchResult {
+		// Owner or permitted delegate signer
+		Self::ensure_authorized(identity, signer, operation)?;
+		let address = Self::check_signature(signature, msg, struct_hash, signer)?;
+		Self::note_ethereum_address(signer, address);
+		Ok(())
+	}
+
+	/// Checks that `msg` was signed on behalf of an identity, e.g. a credential it issued.
+	/// The signer has to own the identity or be a valid delegate of one of the
+	/// `ASSERTING_DELEGATE_TYPES`, each looked up by key.
+	pub fn valid_signer(
 		identity: &T::AccountId,
-		signature: &T::Signature,
+		signature: &SignatureOf<T>,
 		msg: &[u8],
 		signer: &T::AccountId,
 	) -> DispatchResult {
-		// Owner or delegate signer
-		Self::valid_delegate(&identity, b"x25519VerificationKey2018", &signer)?;
-		Self::check_signature(&signature, &msg, &signer)
+		ensure!(!Self::is_deactivated(identity), Error::<T>::Deactivated);
+		let asserts = Self::is_owner(identity, signer).is_ok() ||
+			ASSERTING_DELEGATE_TYPES.iter().any(|delegate_type| {
+				DelegateType::<T>::try_from(delegate_type.to_vec()).map_or(false, |delegate_type| {
+					Self::valid_listed_delegate(identity, &delegate_type, signer).is_ok()
+				})
+			});
+		ensure!(asserts, Error::<T>::InvalidDelegate);
+		let struct_hash = Self::eip712_struct(EIP712_MESSAGE_TYPE, &[Self::eip712_bytes(msg)]);
+		Self::check_signature(signature, msg, &struct_hash, signer).map(|_| ())
 	}
 
 	/// Adds a new attribute to an identity and collects the storage fee.
+	/// The caller is expected to be authorized already and to record the change.
 	fn create_attribute(
-		who: &T::AccountId,
 		identity: &T::AccountId,
 		name: &[u8],
 		value: &[u8],
 		valid_for: Option<T::BlockNumber>,
 	) -> DispatchResult {
-		Self::is_owner(&identity, &who)?;
+		let bounded_name: AttributeName<T> =
+			name.to_vec().try_into().map_err(|_| Error::<T>::AttributeCreationFailed)?;
+		let bounded_value: AttributeValue<T> =
+			value.to_vec().try_into().map_err(|_| Error::<T>::AttributeCreationFailed)?;
+
+		// Public keys are only listed in the DID document if well-formed.
+		if name.starts_with(PUBLIC_KEY_PREFIX) {
+			let (kind, _) = Self::public_key_attribute(name).ok_or(Error::<T>::InvalidPublicKey)?;
+			ensure!(kind.key_type.valid_key(value), Error::<T>::InvalidPublicKey);
+		}
 
 		if Self::attribute_and_id(identity, name).is_some() {
 			Err(Error::<T>::AttributeCreationFailed.into())
@@ -405,11 +1973,11 @@ impl<T: Config> Pallet<T> {
 				None => u32::MAX.into(),
 			};
 
-			let mut nonce = Self::nonce_of(&identity, name.to_vec());
+			let mut nonce = Self::nonce_of(&identity, &bounded_name);
 			let id = (&identity, name, nonce).using_encoded(blake2_256);
 			let new_attribute = Attribute {
-				name: (&name).to_vec(),
-				value: (&value).to_vec(),
+				name: bounded_name.clone(),
+				value: bounded_value,
 				validity,
 				creation: now_timestamp,
 				nonce,
@@ -417,17 +1985,61 @@ impl<T: Config> Pallet<T> {
 
 			// Prevent panic overflow
 			nonce = nonce.checked_add(1).ok_or(Error::<T>::Overflow)?;
+			Self::reserve_deposit(identity, Self::storage_deposit(name.len() + value.len()))?;
 			<AttributeOf<T>>::insert(&identity, &id, new_attribute);
-			<AttributeNonce<T>>::mutate(&identity, name.to_vec(), |n| *n = nonce);
-			<UpdatedBy<T>>::insert(identity, (who, now_block_number, now_timestamp));
+			<AttributeNonce<T>>::mutate(&identity, &bounded_name, |n| *n = nonce);
 
 			Ok(())
 		}
 	}
 
+	/// Computes the storage deposit of an item holding `bytes` bytes of user data.
+	fn storage_deposit(bytes: usize) -> BalanceOf<T> {
+		let bytes: BalanceOf<T> = (bytes as u32).into();
+		T::DepositPerItem::get().saturating_add(T::DepositPerByte::get().saturating_mul(bytes))
+	}
+
+	/// Reserves a storage deposit for an identity from its owner.
+	fn reserve_deposit(identity: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let owner = Self::deposit_account(&Self::identity_owner(identity));
+		T::Currency::reserve(&owner, amount).map_err(|_| Error::<T>::InsufficientDeposit)?;
+		<DepositOf<T>>::mutate(identity, |d| *d = d.saturating_add(amount));
+		Ok(())
+	}
+
+	/// Returns a storage deposit of an identity to its owner.
+	fn release_deposit(identity: &T::AccountId, amount: BalanceOf<T>) {
+		let owner = Self::deposit_account(&Self::identity_owner(identity));
+		let amount = amount.min(Self::deposit_of(identity));
+		T::Currency::unreserve(&owner, amount);
+		<DepositOf<T>>::mutate(identity, |d| *d = d.saturating_sub(amount));
+	}
+
+	/// Moves the whole storage deposit of an identity from its previous owner to the new one.
+	fn transfer_deposit(
+		identity: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+	) -> DispatchResult {
+		let amount = Self::deposit_of(identity);
+		let (from, to) = (Self::deposit_account(from), Self::deposit_account(to));
+		if amount.is_zero() || from == to {
+			return Ok(())
+		}
+		T::Currency::reserve(&to, amount).map_err(|_| Error::<T>::InsufficientDeposit)?;
+		T::Currency::unreserve(&from, amount);
+		Ok(())
+	}
+
+	/// Returns the account paying the deposits of an owner,
+	/// the linked native account of an Ethereum account.
+	fn deposit_account(owner: &T::AccountId) -> T::AccountId {
+		Self::ethereum_link(owner).map_or_else(|| owner.clone(), |(_, native)| native)
+	}
+
 	/// Update the attribute validity to make it expire and invalid.
-	fn reset_attribute(who: &T::AccountId, identity: &T::AccountId, name: &[u8]) -> DispatchResult {
-		Self::is_owner(&identity, &who)?;
+	/// The caller is expected to be authorized already and to record the change.
+	fn reset_attribute(identity: &T::AccountId, name: &[u8]) -> DispatchResult {
 		// If the attribute contains_key, the lastest valid block is set to the current block.
 		let result = Self::attribute_and_id(&identity, &name);
 		match result {
@@ -438,10 +2050,18 @@ impl<T: Config> Pallet<T> {
 			None => return Err(Error::<T>::AttributeResetFailed.into()),
 		};
 
-		// Keep track of the updates.
-		<UpdatedBy<T>>::insert(
+		Ok(())
+	}
+
+	/// Removes an attribute from an identity and releases its deposit.
+	/// The caller is expected to be authorized already and to record the change.
+	fn remove_attribute(identity: &T::AccountId, name: &[u8]) -> DispatchResult {
+		let (attribute, id) =
+			Self::attribute_and_id(identity, name).ok_or(Error::<T>::AttributeRemovalFailed)?;
+		<AttributeOf<T>>::remove(identity, &id);
+		Self::release_deposit(
 			identity,
-			(who, <frame_system::Pallet<T>>::block_number(), T::Time::now()),
+			Self::storage_deposit(attribute.name.len() + attribute.value.len()),
 		);
 
 		Ok(())
@@ -450,7 +2070,6 @@ impl<T: Config> Pallet<T> {
 	/// Validates if an attribute belongs to an identity and it has not expired.
 	#[allow(dead_code)]
 	fn valid_attribute(identity: &T::AccountId, name: &[u8], value: &[u8]) -> DispatchResult {
-		ensure!(name.len() <= 64, Error::<T>::InvalidAttribute);
 		let result = Self::attribute_and_id(&identity, &name);
 
 		let (attr, _) = match result {
@@ -458,8 +2077,9 @@ impl<T: Config> Pallet<T> {
 			None => return Err(Error::<T>::InvalidAttribute.into()),
 		};
 
-		if (attr.validity > (<frame_system::Pallet<T>>::block_number()))
-			&& (attr.value == value.to_vec())
+		let validity = Self::capped_validity(identity, attr.validity);
+		if (validity > (<frame_system::Pallet<T>>::block_number())) &&
+			(attr.value.as_slice() == value)
 		{
 			Ok(())
 		} else {
@@ -472,8 +2092,10 @@ impl<T: Config> Pallet<T> {
 	fn attribute_and_id(
 		identity: &T::AccountId,
 		name: &[u8],
-	) -> Option<AttributedId<T::BlockNumber, <<T as Config>::Time as Time>::Moment>> {
-		let nonce = Self::nonce_of(&identity, name.to_vec());
+	) -> Option<AttributedId<T::BlockNumber, MomentOf<T>, AttributeName<T>, AttributeValue<T>>> {
+		// A name longer than the limit can never have been stored.
+		let bounded_name: AttributeName<T> = name.to_vec().try_into().ok()?;
+		let nonce = Self::nonce_of(&identity, &bounded_name);
 
 		// Used for first time attribute creation
 		let lookup_nonce = match nonce {
@@ -492,39 +2114,670 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Returns the payload to be signed for an off-chain transaction.
+	/// It covers every field of the transaction but the signature itself, is separated from
+	/// other kinds of transactions by `domain` and bound to this chain by its genesis hash.
+	fn signing_payload(domain: &[u8], fields: impl Encode) -> Vec<u8> {
+		let mut encoded = domain.to_vec();
+		encoded.extend(fields.encode());
+		encoded.extend(<frame_system::Pallet<T>>::block_hash(T::BlockNumber::zero()).encode());
+		encoded
+	}
+
+	/// Returns the payload an Ethereum account signs to link itself to a native account.
+	pub fn link_account_payload(address: &H160, who: &T::AccountId) -> Vec<u8> {
+		Self::signing_payload(DOMAIN_LINK_ACCOUNT, (address, who))
+	}
+
+	/// Returns the payload to be signed for an off-chain attribute transaction.
+	pub fn attribute_transaction_payload(
+		transaction: &AttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_SET_ATTRIBUTE,
+			(
+				&transaction.name,
+				&transaction.value,
+				transaction.validity,
+				&transaction.identity,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the payload to be signed for an off-chain ownership transfer.
+	pub fn change_owner_payload(
+		transaction: &ChangeOwnerTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_CHANGE_OWNER,
+			(
+				&transaction.signer,
+				&transaction.identity,
+				&transaction.new_owner,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the payload to be signed for an off-chain delegate creation.
+	pub fn add_delegate_payload(
+		transaction: &AddDelegateTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_ADD_DELEGATE,
+			(
+				&transaction.signer,
+				&transaction.identity,
+				&transaction.delegate,
+				&transaction.delegate_type,
+				transaction.valid_for,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the payload to be signed for an off-chain delegate revocation.
+	pub fn revoke_delegate_payload(
+		transaction: &RevokeDelegateTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_REVOKE_DELEGATE,
+			(
+				&transaction.signer,
+				&transaction.identity,
+				&transaction.delegate_type,
+				&transaction.delegate,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the payload to be signed for an off-chain attribute revocation.
+	pub fn revoke_attribute_payload(
+		transaction: &RevokeAttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_REVOKE_ATTRIBUTE,
+			(
+				&transaction.signer,
+				&transaction.identity,
+				&transaction.name,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the payload to be signed for an off-chain attribute deletion.
+	pub fn delete_attribute_payload(
+		transaction: &DeleteAttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> Vec<u8> {
+		Self::signing_payload(
+			DOMAIN_DELETE_ATTRIBUTE,
+			(
+				&transaction.signer,
+				&transaction.identity,
+				&transaction.name,
+				transaction.nonce,
+				transaction.expiry,
+			),
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain attribute transaction.
+	pub fn attribute_transaction_struct_hash(
+		transaction: &AttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_SET_ATTRIBUTE_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.name),
+				Self::eip712_bytes(&transaction.value),
+				Self::eip712_uint(transaction.validity.into()),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain ownership transfer.
+	pub fn change_owner_struct_hash(
+		transaction: &ChangeOwnerTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_CHANGE_OWNER_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.signer.encode()),
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.new_owner.encode()),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain delegate creation.
+	/// A delegate valid forever does not expire and is valid for 0 blocks.
+	pub fn add_delegate_struct_hash(
+		transaction: &AddDelegateTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_ADD_DELEGATE_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.signer.encode()),
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.delegate.encode()),
+				Self::eip712_bytes(&transaction.delegate_type),
+				Self::eip712_uint(transaction.valid_for.is_some().into()),
+				Self::eip712_uint(transaction.valid_for.unwrap_or_default().into()),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain delegate revocation.
+	pub fn revoke_delegate_struct_hash(
+		transaction: &RevokeDelegateTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_REVOKE_DELEGATE_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.signer.encode()),
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.delegate_type),
+				Self::eip712_bytes(&transaction.delegate.encode()),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain attribute revocation.
+	pub fn revoke_attribute_struct_hash(
+		transaction: &RevokeAttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_REVOKE_ATTRIBUTE_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.signer.encode()),
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.name),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Returns the EIP-712 `hashStruct` of an off-chain attribute deletion.
+	pub fn delete_attribute_struct_hash(
+		transaction: &DeleteAttributeTransaction<SignatureOf<T>, T::AccountId>,
+	) -> [u8; 32] {
+		Self::eip712_struct(
+			EIP712_DELETE_ATTRIBUTE_TYPE,
+			&[
+				Self::eip712_bytes(&transaction.signer.encode()),
+				Self::eip712_bytes(&transaction.identity.encode()),
+				Self::eip712_bytes(&transaction.name),
+				Self::eip712_uint(transaction.nonce),
+				Self::eip712_uint(transaction.expiry.into()),
+			],
+		)
+	}
+
+	/// Checks the signature and expiry of an off-chain transaction and consumes its nonce.
+	/// Whether the signer may perform the transaction is checked by the caller.
+	fn check_transaction(
+		identity: &T::AccountId,
+		signer: &T::AccountId,
+		signature: &SignatureOf<T>,
+		payload: &[u8],
+		struct_hash: &[u8; 32],
+		nonce: u64,
+		expiry: u32,
+	) -> DispatchResult {
+		let address = Self::check_signature(signature, payload, struct_hash, signer)?;
+		Self::note_ethereum_address(signer, address);
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		ensure!(now_block_number <= expiry.into(), Error::<T>::TransactionExpired);
+		Self::use_signing_nonce(identity, nonce)
+	}
+
+	/// Consumes the signing nonce of an identity if it matches the expected one.
+	fn use_signing_nonce(identity: &T::AccountId, nonce: u64) -> DispatchResult {
+		let expected = Self::signing_nonce_of(identity);
+		ensure!(nonce == expected, Error::<T>::TransactionReplayed);
+		let next = expected.checked_add(1).ok_or(Error::<T>::Overflow)?;
+		<SigningNonce<T>>::insert(identity, next);
+		Ok(())
+	}
+
 	/// Creates a new attribute from a off-chain transaction.
 	#[allow(dead_code)]
 	fn signed_attribute(
 		who: T::AccountId,
 		encoded: &[u8],
-		transaction: &AttributeTransaction<T::Signature, T::AccountId>,
+		transaction: &AttributeTransaction<SignatureOf<T>, T::AccountId>,
 	) -> DispatchResult {
-		// Verify that the Data was signed by the owner or a not expired signer delegate.
-		Self::valid_signer(
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		let validity = now_block_number + transaction.validity.into();
+		// If validity was set to 0 in the transaction,
+		// it will set the attribute latest valid block to the actual block.
+		let operation = match validity > now_block_number {
+			true => Operation::AddAttribute,
+			false => Operation::RevokeAttribute,
+		};
+
+		// Verify that the Data was signed by the owner or a not expired permitted delegate.
+		Self::authorized_signer(
 			&transaction.identity,
 			&transaction.signature,
 			&encoded,
+			&Self::attribute_transaction_struct_hash(transaction),
 			&transaction.signer,
+			operation,
 		)?;
-		Self::is_owner(&transaction.identity, &transaction.signer)?;
-		ensure!(transaction.name.len() <= 64, Error::<T>::BadTransaction);
+		ensure!(
+			transaction.name.len() <= T::MaxNameLength::get() as usize,
+			Error::<T>::BadTransaction
+		);
 
-		let now_block_number = <frame_system::Pallet<T>>::block_number();
-		let validity = now_block_number + transaction.validity.into();
+		ensure!(now_block_number <= transaction.expiry.into(), Error::<T>::TransactionExpired);
+		Self::use_signing_nonce(&transaction.identity, transaction.nonce)?;
 
-		// If validity was set to 0 in the transaction,
-		// it will set the attribute latest valid block to the actual block.
-		if validity > now_block_number {
+		if operation == Operation::AddAttribute {
 			Self::create_attribute(
-				&who,
 				&transaction.identity,
 				&transaction.name,
 				&transaction.value,
 				Some(transaction.validity.into()),
 			)?;
 		} else {
-			Self::reset_attribute(&who, &transaction.identity, &transaction.name)?;
+			Self::reset_attribute(&transaction.identity, &transaction.name)?;
 		}
+
+		// Keep track of the updates.
+		Self::record_change(&transaction.identity, &who);
+
 		Ok(())
 	}
+
+	/// Resolves an identity into its W3C DID document.
+	/// Only delegates and attributes still valid at this block are folded into the document,
+	/// following the ERC-1056 conventions:
+	/// delegates of type `veriKey`/`sigAuth` or of a verification method kind such as
+	/// `Ed25519/veriKey` become verification methods,
+	/// attributes named `did/pub/<algorithm>/<purpose>/<encoding>` become public keys and
+	/// attributes named `did/svc/<type>` become service endpoints.
+	/// Accounts of Ethereum addresses recorded in `EthereumAddressOf` are listed by address,
+	/// as `EcdsaSecp256k1RecoveryMethod2020` methods.
+	pub fn resolve(identity: &T::AccountId) -> DidDocument<T::AccountId, T::BlockNumber> {
+		Self::resolve_keyed(identity, Self::ethereum_address_of(identity))
+	}
+
+	/// Resolves the identity keyed by an Ethereum address, as `resolve` does once the address
+	/// is recorded.
+	pub fn resolve_ethereum(address: &H160) -> DidDocument<T::AccountId, T::BlockNumber> {
+		Self::resolve_keyed(&Self::ethereum_account(address), Some(*address))
+	}
+
+	/// Resolves an identity keyed by `ethereum_address`, if any.
+	fn resolve_keyed(
+		identity: &T::AccountId,
+		ethereum_address: Option<H160>,
+	) -> DidDocument<T::AccountId, T::BlockNumber> {
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		let controller = Self::identity_owner(identity);
+		let controller_key = b"controller".to_vec();
+
+		let mut document = DidDocument {
+			id: identity.clone(),
+			controller: controller.clone(),
+			verification_method: Vec::new(),
+			authentication: Vec::new(),
+			assertion_method: Vec::new(),
+			key_agreement: Vec::new(),
+			service: Vec::new(),
+			updated: Self::updated_by(identity).map(|(_, block_number, _)| block_number),
+			version_id: Self::document_version(identity).map_or(0, |version| version.version_id),
+			deactivated: Self::is_deactivated(identity),
+			handle: None,
+			ethereum_address,
+		};
+
+		// A deactivated identity can no longer be authenticated nor reached.
+		if document.deactivated {
+			return document
+		}
+		document.handle = Self::identity_handle(identity);
+
+		// Accounts of Ethereum addresses have no key of their own, signatures recover to them.
+		let account_method = |account: T::AccountId, method_type: &[u8]| {
+			let address = match ethereum_address {
+				Some(address) if account == *identity => Some(address),
+				_ => Self::ethereum_address_of(&account),
+			};
+			match address {
+				Some(address) =>
+					(ETHEREUM_METHOD_TYPE.to_vec(), PublicKey::EthereumAddress(address)),
+				None => (method_type.to_vec(), PublicKey::BlockchainAccount(account)),
+			}
+		};
+
+		// The owner always controls the document.
+		let (method_type, public_key) = account_method(controller, ACCOUNT_METHOD_TYPE);
+		document.verification_method.push(VerificationMethod {
+			id: controller_key.clone(),
+			method_type,
+			controller: identity.clone(),
+			public_key,
+		});
+		document.authentication.push(controller_key.clone());
+		document.assertion_method.push(controller_key);
+
+		let mut key_count = 0u32;
+		for ((delegate_type, delegate), validity) in
+			<DelegateOf<T>>::iter_prefix((identity.clone(),))
+		{
+			let kind = match Self::delegate_method_kind(&delegate_type) {
+				Some(kind) if validity > now_block_number => kind,
+				_ => continue,
+			};
+
+			key_count += 1;
+			let key = Self::fragment(b"delegate-", key_count);
+			let (method_type, public_key) = account_method(delegate, kind.key_type.method_type());
+			document.verification_method.push(VerificationMethod {
+				id: key.clone(),
+				method_type,
+				controller: identity.clone(),
+				public_key,
+			});
+			Self::relate(&mut document, key, kind.purpose);
+		}
+
+		let mut service_count = 0u32;
+		for attribute in <AttributeOf<T>>::iter_prefix_values(identity) {
+			if attribute.validity <= now_block_number {
+				continue;
+			}
+
+			if let Some((kind, public_key)) = Self::public_key_attribute(&attribute.name) {
+				// Keys set before their material was validated may be malformed.
+				if !kind.key_type.valid_key(&attribute.value) {
+					continue;
+				}
+				key_count += 1;
+				let key = Self::fragment(b"delegate-", key_count);
+				document.verification_method.push(VerificationMethod {
+					id: key.clone(),
+					method_type: kind.key_type.method_type().to_vec(),
+					controller: identity.clone(),
+					public_key: public_key(attribute.value.to_vec()),
+				});
+				Self::relate(&mut document, key, kind.purpose);
+				continue;
+			}
+
+			let parts: Vec<&[u8]> = attribute.name.split(|c| *c == b'/').collect();
+			match parts.as_slice() {
+				[did, kind, service_type] if *did == b"did" && *kind == b"svc" => {
+					service_count += 1;
+					document.service.push(Service {
+						id: Self::fragment(b"service-", service_count),
+						service_type: service_type.to_vec(),
+						endpoint: attribute.value.to_vec(),
+					});
+				},
+				_ => continue,
+			}
+		}
+
+		document
+	}
+
+	/// Removes the delegates and attributes expired for longer than `PruningGracePeriod`
+	/// and releases their deposits. The sweep resumes from `PruningPosition`, stops once
+	/// `remaining_weight` is used up and covers each map at most once per call.
+	fn prune_expired(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let mut used = T::DbWeight::get().reads_writes(1, 1);
+		if remaining_weight < used {
+			return 0
+		}
+		let cutoff = now.saturating_sub(T::PruningGracePeriod::get());
+
+		let mut cursor = Self::pruning_position();
+		for _ in 0..2 {
+			// `Ok` holds the position to resume from, `Err` the start of the next map.
+			let resume = match cursor {
+				PruningCursor::Delegates(start) =>
+					Self::prune_delegates(start, cutoff, remaining_weight, &mut used)
+						.ok_or(PruningCursor::Attributes(None)),
+				PruningCursor::Attributes(start) =>
+					Self::prune_attributes(start, cutoff, remaining_weight, &mut used)
+						.ok_or(PruningCursor::Delegates(None)),
+			};
+			match resume {
+				Ok(position) => {
+					cursor = position;
+					break
+				},
+				Err(next) => cursor = next,
+			}
+		}
+
+		<PruningPosition<T>>::put(cursor);
+		used
+	}
+
+	/// Sweeps the delegates from `start` until the end of the map.
+	/// Returns the position to resume from if the weight is used up first.
+	fn prune_delegates(
+		start: Option<RawKey>,
+		cutoff: T::BlockNumber,
+		remaining_weight: Weight,
+		used: &mut Weight,
+	) -> Option<PruningCursor<RawKey>> {
+		let item_weight = T::WeightInfo::prune_delegate();
+		let mut iter = match start {
+			Some(key) => <DelegateOf<T>>::iter_from(key.into_inner()),
+			None => <DelegateOf<T>>::iter(),
+		};
+
+		loop {
+			if used.saturating_add(item_weight) > remaining_weight {
+				// A key too long to be persisted makes the sweep start over.
+				let last = iter.last_raw_key().to_vec().try_into().ok();
+				return Some(PruningCursor::Delegates(last))
+			}
+			let ((identity, delegate_type, delegate), validity) = iter.next()?;
+			*used = used.saturating_add(item_weight);
+			if Self::capped_validity(&identity, validity) > cutoff {
+				continue
+			}
+
+			<DelegateOf<T>>::remove((&identity, &delegate_type, &delegate));
+			Self::release_deposit(&identity, Self::storage_deposit(delegate_type.len()));
+			Self::deposit_event(Event::DelegatePruned(
+				identity,
+				delegate_type.into_inner(),
+				delegate,
+			));
+		}
+	}
+
+	/// Sweeps the attributes from `start` until the end of the map.
+	/// Returns the position to resume from if the weight is used up first.
+	fn prune_attributes(
+		start: Option<RawKey>,
+		cutoff: T::BlockNumber,
+		remaining_weight: Weight,
+		used: &mut Weight,
+	) -> Option<PruningCursor<RawKey>> {
+		let item_weight = T::WeightInfo::prune_attribute();
+		let mut iter = match start {
+			Some(key) => <AttributeOf<T>>::iter_from(key.into_inner()),
+			None => <AttributeOf<T>>::iter(),
+		};
+
+		loop {
+			if used.saturating_add(item_weight) > remaining_weight {
+				// A key too long to be persisted makes the sweep start over.
+				let last = iter.last_raw_key().to_vec().try_into().ok();
+				return Some(PruningCursor::Attributes(last))
+			}
+			let (identity, id, attribute) = iter.next()?;
+			*used = used.saturating_add(item_weight);
+			if Self::capped_validity(&identity, attribute.validity) > cutoff {
+				continue
+			}
+
+			<AttributeOf<T>>::remove(&identity, &id);
+			Self::release_deposit(
+				&identity,
+				Self::storage_deposit(attribute.name.len() + attribute.value.len()),
+			);
+			Self::deposit_event(Event::AttributePruned(identity, attribute.name.into_inner()));
+		}
+	}
+
+	/// Lists the attributes of an identity with their validity status at this block.
+	/// Expired and revoked attributes are only listed if `include_expired` is set.
+	pub fn attributes(
+		identity: &T::AccountId,
+		include_expired: bool,
+	) -> Vec<AttributeRecord<T::BlockNumber, MomentOf<T>>> {
+		// Nothing is valid once the identity is deactivated.
+		let active = !Self::is_deactivated(identity);
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		<AttributeOf<T>>::iter_prefix_values(identity)
+			.filter_map(|attribute| {
+				let valid = active && attribute.validity > now_block_number;
+				if !valid && !include_expired {
+					return None
+				}
+				let attribute = Attribute {
+					name: attribute.name.into_inner(),
+					value: attribute.value.into_inner(),
+					validity: attribute.validity,
+					creation: attribute.creation,
+					nonce: attribute.nonce,
+				};
+				Some(AttributeRecord { attribute, valid })
+			})
+			.collect()
+	}
+
+	/// Lists the delegates of an identity, optionally of a single type,
+	/// with their validity status at this block.
+	pub fn delegates(
+		identity: &T::AccountId,
+		delegate_type: Option<Vec<u8>>,
+	) -> Vec<DelegateRecord<T::AccountId, T::BlockNumber>> {
+		// Nothing is valid once the identity is deactivated.
+		let active = !Self::is_deactivated(identity);
+		let now_block_number = <frame_system::Pallet<T>>::block_number();
+		let record =
+			|delegate_type: &DelegateType<T>, delegate: T::AccountId, validity: T::BlockNumber| {
+				DelegateRecord {
+					delegate_type: delegate_type.to_vec(),
+					delegate,
+					validity,
+					valid: active && validity > now_block_number,
+				}
+			};
+
+		match delegate_type {
+			Some(delegate_type) => {
+				// A type longer than the limit can never have been stored.
+				let delegate_type: DelegateType<T> = match delegate_type.try_into() {
+					Ok(delegate_type) => delegate_type,
+					Err(_) => return Vec::new(),
+				};
+				<DelegateOf<T>>::iter_prefix((identity.clone(), delegate_type.clone()))
+					.map(|(delegate, validity)| record(&delegate_type, delegate, validity))
+					.collect()
+			},
+			None => <DelegateOf<T>>::iter_prefix((identity.clone(),))
+				.map(|((delegate_type, delegate), validity)| {
+					record(&delegate_type, delegate, validity)
+				})
+				.collect(),
+		}
+	}
+
+	/// Returns the verification method kind of a delegate type listed in the DID document.
+	/// Untyped `veriKey` and `sigAuth` delegates are Sr25519 account keys.
+	pub fn delegate_method_kind(delegate_type: &[u8]) -> Option<VerificationMethodKind> {
+		match delegate_type {
+			DELEGATE_VERIFICATION_KEY => Some(VerificationMethodKind {
+				key_type: KeyType::Sr25519,
+				purpose: KeyPurpose::AssertionMethod,
+			}),
+			DELEGATE_SIGNATURE_AUTH => Some(VerificationMethodKind {
+				key_type: KeyType::Sr25519,
+				purpose: KeyPurpose::Authentication,
+			}),
+			// Delegates are accounts, which cannot agree keys.
+			_ => VerificationMethodKind::from_name(delegate_type)
+				.filter(|kind| kind.purpose != KeyPurpose::KeyAgreement),
+		}
+	}
+
+	/// Parses the name of a `did/pub/<algorithm>/<purpose>/<encoding>` attribute into the kind
+	/// of its key and the constructor of its public key for the encoding.
+	fn public_key_attribute(
+		name: &[u8],
+	) -> Option<(VerificationMethodKind, fn(Vec<u8>) -> PublicKey<T::AccountId>)> {
+		let name = name.strip_prefix(PUBLIC_KEY_PREFIX)?;
+		let separator = name.iter().rposition(|c| *c == b'/')?;
+		let kind = VerificationMethodKind::from_name(&name[..separator])?;
+		let public_key: fn(Vec<u8>) -> PublicKey<T::AccountId> = match &name[separator + 1..] {
+			b"hex" => PublicKey::Hex,
+			b"base64" => PublicKey::Base64,
+			b"base58" => PublicKey::Base58,
+			_ => return None,
+		};
+		Some((kind, public_key))
+	}
+
+	/// Lists a verification method of a document under the relationships of its purpose.
+	fn relate(
+		document: &mut DidDocument<T::AccountId, T::BlockNumber>,
+		key: Vec<u8>,
+		purpose: KeyPurpose,
+	) {
+		match purpose {
+			KeyPurpose::Authentication => {
+				document.authentication.push(key.clone());
+				document.assertion_method.push(key);
+			},
+			KeyPurpose::AssertionMethod => document.assertion_method.push(key),
+			KeyPurpose::KeyAgreement => document.key_agreement.push(key),
+		}
+	}
+
+	/// Builds a document fragment such as `delegate-1` from a prefix and an index.
+	fn fragment(prefix: &[u8], index: u32) -> Vec<u8> {
+		let mut digits = Vec::new();
+		let mut rest = index;
+		loop {
+			digits.push(b'0' + (rest % 10) as u8);
+			rest /= 10;
+			if rest == 0 {
+				break;
+			}
+		}
+		digits.reverse();
+
+		let mut fragment = prefix.to_vec();
+		fragment.extend(digits);
+		fragment
+	}
 }
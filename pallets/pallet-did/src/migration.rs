@@ -0,0 +1,87 @@
+//! Storage migrations of pallet-did.
+
+use crate::{types::Attribute, AttributeOf, BalanceOf, Config, MomentOf, Pallet};
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::{pallet_prelude::*, traits::Get};
+use sp_std::vec::Vec;
+
+/// Wrapper for all migrations of this pallet, based on `StorageVersion`.
+pub fn migrate<T: Config>() -> Weight {
+	let version = StorageVersion::get::<Pallet<T>>();
+	let mut weight: Weight = 0;
+
+	if version < 1 {
+		weight = weight.saturating_add(v1::migrate::<T>());
+		StorageVersion::new(1).put::<Pallet<T>>();
+	}
+
+	weight
+}
+
+/// Records the number of attributes before `migrate`, when testing the upgrade with try-runtime.
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+	let attributes = <AttributeOf<T>>::iter_keys().count() as u32;
+	<Pallet<T>>::set_temp_storage(attributes, "attributes");
+	Ok(())
+}
+
+/// Checks that every attribute left after `migrate` decodes and none was added.
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+	ensure!(StorageVersion::get::<Pallet<T>>() == 1, "the storage version is not 1");
+	let before: u32 =
+		<Pallet<T>>::get_temp_storage("attributes").ok_or("pre_upgrade did not run")?;
+	let keys = <AttributeOf<T>>::iter_keys().count() as u32;
+	ensure!(keys <= before, "attributes were added");
+	ensure!(<AttributeOf<T>>::iter_values().count() as u32 == keys, "attributes do not decode");
+	Ok(())
+}
+
+/// V1: Storage is bounded by `MaxDelegateTypeLength`, `MaxNameLength` and `MaxValueLength`.
+/// Bounded keys are encoded like the former `Vec<u8>` keys so only attribute records are
+/// translated. Records exceeding the limits are dropped and their deposit is released.
+mod v1 {
+	use super::*;
+
+	type OldAttribute<T> =
+		Attribute<<T as frame_system::Config>::BlockNumber, MomentOf<T>, Vec<u8>, Vec<u8>>;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut translated: Weight = 0;
+		let mut dropped: Vec<(T::AccountId, BalanceOf<T>)> = Vec::new();
+
+		<AttributeOf<T>>::translate::<OldAttribute<T>, _>(|identity, _id, old| {
+			translated += 1;
+			let bytes = old.name.len() + old.value.len();
+			match (old.name.try_into(), old.value.try_into()) {
+				(Ok(name), Ok(value)) => Some(Attribute {
+					name,
+					value,
+					validity: old.validity,
+					creation: old.creation,
+					nonce: old.nonce,
+				}),
+				_ => {
+					dropped.push((identity, <Pallet<T>>::storage_deposit(bytes)));
+					None
+				},
+			}
+		});
+
+		if !dropped.is_empty() {
+			log::warn!(
+				target: "runtime::did",
+				"Dropped {} attributes exceeding the storage limits",
+				dropped.len(),
+			);
+		}
+		let released = dropped.len() as Weight;
+		for (identity, amount) in dropped {
+			<Pallet<T>>::release_deposit(&identity, amount);
+		}
+
+		T::DbWeight::get().reads_writes(translated + released * 3, translated + released * 2)
+	}
+}
@@ -0,0 +1,209 @@
+use crate as pallet_did;
+use codec::{Decode, Encode};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system as system;
+use scale_info::TypeInfo;
+use sp_core::{sr25519, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use sp_runtime::{
+	testing::{Header, TestSignature},
+	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Lazy, Verify},
+	KeyTypeId,
+};
+use std::sync::Arc;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Amount reserved for each delegate or attribute stored.
+pub const DEPOSIT_PER_ITEM: u64 = 10;
+/// Amount reserved for each byte of a delegate type or an attribute name and value.
+pub const DEPOSIT_PER_BYTE: u64 = 1;
+/// Free balance of the endowed accounts.
+pub const ENDOWMENT: u64 = 1_000;
+/// Blocks an expired delegate or attribute is kept before being pruned.
+pub const GRACE_PERIOD: u64 = 10;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+pub const DAVE: u64 = 4;
+pub const EVE: u64 = 5;
+
+/// Key type of the sr25519 keys generated by the benchmarks.
+const BENCHMARK_KEY_TYPE: KeyTypeId = KeyTypeId(*b"did!");
+
+/// Account of an sr25519 key, taken from the first bytes of the key.
+fn sr25519_account(public: &sr25519::Public) -> u64 {
+	let mut account = [0u8; 8];
+	account.copy_from_slice(&public.0[..8]);
+	u64::from_le_bytes(account)
+}
+
+/// Key of a mock account, either its id or an sr25519 key generated by the benchmarks.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TestPublic(pub u64);
+
+impl IdentifyAccount for TestPublic {
+	type AccountId = u64;
+	fn into_account(self) -> u64 {
+		self.0
+	}
+}
+
+impl From<sr25519::Public> for TestPublic {
+	fn from(public: sr25519::Public) -> Self {
+		Self(sr25519_account(&public))
+	}
+}
+
+/// Native signature of a mock account. A `TestSignature` is checked against the account id
+/// and the message it holds, an sr25519 signature against the key of the account in the
+/// keystore.
+#[derive(Clone, Eq, PartialEq, Debug, Encode, Decode, TypeInfo)]
+pub enum MockSignature {
+	Test(TestSignature),
+	Sr25519(sr25519::Signature),
+}
+
+impl Verify for MockSignature {
+	type Signer = TestPublic;
+	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &u64) -> bool {
+		match self {
+			MockSignature::Test(signature) => signature.verify(msg, signer),
+			MockSignature::Sr25519(signature) =>
+				sp_io::crypto::sr25519_public_keys(BENCHMARK_KEY_TYPE)
+					.iter()
+					.find(|public| sr25519_account(public) == *signer)
+					.map_or(false, |public| {
+						sp_io::crypto::sr25519_verify(signature, msg.get(), public)
+					}),
+		}
+	}
+}
+
+impl From<sr25519::Signature> for MockSignature {
+	fn from(signature: sr25519::Signature) -> Self {
+		MockSignature::Sr25519(signature)
+	}
+}
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Did: pallet_did,
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PermissionDelegateTypes: &'static [&'static [u8]] =
+		&[pallet_did::DELEGATE_ATTRIBUTE_ADMIN];
+}
+
+impl pallet_did::Config for Test {
+	type Event = Event;
+	type Public = TestPublic;
+	type Signature = MockSignature;
+	type Time = Timestamp;
+	type Currency = Balances;
+	type DepositPerItem = ConstU64<DEPOSIT_PER_ITEM>;
+	type DepositPerByte = ConstU64<DEPOSIT_PER_BYTE>;
+	type MaxDelegateTypeLength = ConstU32<64>;
+	type MaxNameLength = ConstU32<64>;
+	type MaxValueLength = ConstU32<128>;
+	type MaxOperations = ConstU32<4>;
+	type PruningGracePeriod = ConstU64<GRACE_PERIOD>;
+	type MaxGuardians = ConstU32<4>;
+	type DelegatePermissions = pallet_did::AttributeAdmin;
+	type PermissionDelegateTypes = PermissionDelegateTypes;
+	type MaxControllers = ConstU32<4>;
+	type OuterOrigin = Origin;
+	type Proposal = Call;
+	type MaxHandleLength = ConstU32<32>;
+	type HandlePeriod = ConstU64<100>;
+	type OnDeactivate = ();
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with(Default::default())
+}
+
+/// Test externalities with the identities of `genesis` provisioned by the pallet.
+pub fn new_test_ext_with(genesis: pallet_did::GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ALICE, ENDOWMENT),
+			(BOB, ENDOWMENT),
+			(CHARLIE, ENDOWMENT),
+			(DAVE, ENDOWMENT),
+			(EVE, ENDOWMENT),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	genesis.assimilate_storage(&mut storage).unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	// The benchmarks sign off-chain transactions with keys generated in the keystore.
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,1430 @@
+use crate::{mock::*, types::*, AttributeOf, DidOperationFor, DidOperations, Error, SignatureOf};
+use codec::Encode;
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{Currency, Get, Hooks, ReservableCurrency, StorageVersion},
+};
+use sp_core::{bytes::from_hex, ecdsa, Pair, H160, H256};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	testing::TestSignature,
+	traits::{BlakeTwo256, Hash},
+};
+
+/// Native signature of `payload` by `signer`, as checked by `TestSignature`.
+fn sign(signer: u64, payload: Vec<u8>) -> SignatureOf<Test> {
+	DidSignature::Native(MockSignature::Test(TestSignature(signer, payload)))
+}
+
+/// Transaction of `signer` setting the attribute `name` of `identity` for `validity` blocks.
+fn attribute_transaction(
+	identity: u64,
+	signer: u64,
+	name: &[u8],
+	validity: u32,
+	nonce: u64,
+	expiry: u32,
+) -> AttributeTransaction<SignatureOf<Test>, u64> {
+	let mut transaction = AttributeTransaction {
+		signature: sign(signer, Vec::new()),
+		name: name.to_vec(),
+		value: b"value".to_vec(),
+		validity,
+		signer,
+		identity,
+		nonce,
+		expiry,
+	};
+	transaction.signature = sign(signer, Did::attribute_transaction_payload(&transaction));
+	transaction
+}
+
+/// Deposit of a delegate or attribute holding `bytes` bytes.
+fn deposit(bytes: u64) -> u64 {
+	DEPOSIT_PER_ITEM + DEPOSIT_PER_BYTE * bytes
+}
+
+/// Sets the attribute `name` of an identity to "value" on behalf of its owner.
+fn add_attribute(identity: u64, name: &[u8]) {
+	let owner = Did::owner_of(identity).unwrap_or(identity);
+	assert_ok!(Did::add_attribute(
+		Origin::signed(owner),
+		identity,
+		name.to_vec(),
+		b"value".to_vec(),
+		None
+	));
+}
+
+/// Adds a delegate of an identity on behalf of its owner.
+fn add_delegate(identity: u64, delegate: u64, delegate_type: &[u8]) {
+	let owner = Did::owner_of(identity).unwrap_or(identity);
+	assert_ok!(Did::add_delegate(
+		Origin::signed(owner),
+		identity,
+		delegate,
+		delegate_type.to_vec(),
+		None
+	));
+}
+
+#[test]
+fn execute_sets_the_attribute_signed_by_the_owner() {
+	new_test_ext().execute_with(|| {
+		let transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		// Anyone can submit a transaction signed off-chain.
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"value"));
+		assert_eq!(Did::signing_nonce_of(ALICE), 1);
+	});
+}
+
+#[test]
+fn execute_rejects_a_replayed_nonce() {
+	new_test_ext().execute_with(|| {
+		let transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction.clone()));
+
+		assert_noop!(
+			Did::execute(Origin::signed(BOB), transaction),
+			Error::<Test>::TransactionReplayed
+		);
+		// A nonce ahead of the stored one is rejected as well.
+		let transaction = attribute_transaction(ALICE, ALICE, b"other", 10, 2, 5);
+		assert_noop!(
+			Did::execute(Origin::signed(BOB), transaction),
+			Error::<Test>::TransactionReplayed
+		);
+	});
+}
+
+#[test]
+fn execute_rejects_an_expired_signature() {
+	new_test_ext().execute_with(|| {
+		let transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		System::set_block_number(6);
+
+		assert_noop!(
+			Did::execute(Origin::signed(BOB), transaction),
+			Error::<Test>::TransactionExpired
+		);
+		assert_eq!(Did::signing_nonce_of(ALICE), 0);
+	});
+}
+
+#[test]
+fn execute_accepts_a_signature_until_its_expiry() {
+	new_test_ext().execute_with(|| {
+		let transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		System::set_block_number(5);
+
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+	});
+}
+
+#[test]
+fn execute_rejects_a_signature_for_another_chain() {
+	new_test_ext().execute_with(|| {
+		let transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		// The same transaction on a chain with another genesis hash.
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+
+		assert_noop!(Did::execute(Origin::signed(BOB), transaction), Error::<Test>::BadSignature);
+	});
+}
+
+#[test]
+fn execute_rejects_a_signature_of_another_account() {
+	new_test_ext().execute_with(|| {
+		let mut transaction = attribute_transaction(ALICE, ALICE, b"name", 10, 0, 5);
+		transaction.signature = sign(BOB, Did::attribute_transaction_payload(&transaction));
+
+		assert_noop!(Did::execute(Origin::signed(BOB), transaction), Error::<Test>::BadSignature);
+	});
+}
+
+#[test]
+fn writes_reserve_deposits_from_the_owner() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+		add_delegate(ALICE, BOB, crate::DELEGATE_VERIFICATION_KEY);
+
+		let expected = deposit(4 + 5) + deposit(7);
+		assert_eq!(Did::deposit_of(ALICE), expected);
+		assert_eq!(Balances::reserved_balance(ALICE), expected);
+	});
+}
+
+#[test]
+fn writes_fail_without_the_deposit() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&EVE, deposit(9) - 1);
+		assert_noop!(
+			Did::add_attribute(Origin::signed(EVE), EVE, b"name".to_vec(), b"value".to_vec(), None),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn change_owner_moves_the_deposit_to_the_new_owner() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+		assert_ok!(Did::change_owner(Origin::signed(ALICE), ALICE, BOB));
+
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), deposit(9));
+		assert_eq!(Did::deposit_of(ALICE), deposit(9));
+
+		// Later deposits of the identity are reserved from its new owner and released to it.
+		assert_ok!(Did::delete_attribute(Origin::signed(BOB), ALICE, b"name".to_vec()));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Did::deposit_of(ALICE), 0);
+	});
+}
+
+#[test]
+fn change_owner_fails_if_the_new_owner_cannot_pay_the_deposit() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+		Balances::make_free_balance_be(&EVE, deposit(9) - 1);
+
+		assert_noop!(
+			Did::change_owner(Origin::signed(ALICE), ALICE, EVE),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn revoked_items_release_their_deposit_when_pruned() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+		add_delegate(ALICE, BOB, crate::DELEGATE_VERIFICATION_KEY);
+		assert_ok!(Did::revoke_attribute(Origin::signed(ALICE), ALICE, b"name".to_vec()));
+		assert_ok!(Did::revoke_delegate(
+			Origin::signed(ALICE),
+			ALICE,
+			crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+			BOB
+		));
+
+		// Revoked items keep their deposit for the grace period.
+		Did::on_idle(GRACE_PERIOD, u64::MAX);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(9) + deposit(7));
+
+		Did::on_idle(1 + GRACE_PERIOD, u64::MAX);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Did::deposit_of(ALICE), 0);
+		assert_eq!(crate::DelegateOf::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn pruning_resumes_from_its_position_in_the_next_block() {
+	new_test_ext().execute_with(|| {
+		for delegate in [BOB, CHARLIE] {
+			add_delegate(ALICE, delegate, crate::DELEGATE_VERIFICATION_KEY);
+			assert_ok!(Did::revoke_delegate(
+				Origin::signed(ALICE),
+				ALICE,
+				crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+				delegate
+			));
+		}
+		for name in [b"name1", b"name2"] {
+			add_attribute(ALICE, name);
+			assert_ok!(Did::revoke_attribute(Origin::signed(ALICE), ALICE, name.to_vec()));
+		}
+		assert_eq!(Balances::reserved_balance(ALICE), 2 * deposit(7) + 2 * deposit(10));
+
+		// Room for three items sweeps the delegates and stops within the attributes.
+		let base = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
+		let delegate_weight = <() as crate::WeightInfo>::prune_delegate();
+		let attribute_weight = <() as crate::WeightInfo>::prune_attribute();
+		let budget = base + 2 * delegate_weight + attribute_weight;
+		assert_eq!(Did::on_idle(1 + GRACE_PERIOD, budget), budget);
+		assert_eq!(crate::DelegateOf::<Test>::iter().count(), 0);
+		assert_eq!(AttributeOf::<Test>::iter().count(), 1);
+		assert!(matches!(Did::pruning_position(), PruningCursor::Attributes(Some(_))));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(10));
+		System::assert_has_event(
+			crate::Event::DelegatePruned(ALICE, crate::DELEGATE_VERIFICATION_KEY.to_vec(), BOB)
+				.into(),
+		);
+		System::assert_has_event(
+			crate::Event::DelegatePruned(ALICE, crate::DELEGATE_VERIFICATION_KEY.to_vec(), CHARLIE)
+				.into(),
+		);
+
+		// The next block resumes with the remaining attribute.
+		Did::on_idle(2 + GRACE_PERIOD, budget);
+		assert_eq!(AttributeOf::<Test>::iter().count(), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Did::deposit_of(ALICE), 0);
+		System::assert_has_event(crate::Event::AttributePruned(ALICE, b"name1".to_vec()).into());
+		System::assert_has_event(crate::Event::AttributePruned(ALICE, b"name2".to_vec()).into());
+	});
+}
+
+/// Lets BOB, CHARLIE and DAVE recover ALICE, two of them after a delay of 5 blocks.
+fn set_up_guardians() {
+	assert_ok!(Did::set_guardians(Origin::signed(ALICE), ALICE, vec![BOB, CHARLIE, DAVE], 2, 5));
+}
+
+#[test]
+fn genesis_provisions_identities_and_reserves_their_deposits_from_the_owners() {
+	let genesis = crate::GenesisConfig::<Test> {
+		owners: vec![(ALICE, BOB)],
+		delegates: vec![(ALICE, crate::DELEGATE_VERIFICATION_KEY.to_vec(), CHARLIE)],
+		attributes: vec![
+			(ALICE, b"name".to_vec(), b"alice".to_vec()),
+			(DAVE, b"name".to_vec(), b"dave".to_vec()),
+		],
+	};
+	new_test_ext_with(genesis).execute_with(|| {
+		assert_eq!(Did::owner_of(ALICE), Some(BOB));
+		assert_ok!(Did::valid_delegate(&ALICE, crate::DELEGATE_VERIFICATION_KEY, &CHARLIE));
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"alice"));
+		assert_ok!(Did::valid_attribute(&DAVE, b"name", b"dave"));
+
+		let expected = deposit(7) + deposit(4 + 5);
+		assert_eq!(Did::deposit_of(ALICE), expected);
+		assert_eq!(Balances::reserved_balance(BOB), expected);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Did::deposit_of(DAVE), deposit(4 + 4));
+		assert_eq!(Balances::reserved_balance(DAVE), deposit(4 + 4));
+	});
+}
+
+#[test]
+#[should_panic(expected = "genesis attributes are valid and their deposit can be reserved")]
+fn genesis_fails_if_the_owner_cannot_pay_the_deposits() {
+	// The owner is not endowed.
+	let genesis = crate::GenesisConfig::<Test> {
+		owners: vec![(ALICE, 42)],
+		attributes: vec![(ALICE, b"name".to_vec(), b"alice".to_vec())],
+		..Default::default()
+	};
+	new_test_ext_with(genesis);
+}
+
+#[test]
+#[should_panic(expected = "genesis delegates are valid and their deposit can be reserved")]
+fn genesis_fails_with_unregistered_delegate_types() {
+	let genesis = crate::GenesisConfig::<Test> {
+		delegates: vec![(ALICE, b"unknown".to_vec(), CHARLIE)],
+		..Default::default()
+	};
+	new_test_ext_with(genesis);
+}
+
+#[test]
+fn recovery_completes_once_the_threshold_is_reached() {
+	new_test_ext().execute_with(|| {
+		set_up_guardians();
+		assert_ok!(Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE));
+		System::set_block_number(6);
+		assert_noop!(
+			Did::complete_recovery(Origin::signed(EVE), ALICE),
+			Error::<Test>::RecoveryNotReady
+		);
+
+		assert_ok!(Did::approve_recovery(Origin::signed(CHARLIE), ALICE));
+		assert_ok!(Did::complete_recovery(Origin::signed(EVE), ALICE));
+		assert_eq!(Did::owner_of(ALICE), Some(EVE));
+		assert!(Did::active_recovery_of(ALICE).is_none());
+		// The guardian set keeps its deposit, now reserved from the new owner.
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(EVE), Did::deposit_of(ALICE));
+	});
+}
+
+#[test]
+fn recovery_completes_once_the_delay_has_passed() {
+	new_test_ext().execute_with(|| {
+		set_up_guardians();
+		assert_ok!(Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE));
+		assert_ok!(Did::approve_recovery(Origin::signed(CHARLIE), ALICE));
+		System::set_block_number(5);
+		assert_noop!(
+			Did::complete_recovery(Origin::signed(EVE), ALICE),
+			Error::<Test>::RecoveryNotReady
+		);
+
+		System::set_block_number(6);
+		assert_ok!(Did::complete_recovery(Origin::signed(EVE), ALICE));
+		assert_eq!(Did::owner_of(ALICE), Some(EVE));
+	});
+}
+
+#[test]
+fn recovery_requires_guardians() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE),
+			Error::<Test>::NoGuardians
+		);
+
+		set_up_guardians();
+		assert_noop!(
+			Did::initiate_recovery(Origin::signed(EVE), ALICE, EVE),
+			Error::<Test>::NotGuardian
+		);
+		assert_ok!(Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE));
+		assert_noop!(
+			Did::initiate_recovery(Origin::signed(CHARLIE), ALICE, CHARLIE),
+			Error::<Test>::RecoveryInProgress
+		);
+		assert_noop!(Did::approve_recovery(Origin::signed(EVE), ALICE), Error::<Test>::NotGuardian);
+		assert_noop!(
+			Did::approve_recovery(Origin::signed(BOB), ALICE),
+			Error::<Test>::AlreadyApproved
+		);
+	});
+}
+
+#[test]
+fn owner_cancels_a_recovery() {
+	new_test_ext().execute_with(|| {
+		set_up_guardians();
+		assert_ok!(Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE));
+		assert_noop!(Did::cancel_recovery(Origin::signed(BOB), ALICE), Error::<Test>::NotOwner);
+
+		assert_ok!(Did::cancel_recovery(Origin::signed(ALICE), ALICE));
+		assert!(Did::active_recovery_of(ALICE).is_none());
+		assert_noop!(
+			Did::approve_recovery(Origin::signed(CHARLIE), ALICE),
+			Error::<Test>::NoRecovery
+		);
+		System::set_block_number(6);
+		assert_noop!(Did::complete_recovery(Origin::signed(EVE), ALICE), Error::<Test>::NoRecovery);
+		assert_eq!(Did::owner_of(ALICE), None);
+	});
+}
+
+#[test]
+fn set_guardians_validates_the_threshold_and_the_guardians() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::set_guardians(Origin::signed(ALICE), ALICE, vec![BOB, CHARLIE], 0, 5),
+			Error::<Test>::InvalidGuardians
+		);
+		assert_noop!(
+			Did::set_guardians(Origin::signed(ALICE), ALICE, vec![BOB, CHARLIE], 3, 5),
+			Error::<Test>::InvalidGuardians
+		);
+		assert_noop!(
+			Did::set_guardians(Origin::signed(ALICE), ALICE, vec![BOB, BOB], 1, 5),
+			Error::<Test>::InvalidGuardians
+		);
+		assert_noop!(
+			Did::set_guardians(Origin::signed(BOB), ALICE, vec![BOB], 1, 5),
+			Error::<Test>::NotOwner
+		);
+
+		set_up_guardians();
+		assert_ok!(Did::initiate_recovery(Origin::signed(BOB), ALICE, EVE));
+		assert_noop!(
+			Did::set_guardians(Origin::signed(ALICE), ALICE, vec![BOB], 1, 5),
+			Error::<Test>::RecoveryInProgress
+		);
+	});
+}
+
+#[test]
+fn change_owner_signed_transfers_the_identity() {
+	new_test_ext().execute_with(|| {
+		let mut transaction = ChangeOwnerTransaction {
+			signature: sign(ALICE, Vec::new()),
+			signer: ALICE,
+			identity: ALICE,
+			new_owner: BOB,
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(ALICE, Did::change_owner_payload(&transaction));
+		assert_ok!(Did::change_owner_signed(Origin::signed(CHARLIE), transaction.clone()));
+
+		assert_eq!(Did::owner_of(ALICE), Some(BOB));
+		assert_eq!(Did::signing_nonce_of(ALICE), 1);
+		// The former owner can no longer sign for the identity.
+		transaction.nonce = 1;
+		transaction.new_owner = ALICE;
+		transaction.signature = sign(ALICE, Did::change_owner_payload(&transaction));
+		assert_noop!(
+			Did::change_owner_signed(Origin::signed(CHARLIE), transaction),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn add_and_revoke_delegate_signed_manage_delegates() {
+	new_test_ext().execute_with(|| {
+		let mut transaction = AddDelegateTransaction {
+			signature: sign(ALICE, Vec::new()),
+			signer: ALICE,
+			identity: ALICE,
+			delegate: BOB,
+			delegate_type: crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+			valid_for: None,
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(ALICE, Did::add_delegate_payload(&transaction));
+		assert_ok!(Did::add_delegate_signed(Origin::signed(CHARLIE), transaction));
+		assert_ok!(Did::valid_delegate(&ALICE, crate::DELEGATE_VERIFICATION_KEY, &BOB));
+
+		let mut transaction = RevokeDelegateTransaction {
+			signature: sign(ALICE, Vec::new()),
+			signer: ALICE,
+			identity: ALICE,
+			delegate_type: crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+			delegate: BOB,
+			nonce: 1,
+			expiry: 5,
+		};
+		transaction.signature = sign(ALICE, Did::revoke_delegate_payload(&transaction));
+		assert_ok!(Did::revoke_delegate_signed(Origin::signed(CHARLIE), transaction));
+		assert_err!(
+			Did::valid_delegate(&ALICE, crate::DELEGATE_VERIFICATION_KEY, &BOB),
+			Error::<Test>::InvalidDelegate
+		);
+		assert_eq!(Did::signing_nonce_of(ALICE), 2);
+	});
+}
+
+#[test]
+fn revoke_attribute_signed_is_authorized_for_attribute_admins() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+		add_delegate(ALICE, BOB, crate::DELEGATE_ATTRIBUTE_ADMIN);
+
+		let mut transaction = RevokeAttributeTransaction {
+			signature: sign(BOB, Vec::new()),
+			signer: BOB,
+			identity: ALICE,
+			name: b"name".to_vec(),
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(BOB, Did::revoke_attribute_payload(&transaction));
+		assert_ok!(Did::revoke_attribute_signed(Origin::signed(CHARLIE), transaction));
+
+		assert_err!(
+			Did::valid_attribute(&ALICE, b"name", b"value"),
+			Error::<Test>::InvalidAttribute
+		);
+		assert_eq!(Did::signing_nonce_of(ALICE), 1);
+	});
+}
+
+#[test]
+fn delete_attribute_signed_removes_the_attribute() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"name");
+
+		let mut transaction = DeleteAttributeTransaction {
+			signature: sign(ALICE, Vec::new()),
+			signer: ALICE,
+			identity: ALICE,
+			name: b"name".to_vec(),
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(ALICE, Did::delete_attribute_payload(&transaction));
+		assert_ok!(Did::delete_attribute_signed(Origin::signed(CHARLIE), transaction));
+
+		assert!(Did::attribute_and_id(&ALICE, b"name").is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn signed_transactions_do_not_consume_the_nonce_when_failing() {
+	new_test_ext().execute_with(|| {
+		// The signature is valid but there is no attribute to delete.
+		let mut transaction = DeleteAttributeTransaction {
+			signature: sign(ALICE, Vec::new()),
+			signer: ALICE,
+			identity: ALICE,
+			name: b"name".to_vec(),
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(ALICE, Did::delete_attribute_payload(&transaction));
+		assert_noop!(
+			Did::delete_attribute_signed(Origin::signed(CHARLIE), transaction),
+			Error::<Test>::AttributeRemovalFailed
+		);
+		assert_eq!(Did::signing_nonce_of(ALICE), 0);
+
+		// Nor when the signer is not authorized.
+		let mut transaction = RevokeAttributeTransaction {
+			signature: sign(BOB, Vec::new()),
+			signer: BOB,
+			identity: ALICE,
+			name: b"name".to_vec(),
+			nonce: 0,
+			expiry: 5,
+		};
+		transaction.signature = sign(BOB, Did::revoke_attribute_payload(&transaction));
+		assert_noop!(
+			Did::revoke_attribute_signed(Origin::signed(CHARLIE), transaction),
+			Error::<Test>::NotOwner
+		);
+		assert_eq!(Did::signing_nonce_of(ALICE), 0);
+	});
+}
+
+#[test]
+fn add_delegate_rejects_unknown_delegate_types() {
+	new_test_ext().execute_with(|| {
+		// A typo of `sigAuth` is neither a verification method nor a registered permission.
+		assert_noop!(
+			Did::add_delegate(Origin::signed(ALICE), ALICE, BOB, b"sigAuht".to_vec(), None),
+			Error::<Test>::UnknownDelegateType
+		);
+		assert_noop!(
+			Did::add_delegate(Origin::signed(ALICE), ALICE, BOB, b"Ed25519/sigAuht".to_vec(), None),
+			Error::<Test>::InvalidVerificationMethod
+		);
+		assert_noop!(
+			Did::add_delegate(Origin::signed(ALICE), ALICE, BOB, b"X25519/enc".to_vec(), None),
+			Error::<Test>::InvalidVerificationMethod
+		);
+
+		add_delegate(ALICE, BOB, crate::DELEGATE_SIGNATURE_AUTH);
+		add_delegate(ALICE, BOB, b"Ed25519/sigAuth");
+		add_delegate(ALICE, BOB, crate::DELEGATE_ATTRIBUTE_ADMIN);
+	});
+}
+
+#[test]
+fn valid_signer_accepts_the_owner_and_asserting_delegates() {
+	new_test_ext().execute_with(|| {
+		let msg = b"credential".to_vec();
+		add_delegate(ALICE, BOB, b"Ed25519/veriKey");
+		add_delegate(ALICE, CHARLIE, crate::DELEGATE_ATTRIBUTE_ADMIN);
+
+		assert_ok!(Did::valid_signer(&ALICE, &sign(ALICE, msg.clone()), &msg, &ALICE));
+		assert_ok!(Did::valid_signer(&ALICE, &sign(BOB, msg.clone()), &msg, &BOB));
+		// Permission delegates do not sign on behalf of the identity.
+		assert_noop!(
+			Did::valid_signer(&ALICE, &sign(CHARLIE, msg.clone()), &msg, &CHARLIE),
+			Error::<Test>::InvalidDelegate
+		);
+		assert_noop!(
+			Did::valid_signer(&ALICE, &sign(BOB, b"other".to_vec()), &msg, &BOB),
+			Error::<Test>::BadSignature
+		);
+
+		assert_ok!(Did::revoke_delegate(
+			Origin::signed(ALICE),
+			ALICE,
+			b"Ed25519/veriKey".to_vec(),
+			BOB
+		));
+		assert_noop!(
+			Did::valid_signer(&ALICE, &sign(BOB, msg.clone()), &msg, &BOB),
+			Error::<Test>::InvalidDelegate
+		);
+	});
+}
+
+/// Hands ALICE to BOB, CHARLIE and DAVE, two of them approving each call.
+fn set_up_controllers() {
+	assert_ok!(Did::set_controllers(Origin::signed(ALICE), ALICE, vec![BOB, CHARLIE, DAVE], 2));
+}
+
+/// Call of the controllers of ALICE setting its attribute `name`.
+fn add_attribute_proposal(name: &[u8]) -> Box<Call> {
+	Box::new(Call::Did(crate::Call::add_attribute {
+		identity: ALICE,
+		name: name.to_vec(),
+		value: b"value".to_vec(),
+		valid_for: None,
+	}))
+}
+
+#[test]
+fn proposals_execute_once_the_threshold_is_reached() {
+	new_test_ext().execute_with(|| {
+		set_up_controllers();
+		let proposal = add_attribute_proposal(b"name");
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, proposal.clone()));
+		let deposit = Did::proposal_of(ALICE, proposal_hash).unwrap().deposit;
+		assert_eq!(Balances::reserved_balance(BOB), deposit);
+
+		assert_noop!(
+			Did::execute_proposal(Origin::signed(BOB), ALICE, proposal.clone()),
+			Error::<Test>::ThresholdNotReached
+		);
+		assert_ok!(Did::approve_proposal(Origin::signed(CHARLIE), ALICE, proposal_hash));
+		assert_ok!(Did::execute_proposal(Origin::signed(CHARLIE), ALICE, proposal));
+
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"value"));
+		assert!(Did::proposal_of(ALICE, proposal_hash).is_none());
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		System::assert_last_event(
+			crate::Event::ProposalExecuted(ALICE, proposal_hash, Ok(())).into(),
+		);
+		// The controllers only act for the identity through its approved proposals.
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(CHARLIE),
+				ALICE,
+				b"other".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::ApprovalRequired
+		);
+	});
+}
+
+#[test]
+fn failing_proposals_report_the_error_of_the_call() {
+	new_test_ext().execute_with(|| {
+		set_up_controllers();
+		let proposal = Box::new(Call::Did(crate::Call::delete_attribute {
+			identity: ALICE,
+			name: b"name".to_vec(),
+		}));
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, proposal.clone()));
+		assert_ok!(Did::approve_proposal(Origin::signed(CHARLIE), ALICE, proposal_hash));
+
+		// The execution succeeds and reports the error of the call.
+		assert_ok!(Did::execute_proposal(Origin::signed(BOB), ALICE, proposal));
+		System::assert_last_event(
+			crate::Event::ProposalExecuted(
+				ALICE,
+				proposal_hash,
+				Err(Error::<Test>::AttributeRemovalFailed.into()),
+			)
+			.into(),
+		);
+		assert_noop!(
+			Did::change_owner(Origin::signed(BOB), ALICE, BOB),
+			Error::<Test>::ApprovalRequired
+		);
+	});
+}
+
+#[test]
+fn the_identity_origin_only_acts_for_its_identity() {
+	new_test_ext().execute_with(|| {
+		set_up_controllers();
+		let origin = || Origin::from(RawOrigin::Identity(ALICE));
+
+		assert_ok!(Did::add_attribute(origin(), ALICE, b"name".to_vec(), b"value".to_vec(), None));
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"value"));
+		assert_eq!(Did::ensure_caller(origin()), Ok(Caller::Identity(ALICE)));
+		assert_noop!(
+			Did::add_attribute(origin(), BOB, b"name".to_vec(), b"value".to_vec(), None),
+			Error::<Test>::NotOwner
+		);
+		// The identity account signing on its own is not the identity origin.
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(ALICE),
+				ALICE,
+				b"other".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::NotOwner
+		);
+
+		// Identities owned by the identity are acted for as by their owner.
+		assert_ok!(Did::change_owner(Origin::signed(EVE), EVE, ALICE));
+		assert_ok!(Did::add_attribute(origin(), EVE, b"name".to_vec(), b"value".to_vec(), None));
+		assert_ok!(Did::valid_attribute(&EVE, b"name", b"value"));
+	});
+}
+
+#[test]
+fn non_controllers_cannot_act_for_a_controlled_identity() {
+	new_test_ext().execute_with(|| {
+		set_up_controllers();
+		let proposal = add_attribute_proposal(b"name");
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+
+		assert_noop!(
+			Did::propose(Origin::signed(EVE), ALICE, proposal.clone()),
+			Error::<Test>::NotController
+		);
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, proposal.clone()));
+		assert_noop!(
+			Did::approve_proposal(Origin::signed(EVE), ALICE, proposal_hash),
+			Error::<Test>::NotController
+		);
+		assert_noop!(
+			Did::execute_proposal(Origin::signed(EVE), ALICE, proposal),
+			Error::<Test>::NotController
+		);
+		// The former owner lost the identity to its controllers.
+		assert_noop!(
+			Did::add_attribute(
+				Origin::signed(ALICE),
+				ALICE,
+				b"name".to_vec(),
+				b"value".to_vec(),
+				None
+			),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn approvals_of_removed_controllers_do_not_count() {
+	new_test_ext().execute_with(|| {
+		set_up_controllers();
+		let proposal = add_attribute_proposal(b"name");
+		let proposal_hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, proposal.clone()));
+		assert_ok!(Did::approve_proposal(Origin::signed(CHARLIE), ALICE, proposal_hash));
+
+		// The controllers replace themselves without CHARLIE.
+		let replace = Box::new(Call::Did(crate::Call::set_controllers {
+			identity: ALICE,
+			controllers: vec![BOB, DAVE],
+			threshold: 2,
+		}));
+		let replace_hash = BlakeTwo256::hash_of(&replace);
+		assert_ok!(Did::propose(Origin::signed(BOB), ALICE, replace.clone()));
+		assert_ok!(Did::approve_proposal(Origin::signed(DAVE), ALICE, replace_hash));
+		assert_ok!(Did::execute_proposal(Origin::signed(DAVE), ALICE, replace));
+
+		assert_noop!(
+			Did::execute_proposal(Origin::signed(BOB), ALICE, proposal),
+			Error::<Test>::ThresholdNotReached
+		);
+	});
+}
+
+/// Private key of the first Hardhat and Anvil development account.
+const ETHEREUM_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+/// Address of `ETHEREUM_KEY`.
+const ETHEREUM_ADDRESS: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+
+fn ethereum_pair() -> ecdsa::Pair {
+	let mut seed = [0u8; 32];
+	seed.copy_from_slice(&from_hex(ETHEREUM_KEY).unwrap());
+	ecdsa::Pair::from_seed(&seed)
+}
+
+fn ethereum_address() -> H160 {
+	H160::from_slice(&from_hex(ETHEREUM_ADDRESS).unwrap())
+}
+
+/// Signature in the `r || s || v` form returned by Ethereum wallets.
+fn ethereum_signature(hex: &str) -> ecdsa::Signature {
+	let mut raw = [0u8; 65];
+	raw.copy_from_slice(&from_hex(hex).unwrap());
+	ecdsa::Signature::from_raw(raw)
+}
+
+#[test]
+fn eip191_hash_matches_personal_sign() {
+	new_test_ext().execute_with(|| {
+		// `hashMessage("Hello World")` of ethers.js.
+		assert_eq!(
+			Did::eip191_hash(b"Hello World").to_vec(),
+			from_hex("a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2").unwrap()
+		);
+	});
+}
+
+#[test]
+fn eip191_signature_recovers_the_signer_address() {
+	new_test_ext().execute_with(|| {
+		// `signMessage("Hello World")` of the development account.
+		let signature = ethereum_signature(
+			"65e72b1cf8e189569963750e10ccb88fe89389daeeb8b735277d59cd6885ee82\
+			3eb5a6982b540f185703492dab77b863a88ce01f27e21ade8b2879c10fc9e6531c",
+		);
+		let hash = Did::eip191_hash(b"Hello World");
+
+		assert_eq!(Did::ethereum_signer(&signature, &hash), Some(ethereum_address()));
+		assert_ne!(
+			Did::ethereum_signer(&signature, &Did::eip191_hash(b"Hello World!")),
+			Some(ethereum_address())
+		);
+	});
+}
+
+#[test]
+fn eip712_signature_recovers_the_signer_address() {
+	new_test_ext().execute_with(|| {
+		// `eth_signTypedData_v4` of the development account over the typed data
+		// {
+		//   "types": {
+		//     "EIP712Domain": [
+		//       { "name": "name", "type": "string" },
+		//       { "name": "version", "type": "string" },
+		//       { "name": "salt", "type": "bytes32" }
+		//     ],
+		//     "SetAttribute": [
+		//       { "name": "identity", "type": "bytes" },
+		//       { "name": "name", "type": "string" },
+		//       { "name": "value", "type": "bytes" },
+		//       { "name": "validity", "type": "uint32" },
+		//       { "name": "nonce", "type": "uint64" },
+		//       { "name": "expiry", "type": "uint32" }
+		//     ]
+		//   },
+		//   "primaryType": "SetAttribute",
+		//   "domain": {
+		//     "name": "Substrate DID",
+		//     "version": "1",
+		//     "salt": "0x4545454545454545454545454545454545454545454545454545454545454545"
+		//   },
+		//   "message": {
+		//     "identity": "0xaf238fa52f1c4262",
+		//     "name": "eip712",
+		//     "value": "0x76616c7565",
+		//     "validity": 10,
+		//     "nonce": 0,
+		//     "expiry": 5
+		//   }
+		// }
+		// where the salt is the genesis hash of the mock runtime and the identity the SCALE
+		// encoding of the account of the development address.
+		let identity = Did::ethereum_account(&ethereum_address());
+		assert_eq!(identity.encode(), from_hex("af238fa52f1c4262").unwrap());
+		Balances::make_free_balance_be(&identity, ENDOWMENT);
+		let mut transaction = attribute_transaction(identity, identity, b"eip712", 10, 0, 5);
+		let hash = Did::eip712_hash(&Did::attribute_transaction_struct_hash(&transaction));
+		assert_eq!(
+			hash.to_vec(),
+			from_hex("549e24ca27a2a19483b7755fcbe12b2ae7b83e23ae016b2d0d85f4d0e8de1f73").unwrap()
+		);
+		let signature = ethereum_signature(
+			"516a441450dcbba688036d1d1d70341a5736a3d59c3e6f8dffaca25d85464306\
+			4d78c4f6a386e20be2564de5773b4e7900794150a91e2dee6e5a5b608e5959ed1c",
+		);
+		assert_eq!(Did::ethereum_signer(&signature, &hash), Some(ethereum_address()));
+
+		transaction.signature = DidSignature::Eip712(signature);
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+		assert_ok!(Did::valid_attribute(&identity, b"eip712", b"value"));
+	});
+}
+
+#[test]
+fn eip712_hash_binds_every_field_and_the_chain() {
+	new_test_ext().execute_with(|| {
+		let identity = Did::ethereum_account(&ethereum_address());
+		let transaction = attribute_transaction(identity, identity, b"eip712", 10, 0, 5);
+		let hash = Did::eip712_hash(&Did::attribute_transaction_struct_hash(&transaction));
+
+		let mut other = transaction.clone();
+		other.value = b"other".to_vec();
+		assert_ne!(Did::eip712_hash(&Did::attribute_transaction_struct_hash(&other)), hash);
+		let mut other = transaction.clone();
+		other.nonce = 1;
+		assert_ne!(Did::eip712_hash(&Did::attribute_transaction_struct_hash(&other)), hash);
+
+		// The salt binds the typed data to this chain.
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+		assert_ne!(Did::eip712_hash(&Did::attribute_transaction_struct_hash(&transaction)), hash);
+	});
+}
+
+#[test]
+fn execute_accepts_ethereum_signatures_of_ethr_identities() {
+	new_test_ext().execute_with(|| {
+		let identity = Did::ethereum_account(&ethereum_address());
+		Balances::make_free_balance_be(&identity, ENDOWMENT);
+
+		let mut transaction = attribute_transaction(identity, identity, b"eip191", 10, 0, 5);
+		let payload = Did::attribute_transaction_payload(&transaction);
+		transaction.signature =
+			DidSignature::Eip191(ethereum_pair().sign_prehashed(&Did::eip191_hash(&payload)));
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+
+		let mut transaction = attribute_transaction(identity, identity, b"eip712", 10, 1, 5);
+		let hash = Did::eip712_hash(&Did::attribute_transaction_struct_hash(&transaction));
+		transaction.signature = DidSignature::Eip712(ethereum_pair().sign_prehashed(&hash));
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+
+		assert_ok!(Did::valid_attribute(&identity, b"eip191", b"value"));
+		assert_ok!(Did::valid_attribute(&identity, b"eip712", b"value"));
+	});
+}
+
+#[test]
+fn execute_rejects_ethereum_signatures_of_other_keys() {
+	new_test_ext().execute_with(|| {
+		let identity = Did::ethereum_account(&ethereum_address());
+		Balances::make_free_balance_be(&identity, ENDOWMENT);
+
+		let mut transaction = attribute_transaction(identity, identity, b"name", 10, 0, 5);
+		let payload = Did::attribute_transaction_payload(&transaction);
+		let other = ecdsa::Pair::from_seed(&[1u8; 32]);
+		transaction.signature =
+			DidSignature::Eip191(other.sign_prehashed(&Did::eip191_hash(&payload)));
+
+		assert_noop!(Did::execute(Origin::signed(BOB), transaction), Error::<Test>::BadSignature);
+	});
+}
+
+#[test]
+fn ethereum_signatures_record_the_address_of_their_account() {
+	new_test_ext().execute_with(|| {
+		let identity = Did::ethereum_account(&ethereum_address());
+		Balances::make_free_balance_be(&identity, ENDOWMENT);
+		assert_eq!(Did::resolve(&identity).ethereum_address, None);
+
+		let mut transaction = attribute_transaction(identity, identity, b"name", 10, 0, 5);
+		let payload = Did::attribute_transaction_payload(&transaction);
+		transaction.signature =
+			DidSignature::Eip191(ethereum_pair().sign_prehashed(&Did::eip191_hash(&payload)));
+		assert_ok!(Did::execute(Origin::signed(BOB), transaction));
+
+		assert_eq!(Did::ethereum_address_of(identity), Some(ethereum_address()));
+		let document = Did::resolve(&identity);
+		assert_eq!(document.ethereum_address, Some(ethereum_address()));
+		assert_eq!(document.verification_method[0].method_type, crate::ETHEREUM_METHOD_TYPE);
+		assert_eq!(
+			document.verification_method[0].public_key,
+			PublicKey::EthereumAddress(ethereum_address())
+		);
+	});
+}
+
+#[test]
+fn resolve_ethereum_keys_the_document_by_the_address() {
+	new_test_ext().execute_with(|| {
+		let identity = Did::ethereum_account(&ethereum_address());
+
+		let document = Did::resolve_ethereum(&ethereum_address());
+		assert_eq!(document.id, identity);
+		assert_eq!(document.ethereum_address, Some(ethereum_address()));
+		assert_eq!(
+			document.verification_method[0].public_key,
+			PublicKey::EthereumAddress(ethereum_address())
+		);
+
+		// Native identities and their owners keep their account methods.
+		let document = Did::resolve(&ALICE);
+		assert_eq!(document.ethereum_address, None);
+		assert_eq!(document.verification_method[0].public_key, PublicKey::BlockchainAccount(ALICE));
+	});
+}
+
+/// Bounded batch of document operations.
+fn operations(operations: Vec<DidOperationFor<Test>>) -> DidOperations<Test> {
+	operations.try_into().unwrap()
+}
+
+#[test]
+fn update_document_applies_every_operation_as_a_single_version() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"old");
+		add_attribute(ALICE, b"stale");
+		add_delegate(ALICE, DAVE, crate::DELEGATE_VERIFICATION_KEY);
+		let version = Did::document_version(ALICE).unwrap().version_id;
+
+		assert_ok!(Did::update_document(
+			Origin::signed(ALICE),
+			ALICE,
+			operations(vec![
+				DidOperation::AddDelegate {
+					delegate_type: crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+					delegate: BOB,
+					valid_for: None,
+				},
+				DidOperation::RevokeDelegate {
+					delegate_type: crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+					delegate: DAVE,
+				},
+				DidOperation::AddAttribute {
+					name: b"name".to_vec(),
+					value: b"alice".to_vec(),
+					valid_for: None,
+				},
+				DidOperation::DeleteAttribute { name: b"stale".to_vec() },
+			]),
+		));
+
+		assert_ok!(Did::valid_delegate(&ALICE, crate::DELEGATE_VERIFICATION_KEY, &BOB));
+		assert!(Did::valid_delegate(&ALICE, crate::DELEGATE_VERIFICATION_KEY, &DAVE).is_err());
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"alice"));
+		assert_ok!(Did::valid_attribute(&ALICE, b"old", b"value"));
+		assert!(Did::attribute_and_id(&ALICE, b"stale").is_none());
+		assert_eq!(Did::document_version(ALICE).unwrap().version_id, version + 1);
+		System::assert_last_event(crate::Event::DocumentUpdated(ALICE, ALICE, 4).into());
+
+		// The revoked delegate keeps its deposit until pruned, the deleted attribute not.
+		let expected = deposit(3 + 5) + deposit(7) * 2 + deposit(4 + 5);
+		assert_eq!(Did::deposit_of(ALICE), expected);
+		assert_eq!(Balances::reserved_balance(ALICE), expected);
+	});
+}
+
+#[test]
+fn update_document_applies_nothing_if_any_operation_fails() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::update_document(
+				Origin::signed(ALICE),
+				ALICE,
+				operations(vec![
+					DidOperation::AddAttribute {
+						name: b"name".to_vec(),
+						value: b"alice".to_vec(),
+						valid_for: None,
+					},
+					DidOperation::RevokeAttribute { name: b"missing".to_vec() },
+				]),
+			),
+			Error::<Test>::AttributeResetFailed
+		);
+		assert!(Did::attribute_and_id(&ALICE, b"name").is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
+#[test]
+fn update_document_authorizes_each_operation() {
+	new_test_ext().execute_with(|| {
+		add_delegate(ALICE, BOB, crate::DELEGATE_ATTRIBUTE_ADMIN);
+		let add_attribute = DidOperation::AddAttribute {
+			name: b"name".to_vec(),
+			value: b"alice".to_vec(),
+			valid_for: None,
+		};
+
+		// An attribute admin cannot add delegates, even along operations it may apply.
+		assert_noop!(
+			Did::update_document(
+				Origin::signed(BOB),
+				ALICE,
+				operations(vec![
+					add_attribute.clone(),
+					DidOperation::AddDelegate {
+						delegate_type: crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+						delegate: CHARLIE,
+						valid_for: None,
+					},
+				]),
+			),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Did::update_document(
+			Origin::signed(BOB),
+			ALICE,
+			operations(vec![add_attribute])
+		));
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"alice"));
+		assert_eq!(Did::updated_by(ALICE).unwrap().0, BOB);
+	});
+}
+
+#[test]
+fn update_document_requires_operations() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::update_document(Origin::signed(ALICE), ALICE, operations(Vec::new())),
+			Error::<Test>::NoOperations
+		);
+	});
+}
+
+/// Registers a handle of an identity on behalf of itself.
+fn register_handle(identity: u64, handle: &[u8]) {
+	assert_ok!(Did::register_handle(Origin::signed(identity), identity, handle.to_vec()));
+}
+
+#[test]
+fn registered_handles_resolve_both_ways() {
+	new_test_ext().execute_with(|| {
+		register_handle(ALICE, b"alice.org");
+
+		assert_eq!(Did::handle_identity(b"alice.org"), Some(ALICE));
+		assert_eq!(Did::identity_handle(&ALICE), Some(b"alice.org".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(9));
+		System::assert_last_event(
+			crate::Event::HandleRegistered(b"alice.org".to_vec(), ALICE, 1 + 100).into(),
+		);
+
+		assert_noop!(
+			Did::register_handle(Origin::signed(BOB), BOB, b"alice.org".to_vec()),
+			Error::<Test>::HandleTaken
+		);
+		assert_noop!(
+			Did::register_handle(Origin::signed(ALICE), ALICE, b"alice.net".to_vec()),
+			Error::<Test>::IdentityHasHandle
+		);
+		assert_noop!(
+			Did::register_handle(Origin::signed(BOB), ALICE, b"bob.org".to_vec()),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn handles_have_the_syntax_of_domain_names() {
+	new_test_ext().execute_with(|| {
+		let invalid: [&[u8]; 7] =
+			[b"ab", b"Alice", b"alice_org", b"-alice", b"alice-", b"alice..org", b"alice."];
+		for handle in invalid {
+			assert_noop!(
+				Did::register_handle(Origin::signed(ALICE), ALICE, handle.to_vec()),
+				Error::<Test>::InvalidHandle
+			);
+		}
+		assert_noop!(
+			Did::register_handle(Origin::signed(ALICE), ALICE, vec![b'a'; 33]),
+			Error::<Test>::InvalidHandle
+		);
+
+		register_handle(ALICE, b"a-1.b2");
+	});
+}
+
+#[test]
+fn expired_handles_can_be_registered_again() {
+	new_test_ext().execute_with(|| {
+		register_handle(ALICE, b"alice.org");
+
+		System::set_block_number(1 + 100);
+		assert_eq!(Did::handle_identity(b"alice.org"), None);
+		assert_eq!(Did::identity_handle(&ALICE), None);
+
+		// The deposit of the previous registration goes back to its depositor.
+		register_handle(BOB, b"alice.org");
+		assert_eq!(Did::handle_identity(b"alice.org"), Some(BOB));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), deposit(9));
+
+		// The identity of an expired handle can register another one.
+		register_handle(ALICE, b"alice.net");
+		assert_eq!(Did::identity_handle(&ALICE), Some(b"alice.net".to_vec()));
+	});
+}
+
+#[test]
+fn renew_handle_extends_its_registration() {
+	new_test_ext().execute_with(|| {
+		register_handle(ALICE, b"alice.org");
+		assert_noop!(
+			Did::renew_handle(Origin::signed(BOB), b"alice.org".to_vec()),
+			Error::<Test>::NotOwner
+		);
+
+		// A handle which expired but was not registered again can still be renewed.
+		System::set_block_number(1 + 100);
+		assert_ok!(Did::renew_handle(Origin::signed(ALICE), b"alice.org".to_vec()));
+		System::assert_last_event(
+			crate::Event::HandleRenewed(b"alice.org".to_vec(), 1 + 100 + 100).into(),
+		);
+		System::set_block_number(100 + 100);
+		assert_eq!(Did::handle_identity(b"alice.org"), Some(ALICE));
+		System::set_block_number(1 + 100 + 100);
+		assert_eq!(Did::handle_identity(b"alice.org"), None);
+
+		assert_noop!(
+			Did::renew_handle(Origin::signed(ALICE), b"alice.net".to_vec()),
+			Error::<Test>::HandleNotFound
+		);
+	});
+}
+
+#[test]
+fn transfer_handle_points_it_to_another_identity() {
+	new_test_ext().execute_with(|| {
+		register_handle(ALICE, b"alice.org");
+		register_handle(CHARLIE, b"charlie.org");
+		assert_noop!(
+			Did::transfer_handle(Origin::signed(ALICE), b"alice.org".to_vec(), CHARLIE),
+			Error::<Test>::IdentityHasHandle
+		);
+		assert_noop!(
+			Did::transfer_handle(Origin::signed(BOB), b"alice.org".to_vec(), BOB),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Did::transfer_handle(Origin::signed(ALICE), b"alice.org".to_vec(), BOB));
+		assert_eq!(Did::handle_identity(b"alice.org"), Some(BOB));
+		assert_eq!(Did::identity_handle(&BOB), Some(b"alice.org".to_vec()));
+		assert_eq!(Did::identity_handle(&ALICE), None);
+		// The deposit stays reserved from the depositor.
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(9));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		System::set_block_number(1 + 100);
+		assert_noop!(
+			Did::transfer_handle(Origin::signed(BOB), b"alice.org".to_vec(), ALICE),
+			Error::<Test>::HandleExpired
+		);
+	});
+}
+
+#[test]
+fn release_handle_returns_its_deposit() {
+	new_test_ext().execute_with(|| {
+		register_handle(ALICE, b"alice.org");
+		register_handle(BOB, b"bob.org");
+		assert_noop!(
+			Did::release_handle(Origin::signed(BOB), b"alice.org".to_vec()),
+			Error::<Test>::NotOwner
+		);
+
+		assert_ok!(Did::release_handle(Origin::signed(ALICE), b"alice.org".to_vec()));
+		assert_eq!(Did::handle_identity(b"alice.org"), None);
+		assert_eq!(Did::identity_handle(&ALICE), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		// Anyone releases an expired handle, for its depositor.
+		System::set_block_number(1 + 100);
+		assert_ok!(Did::release_handle(Origin::signed(CHARLIE), b"bob.org".to_vec()));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_noop!(
+			Did::release_handle(Origin::signed(CHARLIE), b"bob.org".to_vec()),
+			Error::<Test>::HandleNotFound
+		);
+	});
+}
+
+#[test]
+fn attributes_lists_expired_attributes_on_request() {
+	new_test_ext().execute_with(|| {
+		add_attribute(ALICE, b"kept");
+		add_attribute(ALICE, b"revoked");
+		add_attribute(BOB, b"other");
+		assert_ok!(Did::revoke_attribute(Origin::signed(ALICE), ALICE, b"revoked".to_vec()));
+
+		let listed = |include_expired| {
+			let mut records: Vec<_> = Did::attributes(&ALICE, include_expired)
+				.into_iter()
+				.map(|record| (record.attribute.name, record.valid))
+				.collect();
+			records.sort();
+			records
+		};
+		assert_eq!(listed(false), vec![(b"kept".to_vec(), true)]);
+		assert_eq!(listed(true), vec![(b"kept".to_vec(), true), (b"revoked".to_vec(), false)]);
+		assert!(Did::attributes(&CHARLIE, true).is_empty());
+
+		// Nothing is valid once the identity is deactivated.
+		assert_ok!(Did::deactivate(Origin::signed(ALICE), ALICE));
+		assert!(Did::attributes(&ALICE, false).is_empty());
+		assert_eq!(listed(true), vec![(b"kept".to_vec(), false), (b"revoked".to_vec(), false)]);
+	});
+}
+
+#[test]
+fn delegates_lists_the_delegates_of_a_type_or_of_every_type() {
+	new_test_ext().execute_with(|| {
+		add_delegate(ALICE, BOB, crate::DELEGATE_VERIFICATION_KEY);
+		add_delegate(ALICE, CHARLIE, crate::DELEGATE_VERIFICATION_KEY);
+		add_delegate(ALICE, DAVE, crate::DELEGATE_ATTRIBUTE_ADMIN);
+		add_delegate(BOB, EVE, crate::DELEGATE_VERIFICATION_KEY);
+		assert_ok!(Did::revoke_delegate(
+			Origin::signed(ALICE),
+			ALICE,
+			crate::DELEGATE_VERIFICATION_KEY.to_vec(),
+			CHARLIE
+		));
+
+		let listed = |delegate_type: Option<&[u8]>| {
+			let mut records: Vec<_> = Did::delegates(&ALICE, delegate_type.map(|t| t.to_vec()))
+				.into_iter()
+				.map(|record| (record.delegate_type, record.delegate, record.valid))
+				.collect();
+			records.sort();
+			records
+		};
+		let verification_key = crate::DELEGATE_VERIFICATION_KEY.to_vec();
+		let attribute_admin = crate::DELEGATE_ATTRIBUTE_ADMIN.to_vec();
+		// Revoked delegates are listed as no longer valid.
+		assert_eq!(
+			listed(Some(crate::DELEGATE_VERIFICATION_KEY)),
+			vec![(verification_key.clone(), BOB, true), (verification_key.clone(), CHARLIE, false)]
+		);
+		let mut all = vec![
+			(verification_key.clone(), BOB, true),
+			(verification_key, CHARLIE, false),
+			(attribute_admin, DAVE, true),
+		];
+		all.sort();
+		assert_eq!(listed(None), all);
+
+		assert!(listed(Some(crate::DELEGATE_SIGNATURE_AUTH)).is_empty());
+		// A type longer than the limit is not an error.
+		assert!(listed(Some(&[0u8; 65])).is_empty());
+	});
+}
+
+/// Stores an attribute of `ALICE` in the format preceding the storage version 1, with its
+/// deposit reserved, and returns its id.
+fn insert_legacy_attribute(name: &[u8], value: &[u8]) -> [u8; 32] {
+	let id = (ALICE, name, 0u64).using_encoded(blake2_256);
+	let attribute = Attribute {
+		name: name.to_vec(),
+		value: value.to_vec(),
+		validity: 100u64,
+		creation: 0u64,
+		nonce: 0,
+	};
+	unhashed::put(&AttributeOf::<Test>::hashed_key_for(ALICE, id), &attribute);
+
+	let amount = deposit((name.len() + value.len()) as u64);
+	assert_ok!(Balances::reserve(&ALICE, amount));
+	crate::DepositOf::<Test>::mutate(ALICE, |deposit| *deposit += amount);
+	id
+}
+
+#[test]
+fn migration_to_v1_bounds_the_attributes() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Did>();
+		insert_legacy_attribute(b"name", b"value");
+		let oversized = insert_legacy_attribute(b"oversized", &[0u8; 129]);
+
+		Did::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Did>(), 1);
+		assert_ok!(Did::valid_attribute(&ALICE, b"name", b"value"));
+		assert!(!AttributeOf::<Test>::contains_key(ALICE, oversized));
+	});
+}
+
+#[test]
+fn migration_to_v1_releases_the_deposit_of_dropped_attributes() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Did>();
+		insert_legacy_attribute(b"name", b"value");
+		insert_legacy_attribute(b"oversized", &[0u8; 129]);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(9) + deposit(138));
+
+		Did::on_runtime_upgrade();
+
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(9));
+		assert_eq!(Did::deposit_of(ALICE), deposit(9));
+	});
+}
+
+#[test]
+fn migration_runs_once() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<Did>();
+		let id = insert_legacy_attribute(b"oversized", &[0u8; 129]);
+
+		Did::on_runtime_upgrade();
+
+		// Nothing is translated at the current version.
+		assert!(unhashed::exists(&AttributeOf::<Test>::hashed_key_for(ALICE, id)));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit(138));
+	});
+}
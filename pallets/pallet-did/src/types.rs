@@ -1,23 +1,49 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::sp_std::vec::Vec;
 use scale_info::TypeInfo;
-use sp_core::RuntimeDebug;
+use sp_core::{ecdsa, RuntimeDebug, H160};
 
 /// Attributes or properties that make an identity.
+/// Name and value are bounded byte vectors in storage.
 #[derive(
-	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	Default,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
 )]
-pub struct Attribute<BlockNumber, Moment> {
-	pub name: Vec<u8>,
-	pub value: Vec<u8>,
+pub struct Attribute<BlockNumber, Moment, Name, Value> {
+	pub name: Name,
+	pub value: Value,
 	pub validity: BlockNumber,
 	pub creation: Moment,
 	pub nonce: u64,
 }
 
-pub type AttributedId<BlockNumber, Moment> = (Attribute<BlockNumber, Moment>, [u8; 32]);
+pub type AttributedId<BlockNumber, Moment, Name, Value> =
+	(Attribute<BlockNumber, Moment, Name, Value>, [u8; 32]);
 
-/// Off-chain signed transaction.
+/// Signature of an off-chain transaction by its `signer`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum DidSignature<Signature> {
+	/// Signature of the native `signer` account, checked by `Config::Signature`.
+	Native(Signature),
+	/// secp256k1 signature of the EIP-191 personal message of the payload, as `personal_sign`.
+	/// The recovered Ethereum address has to map to `signer`.
+	Eip191(ecdsa::Signature),
+	/// secp256k1 signature of the EIP-712 typed data of the transaction, as
+	/// `eth_signTypedData_v4`. Each kind of transaction has its own type, see `EIP712_*_TYPE`.
+	/// The recovered Ethereum address has to map to `signer`.
+	Eip712(ecdsa::Signature),
+}
+
+/// Off-chain signed transaction authorizing `signer` to set an attribute of `identity`.
 #[derive(
 	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
 )]
@@ -28,4 +54,384 @@ pub struct AttributeTransaction<Signature, AccountId> {
 	pub validity: u32,
 	pub signer: AccountId,
 	pub identity: AccountId,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Off-chain signed transaction authorizing `signer` to transfer `identity` to `new_owner`.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+)]
+pub struct ChangeOwnerTransaction<Signature, AccountId> {
+	pub signature: Signature,
+	pub signer: AccountId,
+	pub identity: AccountId,
+	pub new_owner: AccountId,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Off-chain signed transaction authorizing `signer` to add `delegate` to `identity`.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+)]
+pub struct AddDelegateTransaction<Signature, AccountId> {
+	pub signature: Signature,
+	pub signer: AccountId,
+	pub identity: AccountId,
+	pub delegate: AccountId,
+	pub delegate_type: Vec<u8>,
+	/// Number of blocks the delegate is valid for, forever if none.
+	pub valid_for: Option<u32>,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Off-chain signed transaction authorizing `signer` to revoke `delegate` from `identity`.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+)]
+pub struct RevokeDelegateTransaction<Signature, AccountId> {
+	pub signature: Signature,
+	pub signer: AccountId,
+	pub identity: AccountId,
+	pub delegate_type: Vec<u8>,
+	pub delegate: AccountId,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Off-chain signed transaction authorizing `signer` to revoke the attribute `name` of `identity`.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+)]
+pub struct RevokeAttributeTransaction<Signature, AccountId> {
+	pub signature: Signature,
+	pub signer: AccountId,
+	pub identity: AccountId,
+	pub name: Vec<u8>,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Off-chain signed transaction authorizing `signer` to delete the attribute `name` of `identity`.
+#[derive(
+	PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo,
+)]
+pub struct DeleteAttributeTransaction<Signature, AccountId> {
+	pub signature: Signature,
+	pub signer: AccountId,
+	pub identity: AccountId,
+	pub name: Vec<u8>,
+	/// Signing nonce of the identity, must match the one stored on chain.
+	pub nonce: u64,
+	/// Last block number at which the transaction can be executed.
+	pub expiry: u32,
+}
+
+/// Change to a DID document, applied along others by `update_document`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum DidOperation<AccountId, BlockNumber> {
+	/// Adds a delegate valid for a number of blocks, forever if none.
+	AddDelegate { delegate_type: Vec<u8>, delegate: AccountId, valid_for: Option<BlockNumber> },
+	/// Revokes a delegate, expiring it at the current block.
+	RevokeDelegate { delegate_type: Vec<u8>, delegate: AccountId },
+	/// Adds an attribute valid for a number of blocks, forever if none.
+	AddAttribute { name: Vec<u8>, value: Vec<u8>, valid_for: Option<BlockNumber> },
+	/// Revokes an attribute, expiring it at the current block.
+	RevokeAttribute { name: Vec<u8> },
+	/// Deletes an attribute and releases its deposit.
+	DeleteAttribute { name: Vec<u8> },
+}
+
+/// Algorithm of the public key of a verification method.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeyType {
+	Ed25519,
+	Sr25519,
+	EcdsaSecp256k1,
+	X25519,
+}
+
+impl KeyType {
+	/// Parses the algorithm segment of a delegate type or public key attribute name.
+	pub fn from_name(name: &[u8]) -> Option<Self> {
+		match name {
+			b"Ed25519" => Some(Self::Ed25519),
+			b"Sr25519" => Some(Self::Sr25519),
+			b"Secp256k1" => Some(Self::EcdsaSecp256k1),
+			b"X25519" => Some(Self::X25519),
+			_ => None,
+		}
+	}
+
+	/// Verification method type of the key in a DID document.
+	pub fn method_type(&self) -> &'static [u8] {
+		match self {
+			Self::Ed25519 => b"Ed25519VerificationKey2018",
+			Self::Sr25519 => b"Sr25519VerificationKey2020",
+			Self::EcdsaSecp256k1 => b"EcdsaSecp256k1VerificationKey2019",
+			Self::X25519 => b"X25519KeyAgreementKey2019",
+		}
+	}
+
+	/// Whether `key` is well-formed raw public key material of this type.
+	/// Secp256k1 keys are either compressed or uncompressed SEC1 points.
+	pub fn valid_key(&self, key: &[u8]) -> bool {
+		match self {
+			Self::Ed25519 | Self::Sr25519 | Self::X25519 => key.len() == 32,
+			Self::EcdsaSecp256k1 => match key.first() {
+				Some(0x02) | Some(0x03) => key.len() == 33,
+				Some(0x04) => key.len() == 65,
+				_ => false,
+			},
+		}
+	}
+
+	/// Whether the key can serve `purpose`, X25519 keys only agree keys and the others only sign.
+	pub fn supports(&self, purpose: KeyPurpose) -> bool {
+		(*self == Self::X25519) == (purpose == KeyPurpose::KeyAgreement)
+	}
+}
+
+/// Verification relationship of a method in a DID document.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeyPurpose {
+	/// Listed as both authentication and assertion method, ERC-1056 `sigAuth`.
+	Authentication,
+	/// Listed as assertion method, ERC-1056 `veriKey`.
+	AssertionMethod,
+	/// Listed as key agreement, ERC-1056 `enc`.
+	KeyAgreement,
+}
+
+impl KeyPurpose {
+	/// Parses the purpose segment of a delegate type or public key attribute name.
+	pub fn from_name(name: &[u8]) -> Option<Self> {
+		match name {
+			b"sigAuth" => Some(Self::Authentication),
+			b"veriKey" => Some(Self::AssertionMethod),
+			b"enc" => Some(Self::KeyAgreement),
+			_ => None,
+		}
+	}
+
+	/// Whether keys of this purpose may sign assertions such as credentials.
+	pub fn asserts(&self) -> bool {
+		matches!(self, Self::Authentication | Self::AssertionMethod)
+	}
+}
+
+/// Kind of a verification method, named `<algorithm>/<purpose>` such as `Ed25519/veriKey`.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VerificationMethodKind {
+	pub key_type: KeyType,
+	pub purpose: KeyPurpose,
+}
+
+impl VerificationMethodKind {
+	/// Parses a `<algorithm>/<purpose>` name, none if unknown or if the key type cannot
+	/// serve the purpose.
+	pub fn from_name(name: &[u8]) -> Option<Self> {
+		let separator = name.iter().position(|c| *c == b'/')?;
+		let key_type = KeyType::from_name(&name[..separator])?;
+		let purpose = KeyPurpose::from_name(&name[separator + 1..])?;
+		key_type.supports(purpose).then(|| Self { key_type, purpose })
+	}
+}
+
+/// Public key material of a verification method in a resolved DID document.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum PublicKey<AccountId> {
+	/// The key is the account itself (owner or delegate).
+	BlockchainAccount(AccountId),
+	/// Raw key bytes to be rendered as hex.
+	Hex(Vec<u8>),
+	/// Raw key bytes to be rendered as base64.
+	Base64(Vec<u8>),
+	/// Raw key bytes to be rendered as base58.
+	Base58(Vec<u8>),
+	/// Address of the Ethereum account the account of the method is derived from, the key
+	/// being recovered from its signatures.
+	EthereumAddress(H160),
+}
+
+/// Verification method of a resolved DID document.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct VerificationMethod<AccountId> {
+	/// Fragment identifying the method inside the document, e.g. `delegate-1`.
+	pub id: Vec<u8>,
+	pub method_type: Vec<u8>,
+	pub controller: AccountId,
+	pub public_key: PublicKey<AccountId>,
+}
+
+/// Service endpoint of a resolved DID document.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Service {
+	/// Fragment identifying the service inside the document, e.g. `service-1`.
+	pub id: Vec<u8>,
+	pub service_type: Vec<u8>,
+	pub endpoint: Vec<u8>,
+}
+
+/// W3C DID document of an identity, folded from its owner, delegates and attributes.
+/// Verification relationships reference the fragments of `verification_method`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DidDocument<AccountId, BlockNumber> {
+	pub id: AccountId,
+	pub controller: AccountId,
+	pub verification_method: Vec<VerificationMethod<AccountId>>,
+	pub authentication: Vec<Vec<u8>>,
+	pub assertion_method: Vec<Vec<u8>>,
+	pub key_agreement: Vec<Vec<u8>>,
+	pub service: Vec<Service>,
+	/// Block number of the latest change of the identity, if any.
+	pub updated: Option<BlockNumber>,
+	/// Number of changes of the identity, its document version.
+	pub version_id: u64,
+	/// Whether the identity is deactivated, its document then lists no method nor service.
+	pub deactivated: bool,
+	/// Registered handle of the identity, listed in `alsoKnownAs`.
+	pub handle: Option<Vec<u8>>,
+	/// Ethereum address the identity is keyed by, if known, making it a `did:ethr` identity.
+	pub ethereum_address: Option<H160>,
+}
+
+/// Attribute of an identity with its validity status at the queried block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AttributeRecord<BlockNumber, Moment> {
+	pub attribute: Attribute<BlockNumber, Moment, Vec<u8>, Vec<u8>>,
+	/// Whether the attribute is still valid at the queried block.
+	pub valid: bool,
+}
+
+/// Delegate of an identity with its validity status at the queried block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct DelegateRecord<AccountId, BlockNumber> {
+	pub delegate_type: Vec<u8>,
+	pub delegate: AccountId,
+	/// Last block number, exclusive, at which the delegate is valid.
+	pub validity: BlockNumber,
+	/// Whether the delegate is still valid at the queried block.
+	pub valid: bool,
+}
+
+/// Position of the sweep pruning expired delegates and attributes, persisted across blocks.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PruningCursor<RawKey> {
+	/// Sweeping delegates after the given raw storage key, from the start if none.
+	Delegates(Option<RawKey>),
+	/// Sweeping attributes after the given raw storage key, from the start if none.
+	Attributes(Option<RawKey>),
+}
+
+impl<RawKey> Default for PruningCursor<RawKey> {
+	fn default() -> Self {
+		PruningCursor::Delegates(None)
+	}
+}
+
+/// Guardians able to recover the ownership of an identity whose owner key is lost.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RecoveryConfig<Guardians, BlockNumber> {
+	pub guardians: Guardians,
+	/// Number of guardian approvals needed to complete a recovery.
+	pub threshold: u32,
+	/// Number of blocks the owner has to cancel a recovery once opened.
+	pub delay: BlockNumber,
+}
+
+/// Recovery opened by a guardian, moving the ownership of an identity to `new_owner`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ActiveRecovery<AccountId, Guardians, BlockNumber> {
+	pub new_owner: AccountId,
+	/// Block number at which the recovery was opened.
+	pub opened: BlockNumber,
+	/// Guardians who approved the recovery, the initiator included.
+	pub approvals: Guardians,
+}
+
+/// Accounts jointly controlling an identity, `threshold` of them have to approve any
+/// owner operation.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ControllerSet<Controllers> {
+	pub controllers: Controllers,
+	/// Number of controller approvals needed to execute a proposal.
+	pub threshold: u32,
+}
+
+/// Call proposed to the controllers of an identity, identified by its hash.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingProposal<AccountId, Balance, Controllers, BlockNumber> {
+	pub proposer: AccountId,
+	/// Amount reserved from the proposer until the proposal is executed or cancelled.
+	pub deposit: Balance,
+	/// Controllers who approved the proposal, the proposer included.
+	pub approvals: Controllers,
+	/// Block number at which the proposal was created.
+	pub created: BlockNumber,
+}
+
+/// Origin of the calls dispatched on behalf of an identity.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RawOrigin<AccountId> {
+	/// The identity acting as its own owner, through a proposal approved by its controllers.
+	Identity(AccountId),
+}
+
+/// Account a call is made by, as authorized by `Pallet::authorize`.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub enum Caller<AccountId> {
+	/// A signed account, acting as the owner or a delegate of an identity.
+	Account(AccountId),
+	/// An identity acting as its own owner, see `RawOrigin::Identity`.
+	Identity(AccountId),
+}
+
+impl<AccountId> Caller<AccountId> {
+	/// The account recorded as the author of the changes of the caller.
+	pub fn account(&self) -> &AccountId {
+		match self {
+			Self::Account(account) | Self::Identity(account) => account,
+		}
+	}
+}
+
+/// Version of the DID document of an identity, recorded on each of its changes.
+/// Like the `changed` mapping of ERC-1056, `previous_change` points to the block of the change
+/// before, so the history can be walked back through the historical state of the chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DocumentVersion<BlockNumber, Moment> {
+	/// Number of changes of the identity, the document without any change being version 0.
+	pub version_id: u64,
+	/// Block number of the change.
+	pub block: BlockNumber,
+	/// Timestamp of the change.
+	pub time: Moment,
+	/// Block number of the previous change, none for the first one.
+	pub previous_change: Option<BlockNumber>,
+}
+
+/// Registration of a handle, a unique human-readable alias of an identity such as `alice.org`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct HandleRecord<AccountId, Balance, BlockNumber> {
+	/// Identity the handle points to.
+	pub identity: AccountId,
+	/// Account which registered the handle, its deposit is returned to it on release.
+	pub depositor: AccountId,
+	/// Amount reserved from the depositor.
+	pub deposit: Balance,
+	/// Last block number, exclusive, at which the handle is registered unless renewed.
+	pub expiry: BlockNumber,
 }
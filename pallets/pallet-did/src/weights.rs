@@ -0,0 +1,982 @@
+//! Weights for pallet_did
+//!
+//! These weights were not measured. They are conservative estimates: round execution times
+//! and the storage reads and writes of the most expensive path of each extrinsic, counting
+//! one `DelegateOf` read for the delegate types permitted by `AttributeAdmin`. Replace this
+//! file with the output of the benchmarks on reference hardware:
+//!
+//! ./target/release/node-template benchmark pallet --chain=dev --steps=50 --repeat=20
+//!   --pallet=pallet_did --extrinsic=* --execution=wasm --wasm-execution=compiled
+//!   --heap-pages=4096 --output=./pallets/pallet-did/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_did.
+pub trait WeightInfo {
+	fn change_owner() -> Weight;
+	fn add_delegate() -> Weight;
+	fn revoke_delegate() -> Weight;
+	fn add_attribute(v: u32, ) -> Weight;
+	fn revoke_attribute() -> Weight;
+	fn delete_attribute() -> Weight;
+	fn execute(v: u32, ) -> Weight;
+	fn change_owner_signed() -> Weight;
+	fn add_delegate_signed() -> Weight;
+	fn revoke_delegate_signed() -> Weight;
+	fn revoke_attribute_signed() -> Weight;
+	fn delete_attribute_signed() -> Weight;
+	fn prune_delegate() -> Weight;
+	fn prune_attribute() -> Weight;
+	fn set_guardians(g: u32, ) -> Weight;
+	fn remove_guardians() -> Weight;
+	fn initiate_recovery() -> Weight;
+	fn approve_recovery(g: u32, ) -> Weight;
+	fn cancel_recovery() -> Weight;
+	fn complete_recovery() -> Weight;
+	fn set_controllers(c: u32, ) -> Weight;
+	fn remove_controllers() -> Weight;
+	fn propose() -> Weight;
+	fn approve_proposal(c: u32, ) -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn execute_proposal(c: u32, ) -> Weight;
+	fn deactivate() -> Weight;
+	fn link_ethereum_account() -> Weight;
+	fn update_document(n: u32, b: u32, ) -> Weight;
+	fn register_handle() -> Weight;
+	fn renew_handle() -> Weight;
+	fn transfer_handle() -> Weight;
+	fn release_handle() -> Weight;
+	fn handle_identity() -> Weight;
+}
+
+/// Weights for pallet_did using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn change_owner() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_delegate() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_delegate() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_attribute(v: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_attribute() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn delete_attribute() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn execute(v: u32, ) -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:0)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn change_owner_signed() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_delegate_signed() -> Weight {
+		(105_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_delegate_signed() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_attribute_signed() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn delete_attribute_signed() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn prune_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn prune_attribute() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:0)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn set_guardians(g: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:0)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn remove_guardians() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn initiate_recovery() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn approve_recovery(g: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn cancel_recovery() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn complete_recovery() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn set_controllers(c: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn remove_controllers() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn propose() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	fn approve_proposal(c: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_proposal() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn execute_proposal(c: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:1)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:0 w:1)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PalletDid HandleByIdentity (r:1 w:1)
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletAbac PurgeQueue (r:0 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn deactivate() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: PalletDid EthereumLinkOf (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:0 w:1)
+	fn link_ethereum_account() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	// For each of the `n` operations:
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	fn update_document(n: u32, b: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid HandleOf (r:2 w:2)
+	// Storage: PalletDid HandleByIdentity (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn register_handle() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	fn renew_handle() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:2 w:2)
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid HandleByIdentity (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:2 w:2)
+	// Storage: PalletDid DocumentVersionOf (r:2 w:2)
+	fn transfer_handle() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PalletDid HandleByIdentity (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn release_handle() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:0)
+	fn handle_identity() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn change_owner() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_delegate() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_delegate() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_attribute(v: u32, ) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_attribute() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn delete_attribute() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn execute(v: u32, ) -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:0)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn change_owner_signed() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn add_delegate_signed() -> Weight {
+		(105_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_delegate_signed() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn revoke_attribute_signed() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:1 w:1)
+	// Storage: PalletDid SigningNonce (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid AttributeNonce (r:1 w:0)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn delete_attribute_signed() -> Weight {
+		(110_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn prune_delegate() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn prune_attribute() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:0)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn set_guardians(g: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:0)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn remove_guardians() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn initiate_recovery() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn approve_recovery(g: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(g as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	fn cancel_recovery() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:1 w:1)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn complete_recovery() -> Weight {
+		(65_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn set_controllers(c: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn remove_controllers() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn propose() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	fn approve_proposal(c: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((200_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_proposal() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid ProposalOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn execute_proposal(c: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((500_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:1)
+	// Storage: PalletDid ControllersOf (r:1 w:1)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid ActiveRecoveryOf (r:0 w:1)
+	// Storage: PalletDid RecoveryConfigOf (r:1 w:1)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: PalletDid HandleByIdentity (r:1 w:1)
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletAbac PurgeQueue (r:0 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn deactivate() -> Weight {
+		(80_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	// Storage: PalletDid EthereumLinkOf (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: System BlockHash (r:1 w:0)
+	// Storage: PalletDid EthereumAddressOf (r:0 w:1)
+	fn link_ethereum_account() -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid DepositOf (r:1 w:1)
+	// Storage: PalletDid EthereumLinkOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	// For each of the `n` operations:
+	// Storage: PalletDid DelegateOf (r:1 w:1)
+	// Storage: PalletDid AttributeNonce (r:1 w:1)
+	// Storage: PalletDid AttributeOf (r:1 w:1)
+	fn update_document(n: u32, b: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((40_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid HandleOf (r:2 w:2)
+	// Storage: PalletDid HandleByIdentity (r:2 w:2)
+	// Storage: System Account (r:3 w:3)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn register_handle() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	fn renew_handle() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:2 w:2)
+	// Storage: PalletDid DeactivatedAt (r:2 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletDid HandleByIdentity (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:2 w:2)
+	// Storage: PalletDid DocumentVersionOf (r:2 w:2)
+	fn transfer_handle() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:1)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PalletDid HandleByIdentity (r:1 w:1)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: PalletDid UpdatedBy (r:1 w:1)
+	// Storage: PalletDid DocumentVersionOf (r:1 w:1)
+	fn release_handle() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PalletDid HandleOf (r:1 w:0)
+	fn handle_identity() -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+}
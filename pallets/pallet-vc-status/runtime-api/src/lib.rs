@@ -0,0 +1,30 @@
+//! Runtime API to fetch the credential status lists of issuers.
+//!
+//! A verifier fetches the whole status list of an issuer with a single call,
+//! then checks the bit of the credential at its status list index.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use pallet_vc_status::types::{CredentialRecord, StatusPurpose};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// The API to read the credential status registry of `pallet_vc_status`.
+	pub trait VcStatusApi<AccountId, Hash, BlockNumber> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the StatusList2021 bitstring of an issuer for a purpose, uncompressed.
+		/// The bit of the credential at index `i` is the `i % 8`-th most significant bit
+		/// of byte `i / 8`, set if the credential is revoked or suspended.
+		fn status_list(issuer: AccountId, purpose: StatusPurpose) -> Vec<u8>;
+
+		/// Returns the record of a credential anchored by an issuer.
+		fn credential(
+			issuer: AccountId,
+			credential: Hash,
+		) -> Option<CredentialRecord<AccountId, BlockNumber>>;
+	}
+}
@@ -0,0 +1,108 @@
+//! Benchmarking setup for pallet-vc-status
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as VcStatus;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
+use sp_std::vec;
+
+const SEED: u32 = 0;
+
+/// Issuer identity able to pay the deposits of the credentials it anchors.
+fn funded_issuer<T: Config>() -> T::AccountId {
+	let issuer: T::AccountId = account("issuer", 0, SEED);
+	<T as pallet_did::Config>::Currency::make_free_balance_be(
+		&issuer,
+		BalanceOf::<T>::max_value() / 2u32.into(),
+	);
+	issuer
+}
+
+/// Hash of the credential anchored by the benchmarks.
+fn credential<T: Config>() -> T::Hash {
+	T::Hashing::hash(b"credential")
+}
+
+/// Anchors a credential at the last index of the issuer status lists, which are filled
+/// up to their full length so that changing its status reads and writes the most bytes.
+fn anchor_last<T: Config>(issuer: &T::AccountId) -> Result<T::Hash, &'static str> {
+	let full_list: StatusList<T> = vec![0xff; StatusListLength::<T>::get() as usize - 1]
+		.try_into()
+		.map_err(|_| "status list too long")?;
+	<StatusListOf<T>>::insert(issuer, StatusPurpose::Revocation, &full_list);
+	<StatusListOf<T>>::insert(issuer, StatusPurpose::Suspension, &full_list);
+	<CredentialCount<T>>::insert(issuer, T::MaxCredentials::get() - 1);
+	let credential = credential::<T>();
+	VcStatus::<T>::anchor_credential(
+		RawOrigin::Signed(issuer.clone()).into(),
+		issuer.clone(),
+		credential,
+	)?;
+	Ok(credential)
+}
+
+benchmarks! {
+	anchor_credential {
+		let issuer = funded_issuer::<T>();
+		let credential = credential::<T>();
+	}: _(RawOrigin::Signed(issuer.clone()), issuer.clone(), credential)
+	verify {
+		assert!(VcStatus::<T>::credential_of(&issuer, &credential).is_some());
+	}
+
+	// A suspended credential has its bit cleared from the suspension list as well.
+	revoke_credential {
+		let issuer = funded_issuer::<T>();
+		let credential = anchor_last::<T>(&issuer)?;
+		VcStatus::<T>::suspend_credential(
+			RawOrigin::Signed(issuer.clone()).into(),
+			issuer.clone(),
+			credential,
+		)?;
+	}: _(RawOrigin::Signed(issuer.clone()), issuer.clone(), credential)
+	verify {
+		let record = VcStatus::<T>::credential_of(&issuer, &credential).unwrap();
+		assert_eq!(record.status, CredentialStatus::Revoked);
+	}
+
+	suspend_credential {
+		let issuer = funded_issuer::<T>();
+		let credential = anchor_last::<T>(&issuer)?;
+	}: _(RawOrigin::Signed(issuer.clone()), issuer.clone(), credential)
+	verify {
+		let record = VcStatus::<T>::credential_of(&issuer, &credential).unwrap();
+		assert_eq!(record.status, CredentialStatus::Suspended);
+	}
+
+	reinstate_credential {
+		let issuer = funded_issuer::<T>();
+		let credential = anchor_last::<T>(&issuer)?;
+		VcStatus::<T>::suspend_credential(
+			RawOrigin::Signed(issuer.clone()).into(),
+			issuer.clone(),
+			credential,
+		)?;
+	}: _(RawOrigin::Signed(issuer.clone()), issuer.clone(), credential)
+	verify {
+		let record = VcStatus::<T>::credential_of(&issuer, &credential).unwrap();
+		assert_eq!(record.status, CredentialStatus::Active);
+	}
+
+	remove_credential {
+		let issuer = funded_issuer::<T>();
+		let credential = anchor_last::<T>(&issuer)?;
+		VcStatus::<T>::revoke_credential(
+			RawOrigin::Signed(issuer.clone()).into(),
+			issuer.clone(),
+			credential,
+		)?;
+	}: _(RawOrigin::Signed(issuer.clone()), issuer.clone(), credential)
+	verify {
+		assert!(VcStatus::<T>::credential_of(&issuer, &credential).is_none());
+	}
+
+	impl_benchmark_test_suite!(VcStatus, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,347 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+//! Registry of the status of W3C Verifiable Credentials issued off-chain by
+//! identities of `pallet_did`.
+//!
+//! An issuer, or any of its "CredentialIssuer" delegates, anchors the hash of a credential
+//! which gets the next index of the issuer status lists. Revoking or suspending the credential
+//! sets its bit in the matching StatusList2021 bitstring, which verifiers fetch at once.
+//! A deposit is reserved from whoever anchors a credential until the revoked credential
+//! is removed.
+pub mod types;
+use crate::types::*;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub use pallet::*;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	pallet_prelude::*,
+	sp_std::vec::Vec,
+	traits::{Currency, ReservableCurrency},
+};
+use frame_system::pallet_prelude::*;
+use pallet_did::BalanceOf;
+use sp_std::marker::PhantomData;
+
+/// Delegate type allowed to manage the credentials of an issuer identity, to be registered in
+/// the `PermissionDelegateTypes` of `pallet_did`.
+pub const DELEGATE_CREDENTIAL_ISSUER: &[u8] = b"CredentialIssuer";
+
+pub type CredentialRecordFor<T> =
+	CredentialRecord<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+pub type StatusList<T> = BoundedVec<u8, StatusListLength<T>>;
+
+/// Length in bytes of a full status list, one bit per credential.
+pub struct StatusListLength<T>(PhantomData<T>);
+
+impl<T: Config> Get<u32> for StatusListLength<T> {
+	fn get() -> u32 {
+		(T::MaxCredentials::get() + 7) / 8
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_did::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The maximum number of credentials an issuer can anchor, the length of its
+		/// status lists in bits. StatusList2021 recommends at least 131072.
+		#[pallet::constant]
+		type MaxCredentials: Get<u32>;
+		/// The amount reserved from the anchoring account for each credential anchored.
+		#[pallet::constant]
+		type AnchorDeposit: Get<BalanceOf<Self>>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn credential_of)]
+	pub(super) type CredentialOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		CredentialRecordFor<T>,
+		OptionQuery,
+	>;
+
+	/// Deposit reserved from the account which anchored a credential.
+	#[pallet::storage]
+	#[pallet::getter(fn anchor_deposit_of)]
+	pub(super) type AnchorDepositOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Number of credentials anchored by an issuer, the index of the next one.
+	#[pallet::storage]
+	#[pallet::getter(fn credential_count)]
+	pub(super) type CredentialCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Status list bitstrings of an issuer, only as long as needed by its last set bit.
+	#[pallet::storage]
+	#[pallet::getter(fn status_list_of)]
+	pub(super) type StatusListOf<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		StatusPurpose,
+		StatusList<T>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// \[issuer, credential, index, who\]
+		CredentialAnchored(T::AccountId, T::Hash, u32, T::AccountId),
+		/// \[issuer, credential, who\]
+		CredentialRevoked(T::AccountId, T::Hash, T::AccountId),
+		/// \[issuer, credential, who\]
+		CredentialSuspended(T::AccountId, T::Hash, T::AccountId),
+		/// \[issuer, credential, who\]
+		CredentialReinstated(T::AccountId, T::Hash, T::AccountId),
+		/// \[issuer, credential, who\]
+		CredentialRemoved(T::AccountId, T::Hash, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is neither the issuer owner nor one of its "CredentialIssuer" delegates.
+		NotIssuer,
+		/// The credential is already anchored by the issuer.
+		AlreadyAnchored,
+		/// The credential is not anchored by the issuer.
+		UnknownCredential,
+		/// The issuer has anchored `MaxCredentials` credentials already.
+		StatusListFull,
+		/// The credential is revoked for good.
+		CredentialRevoked,
+		/// The credential is suspended already.
+		AlreadySuspended,
+		/// The credential is not suspended.
+		NotSuspended,
+		/// The credential is not revoked, so it cannot be removed.
+		NotRevoked,
+		/// The anchoring account cannot pay the deposit of the credential.
+		InsufficientDeposit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Anchors the hash of a credential issued by an identity.
+		/// The credential gets the next index of the issuer status lists
+		/// and `AnchorDeposit` is reserved from the caller until it is removed.
+		#[pallet::weight(<T as Config>::WeightInfo::anchor_credential())]
+		pub fn anchor_credential(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			credential: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_issuer(&issuer, &who)?;
+			ensure!(
+				!<CredentialOf<T>>::contains_key(&issuer, &credential),
+				Error::<T>::AlreadyAnchored
+			);
+
+			let index = Self::credential_count(&issuer);
+			ensure!(index < T::MaxCredentials::get(), Error::<T>::StatusListFull);
+
+			let deposit = T::AnchorDeposit::get();
+			<T as pallet_did::Config>::Currency::reserve(&who, deposit)
+				.map_err(|_| Error::<T>::InsufficientDeposit)?;
+
+			let now_block_number = <frame_system::Pallet<T>>::block_number();
+			let record = CredentialRecord {
+				index,
+				status: CredentialStatus::Active,
+				anchored_by: who.clone(),
+				anchored: now_block_number,
+				updated: now_block_number,
+			};
+			<CredentialOf<T>>::insert(&issuer, &credential, record);
+			<AnchorDepositOf<T>>::insert(&issuer, &credential, deposit);
+			<CredentialCount<T>>::insert(&issuer, index + 1);
+
+			Self::deposit_event(Event::CredentialAnchored(issuer, credential, index, who));
+			Ok(())
+		}
+
+		/// Revokes a credential for good by setting its bit in the revocation list.
+		/// The bit of a suspended credential is cleared from the suspension list.
+		#[pallet::weight(<T as Config>::WeightInfo::revoke_credential())]
+		pub fn revoke_credential(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			credential: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_issuer(&issuer, &who)?;
+
+			let mut suspended = false;
+			let index = Self::update_status(&issuer, &credential, |status| match status {
+				CredentialStatus::Revoked => Err(Error::<T>::CredentialRevoked),
+				status => {
+					suspended = status == CredentialStatus::Suspended;
+					Ok(CredentialStatus::Revoked)
+				},
+			})?;
+			Self::set_status_bit(&issuer, StatusPurpose::Revocation, index, true)?;
+			if suspended {
+				Self::set_status_bit(&issuer, StatusPurpose::Suspension, index, false)?;
+			}
+
+			Self::deposit_event(Event::CredentialRevoked(issuer, credential, who));
+			Ok(())
+		}
+
+		/// Suspends a credential by setting its bit in the suspension list.
+		#[pallet::weight(<T as Config>::WeightInfo::suspend_credential())]
+		pub fn suspend_credential(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			credential: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_issuer(&issuer, &who)?;
+
+			let index = Self::update_status(&issuer, &credential, |status| match status {
+				CredentialStatus::Active => Ok(CredentialStatus::Suspended),
+				CredentialStatus::Suspended => Err(Error::<T>::AlreadySuspended),
+				CredentialStatus::Revoked => Err(Error::<T>::CredentialRevoked),
+			})?;
+			Self::set_status_bit(&issuer, StatusPurpose::Suspension, index, true)?;
+
+			Self::deposit_event(Event::CredentialSuspended(issuer, credential, who));
+			Ok(())
+		}
+
+		/// Reinstates a suspended credential by clearing its bit in the suspension list.
+		#[pallet::weight(<T as Config>::WeightInfo::reinstate_credential())]
+		pub fn reinstate_credential(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			credential: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_issuer(&issuer, &who)?;
+
+			let index = Self::update_status(&issuer, &credential, |status| match status {
+				CredentialStatus::Suspended => Ok(CredentialStatus::Active),
+				CredentialStatus::Active => Err(Error::<T>::NotSuspended),
+				CredentialStatus::Revoked => Err(Error::<T>::CredentialRevoked),
+			})?;
+			Self::set_status_bit(&issuer, StatusPurpose::Suspension, index, false)?;
+
+			Self::deposit_event(Event::CredentialReinstated(issuer, credential, who));
+			Ok(())
+		}
+
+		/// Removes the record of a revoked credential and releases its deposit.
+		/// Its index is not reused and stays set in the revocation list.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_credential())]
+		pub fn remove_credential(
+			origin: OriginFor<T>,
+			issuer: T::AccountId,
+			credential: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_issuer(&issuer, &who)?;
+
+			let record =
+				Self::credential_of(&issuer, &credential).ok_or(Error::<T>::UnknownCredential)?;
+			ensure!(record.status == CredentialStatus::Revoked, Error::<T>::NotRevoked);
+
+			<CredentialOf<T>>::remove(&issuer, &credential);
+			let deposit = <AnchorDepositOf<T>>::take(&issuer, &credential);
+			<T as pallet_did::Config>::Currency::unreserve(&record.anchored_by, deposit);
+
+			Self::deposit_event(Event::CredentialRemoved(issuer, credential, who));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Validates that `who` may manage the credentials of an issuer,
+	/// either as its owner or as a valid "CredentialIssuer" delegate.
+	pub fn ensure_issuer(issuer: &T::AccountId, who: &T::AccountId) -> DispatchResult {
+		pallet_did::Pallet::<T>::valid_delegate(issuer, DELEGATE_CREDENTIAL_ISSUER, who)
+			.map_err(|_| Error::<T>::NotIssuer.into())
+	}
+
+	/// Moves a credential to the status returned by `transition`
+	/// and returns its status list index.
+	fn update_status(
+		issuer: &T::AccountId,
+		credential: &T::Hash,
+		transition: impl FnOnce(CredentialStatus) -> Result<CredentialStatus, Error<T>>,
+	) -> Result<u32, DispatchError> {
+		<CredentialOf<T>>::try_mutate(issuer, credential, |record| {
+			let record = record.as_mut().ok_or(Error::<T>::UnknownCredential)?;
+			record.status = transition(record.status)?;
+			record.updated = <frame_system::Pallet<T>>::block_number();
+			Ok(record.index)
+		})
+	}
+
+	/// Sets or clears the bit at `index` of a status list, growing the list if needed.
+	/// Bits are ordered from the most significant one of the first byte, as in StatusList2021.
+	fn set_status_bit(
+		issuer: &T::AccountId,
+		purpose: StatusPurpose,
+		index: u32,
+		value: bool,
+	) -> DispatchResult {
+		<StatusListOf<T>>::try_mutate(issuer, purpose, |list| {
+			let byte = (index / 8) as usize;
+			let mask = 0x80u8 >> (index % 8);
+			let mut bits = sp_std::mem::take(list).into_inner();
+			if bits.len() <= byte {
+				bits.resize(byte + 1, 0);
+			}
+			if value {
+				bits[byte] |= mask;
+			} else {
+				bits[byte] &= !mask;
+			}
+			*list = bits.try_into().map_err(|_| Error::<T>::StatusListFull)?;
+			Ok(())
+		})
+	}
+
+	/// Returns the full status list of an issuer for a purpose, padded to `MaxCredentials` bits.
+	pub fn status_list(issuer: &T::AccountId, purpose: StatusPurpose) -> Vec<u8> {
+		let mut bits = Self::status_list_of(issuer, purpose).into_inner();
+		bits.resize(StatusListLength::<T>::get() as usize, 0);
+		bits
+	}
+}
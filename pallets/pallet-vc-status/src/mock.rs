@@ -0,0 +1,142 @@
+use crate as pallet_vc_status;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64},
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// Amount reserved for each anchored credential.
+pub const ANCHOR_DEPOSIT: u64 = 10;
+/// Free balance of the endowed accounts.
+pub const ENDOWMENT: u64 = 100;
+
+pub const ISSUER: u64 = 1;
+pub const DELEGATE: u64 = 2;
+pub const OTHER: u64 = 3;
+/// Account holding less than the anchor deposit.
+pub const POOR: u64 = 4;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Timestamp: pallet_timestamp,
+		Did: pallet_did,
+		VcStatus: pallet_vc_status,
+	}
+);
+
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const PermissionDelegateTypes: &'static [&'static [u8]] =
+		&[pallet_vc_status::DELEGATE_CREDENTIAL_ISSUER];
+}
+
+impl pallet_did::Config for Test {
+	type Event = Event;
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+	type Time = Timestamp;
+	type Currency = Balances;
+	type DepositPerItem = ConstU64<0>;
+	type DepositPerByte = ConstU64<0>;
+	type MaxDelegateTypeLength = ConstU32<64>;
+	type MaxNameLength = ConstU32<64>;
+	type MaxValueLength = ConstU32<128>;
+	type MaxOperations = ConstU32<4>;
+	type PruningGracePeriod = ConstU64<10>;
+	type MaxGuardians = ConstU32<4>;
+	type DelegatePermissions = ();
+	type PermissionDelegateTypes = PermissionDelegateTypes;
+	type MaxControllers = ConstU32<4>;
+	type OuterOrigin = Origin;
+	type Proposal = Call;
+	type MaxHandleLength = ConstU32<32>;
+	type HandlePeriod = ConstU64<100>;
+	type OnDeactivate = ();
+	type WeightInfo = ();
+}
+
+impl pallet_vc_status::Config for Test {
+	type Event = Event;
+	// Two bytes long status lists.
+	type MaxCredentials = ConstU32<16>;
+	type AnchorDeposit = ConstU64<ANCHOR_DEPOSIT>;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ISSUER, ENDOWMENT),
+			(DELEGATE, ENDOWMENT),
+			(OTHER, ENDOWMENT),
+			(POOR, ANCHOR_DEPOSIT - 1),
+		],
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = storage.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,222 @@
+use crate::{mock::*, types::*, CredentialCount, Error, Event as VcStatusEvent};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+use sp_core::H256;
+
+fn credential(n: u8) -> H256 {
+	H256::repeat_byte(n)
+}
+
+fn anchor(who: u64, n: u8) -> H256 {
+	assert_ok!(VcStatus::anchor_credential(Origin::signed(who), ISSUER, credential(n)));
+	credential(n)
+}
+
+fn status(n: u8) -> CredentialStatus {
+	VcStatus::credential_of(ISSUER, credential(n)).unwrap().status
+}
+
+#[test]
+fn anchor_credential_assigns_the_next_index_and_reserves_a_deposit() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		anchor(ISSUER, 2);
+
+		let record = VcStatus::credential_of(ISSUER, credential(2)).unwrap();
+		assert_eq!(record.index, 1);
+		assert_eq!(record.status, CredentialStatus::Active);
+		assert_eq!(record.anchored_by, ISSUER);
+		assert_eq!(VcStatus::credential_count(ISSUER), 2);
+		assert_eq!(VcStatus::anchor_deposit_of(ISSUER, credential(2)), ANCHOR_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(ISSUER), 2 * ANCHOR_DEPOSIT);
+		System::assert_last_event(
+			VcStatusEvent::CredentialAnchored(ISSUER, credential(2), 1, ISSUER).into(),
+		);
+	});
+}
+
+#[test]
+fn credential_issuer_delegate_anchors_and_pays_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::add_delegate(
+			Origin::signed(ISSUER),
+			ISSUER,
+			DELEGATE,
+			crate::DELEGATE_CREDENTIAL_ISSUER.to_vec(),
+			None
+		));
+		anchor(DELEGATE, 1);
+
+		assert_eq!(VcStatus::credential_of(ISSUER, credential(1)).unwrap().anchored_by, DELEGATE);
+		assert_eq!(Balances::reserved_balance(DELEGATE), ANCHOR_DEPOSIT);
+	});
+}
+
+#[test]
+fn anchor_credential_rejects_other_accounts() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VcStatus::anchor_credential(Origin::signed(OTHER), ISSUER, credential(1)),
+			Error::<Test>::NotIssuer
+		);
+	});
+}
+
+#[test]
+fn anchor_credential_requires_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VcStatus::anchor_credential(Origin::signed(POOR), POOR, credential(1)),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn anchor_credential_rejects_duplicates() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		assert_noop!(
+			VcStatus::anchor_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::AlreadyAnchored
+		);
+	});
+}
+
+#[test]
+fn anchor_credential_fails_when_the_status_list_is_full() {
+	new_test_ext().execute_with(|| {
+		CredentialCount::<Test>::insert(ISSUER, 15);
+		anchor(ISSUER, 1);
+		assert_noop!(
+			VcStatus::anchor_credential(Origin::signed(ISSUER), ISSUER, credential(2)),
+			Error::<Test>::StatusListFull
+		);
+	});
+}
+
+#[test]
+fn revoke_credential_sets_its_bit_for_good() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		anchor(ISSUER, 2);
+		assert_ok!(VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(2)));
+
+		assert_eq!(status(2), CredentialStatus::Revoked);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Revocation), vec![0x40, 0]);
+		assert_noop!(
+			VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(2)),
+			Error::<Test>::CredentialRevoked
+		);
+		assert_noop!(
+			VcStatus::suspend_credential(Origin::signed(ISSUER), ISSUER, credential(2)),
+			Error::<Test>::CredentialRevoked
+		);
+	});
+}
+
+#[test]
+fn revoke_credential_at_the_last_index_fills_the_status_list() {
+	new_test_ext().execute_with(|| {
+		CredentialCount::<Test>::insert(ISSUER, 15);
+		anchor(ISSUER, 1);
+		assert_ok!(VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+
+		assert_eq!(VcStatus::status_list_of(ISSUER, StatusPurpose::Revocation).to_vec(), [0, 1]);
+	});
+}
+
+#[test]
+fn suspend_and_reinstate_credential_toggle_its_bit() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		assert_noop!(
+			VcStatus::reinstate_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::NotSuspended
+		);
+
+		assert_ok!(VcStatus::suspend_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+		assert_eq!(status(1), CredentialStatus::Suspended);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Suspension), vec![0x80, 0]);
+		assert_noop!(
+			VcStatus::suspend_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::AlreadySuspended
+		);
+
+		assert_ok!(VcStatus::reinstate_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+		assert_eq!(status(1), CredentialStatus::Active);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Suspension), vec![0, 0]);
+	});
+}
+
+#[test]
+fn revoke_credential_clears_its_suspension_bit() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		anchor(ISSUER, 2);
+		assert_ok!(VcStatus::suspend_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+		assert_ok!(VcStatus::suspend_credential(Origin::signed(ISSUER), ISSUER, credential(2)));
+		assert_ok!(VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+
+		assert_eq!(status(1), CredentialStatus::Revoked);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Revocation), vec![0x80, 0]);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Suspension), vec![0x40, 0]);
+		assert_noop!(
+			VcStatus::reinstate_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::CredentialRevoked
+		);
+	});
+}
+
+#[test]
+fn status_changes_require_a_known_credential() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::UnknownCredential
+		);
+	});
+}
+
+#[test]
+fn remove_credential_releases_the_deposit_of_a_revoked_credential() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::add_delegate(
+			Origin::signed(ISSUER),
+			ISSUER,
+			DELEGATE,
+			crate::DELEGATE_CREDENTIAL_ISSUER.to_vec(),
+			None
+		));
+		anchor(DELEGATE, 1);
+		assert_noop!(
+			VcStatus::remove_credential(Origin::signed(ISSUER), ISSUER, credential(1)),
+			Error::<Test>::NotRevoked
+		);
+
+		assert_ok!(VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+		assert_ok!(VcStatus::remove_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+
+		assert!(VcStatus::credential_of(ISSUER, credential(1)).is_none());
+		assert_eq!(VcStatus::anchor_deposit_of(ISSUER, credential(1)), 0);
+		// The deposit returns to the delegate who anchored the credential.
+		assert_eq!(Balances::reserved_balance(DELEGATE), 0);
+		// The index is not reused and the credential stays revoked for verifiers.
+		assert_eq!(VcStatus::credential_count(ISSUER), 1);
+		assert_eq!(VcStatus::status_list(&ISSUER, StatusPurpose::Revocation), vec![0x80, 0]);
+		System::assert_last_event(
+			VcStatusEvent::CredentialRemoved(ISSUER, credential(1), ISSUER).into(),
+		);
+	});
+}
+
+#[test]
+fn remove_credential_rejects_other_accounts() {
+	new_test_ext().execute_with(|| {
+		anchor(ISSUER, 1);
+		assert_ok!(VcStatus::revoke_credential(Origin::signed(ISSUER), ISSUER, credential(1)));
+		assert_noop!(
+			VcStatus::remove_credential(Origin::signed(OTHER), ISSUER, credential(1)),
+			Error::<Test>::NotIssuer
+		);
+	});
+}
@@ -0,0 +1,57 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::RuntimeDebug;
+
+/// Purpose of a status list, as the `statusPurpose` of a StatusList2021 credential.
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum StatusPurpose {
+	/// A set bit marks a credential as revoked for good.
+	Revocation,
+	/// A set bit marks a credential as suspended until it is reinstated.
+	Suspension,
+}
+
+/// Current status of an anchored credential.
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum CredentialStatus {
+	Active,
+	Suspended,
+	Revoked,
+}
+
+/// Credential anchored by an issuer identity.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CredentialRecord<AccountId, BlockNumber> {
+	/// Position of the credential in the status lists of its issuer.
+	pub index: u32,
+	pub status: CredentialStatus,
+	/// Issuer or delegate who anchored the credential.
+	pub anchored_by: AccountId,
+	pub anchored: BlockNumber,
+	/// Block of the last status change.
+	pub updated: BlockNumber,
+}
@@ -0,0 +1,151 @@
+//! Weights for pallet_vc_status
+//!
+//! These weights were not measured. They are conservative estimates: round execution times,
+//! allowing for status lists of `MaxCredentials` bits, and the storage reads and writes of the
+//! most expensive path of each extrinsic. Replace this file with the output of the benchmarks
+//! on reference hardware:
+//!
+//! ./target/release/node-template benchmark pallet --chain=dev --steps=50 --repeat=20
+//!   --pallet=pallet_vc_status --extrinsic=* --execution=wasm --wasm-execution=compiled
+//!   --heap-pages=4096 --output=./pallets/pallet-vc-status/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_vc_status.
+pub trait WeightInfo {
+	fn anchor_credential() -> Weight;
+	fn revoke_credential() -> Weight;
+	fn suspend_credential() -> Weight;
+	fn reinstate_credential() -> Weight;
+	fn remove_credential() -> Weight;
+}
+
+/// Weights for pallet_vc_status using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus CredentialCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PalletVcStatus AnchorDepositOf (r:0 w:1)
+	fn anchor_credential() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:2 w:2)
+	fn revoke_credential() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:1 w:1)
+	fn suspend_credential() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:1 w:1)
+	fn reinstate_credential() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus AnchorDepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_credential() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus CredentialCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PalletVcStatus AnchorDepositOf (r:0 w:1)
+	fn anchor_credential() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:2 w:2)
+	fn revoke_credential() -> Weight {
+		(100_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:1 w:1)
+	fn suspend_credential() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus StatusListOf (r:1 w:1)
+	fn reinstate_credential() -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PalletDid DelegateOf (r:1 w:0)
+	// Storage: PalletDid DeactivatedAt (r:1 w:0)
+	// Storage: PalletDid ControllersOf (r:1 w:0)
+	// Storage: PalletDid OwnerOf (r:1 w:0)
+	// Storage: PalletVcStatus CredentialOf (r:1 w:1)
+	// Storage: PalletVcStatus AnchorDepositOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_credential() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+}
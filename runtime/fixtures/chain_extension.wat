@@ -0,0 +1,58 @@
+;; A policy which calls the chain extension with the `func_id` and payload of its input.
+;; It returns the output of the chain extension, or nothing if the call failed.
+(module
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\00\01")
+
+	;; [4, 8) func_id of the input
+
+	;; [8, 260) payload of the input
+
+	;; [260, 264) length of the output buffer
+	(data (i32.const 260) "\00\01")
+
+	;; [264, 520) output
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(local $status i32)
+		(call $seal_input
+			(i32.const 4)	;; Pointer to the input buffer
+			(i32.const 0)	;; Pointer to the length of the input buffer
+		)
+
+		(local.set $status
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))	;; func_id
+				(i32.const 8)	;; Pointer to the payload
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))	;; Length of the payload
+				(i32.const 264)	;; Pointer to the output buffer
+				(i32.const 260)	;; Pointer to the length of the output buffer
+			)
+		)
+
+		(if (local.get $status)
+			(then
+				(call $seal_return
+					(i32.const 0)	;; Return flags
+					(i32.const 264)	;; Pointer to the output
+					(i32.const 0)	;; Length of the output
+				)
+			)
+		)
+		(call $seal_return
+			(i32.const 0)	;; Return flags
+			(i32.const 264)	;; Pointer to the output
+			(i32.load (i32.const 260))	;; Length of the output
+		)
+		(unreachable)
+	)
+)
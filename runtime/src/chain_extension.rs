@@ -1,14 +1,17 @@
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::sp_std::vec::Vec;
+use frame_support::{sp_std::vec::Vec, traits::Get, weights::Weight};
 use sp_runtime::DispatchError;
 use pallet_contracts::chain_extension::{
 	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
 };
 use pallet_abac::{
 	self,
+	AttrName,
 	Moment,
-	types::Endorsement,
+	WeightInfo as AbacWeightInfo,
+	types::{Endorsement, TypedValue},
 };
+use pallet_did::WeightInfo as DidWeightInfo;
 
 pub const MAX_STRING_LENGTH: usize = 64; // limitation of both key and value of attributes.
 
@@ -19,6 +22,8 @@ pub struct AbacChainExtension;
 enum FuncId {
 	ReadAttributeValue,
 	CheckValidEndorsement,
+	LookupHandle,
+	ReadTypedAttributeValue,
 }
 
 impl TryFrom<u16> for FuncId {
@@ -28,6 +33,8 @@ impl TryFrom<u16> for FuncId {
 		let id = match func_id {
 			0x0001 => Self::ReadAttributeValue,
 			0x0002 => Self::CheckValidEndorsement,
+			0x0003 => Self::LookupHandle,
+			0x0004 => Self::ReadTypedAttributeValue,
 			_ => {
 				log::error!("Called an unregistered `func_id`: {}", func_id);
 				return Err(DispatchError::Other("Unimplemented func_id"));
@@ -50,6 +57,11 @@ struct CheckEndorsementInput<AccountId> {
 	endorsers: Vec<AccountId>,
 }
 
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct LookupHandleInput {
+	handle: [u8; MAX_STRING_LENGTH],
+}
+
 fn convert_slice_u8_to_vec_u8(input: &[u8; MAX_STRING_LENGTH]) -> Vec<u8> {
 	let mut result = Vec::<u8>::with_capacity(MAX_STRING_LENGTH);
 	for &c in input.iter() {
@@ -80,9 +92,13 @@ where
 	log::debug!("read_access_control_attribute_value key = {:?}", key);
 
 	// Query the storage of pallet_abac with the above inputs.
-	let attr_value = 	match <pallet_abac::Pallet<T>>::attr_of(identity, key) {
-		Some(v) => v.value,
-		None => Vec::<u8>::new()
+	// A key longer than the limit can never have been stored.
+	let attr_value = match AttrName::<T>::try_from(key) {
+		Ok(key) => match <pallet_abac::Pallet<T>>::attr_of(identity, key) {
+			Some(v) => v.value.into_inner(),
+			None => Vec::<u8>::new(),
+		},
+		Err(_) => Vec::<u8>::new(),
 	};
 	log::debug!("read_access_control_attribute_value attr_value = {:?}", attr_value);
 
@@ -115,11 +131,15 @@ where
 	let endorsers = input.endorsers;
 
 	let mut valid = false;
+	// A key longer than the limit can never have been endorsed.
+	let key = AttrName::<T>::try_from(key);
 	let now_block_number = <frame_system::Pallet<T>>::block_number(); // used to check validity of an endorsement.
 	// Query the storage of pallet_abac with each endorser listed in the input.
 	for endorser in endorsers.iter() {
-		let endorsement: Option<Endorsement<T::BlockNumber, Moment<T>>> =
-			<pallet_abac::Pallet<T>>::endorsement_of((&identity, &key, endorser));
+		let endorsement: Option<Endorsement<T::BlockNumber, Moment<T>>> = match &key {
+			Ok(key) => <pallet_abac::Pallet<T>>::endorsement_of((&identity, key, endorser)),
+			Err(_) => None,
+		};
 		if let Some(e) = endorsement {
 			if e.validity > now_block_number {
 				valid = true;
@@ -138,6 +158,73 @@ where
 	Ok(RetVal::Converging(0))
 }
 
+fn lookup_subject_by_handle<T, E>(env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+where
+	E: Ext<T = T>,
+	T: pallet_contracts::Config + pallet_abac::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	// Parse input parameters from function call on the policy smart-contract.
+	let mut env = env.buf_in_buf_out();
+	log::debug!("lookup_subject_by_handle env.in_len: {:?}", env.in_len());
+	// Charge the lookup of the handle record before touching the storage.
+	env.charge_weight(<T as pallet_did::Config>::WeightInfo::handle_identity())?;
+	let input: LookupHandleInput = env.read_as()?;
+
+	// Convert handle of rust slice [] to handle of rust Vec<u8>.
+	let handle = convert_slice_u8_to_vec_u8(&input.handle);
+	log::debug!("lookup_subject_by_handle handle = {:?}", handle);
+
+	// Query the handle registry of pallet_did, expired handles point to no subject.
+	let subject: Option<T::AccountId> = <pallet_did::Pallet<T>>::handle_identity(&handle);
+	log::debug!("lookup_subject_by_handle subject = {:?}", subject);
+
+	// Return the result to function on the policy smart-contract.
+	let return_slice = subject.encode();
+	env.write(&return_slice, false, None)
+		.map_err(|_| DispatchError::Other("AbacChainExtension failed to look up handle"))?;
+
+	// Return a status code of successful status.
+	Ok(RetVal::Converging(0))
+}
+
+fn read_typed_attribute_value<T, E>(env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+where
+	E: Ext<T = T>,
+	T: pallet_contracts::Config + pallet_abac::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	// Parse input parameters from function call on the policy smart-contract.
+	let mut env = env.buf_in_buf_out();
+	log::debug!("read_typed_attribute_value env.in_len: {:?}", env.in_len());
+	// Charge the reads of the attribute and its schema before touching the storage.
+	env.charge_weight(<T as pallet_abac::Config>::WeightInfo::typed_attr())?;
+	let input: ReadAttrInput<T::AccountId> = env.read_as()?;
+
+	let identity = input.identity;
+	// Convert attribute key of rust slice [] to attribute key of rust Vec<u8>.
+	let key = convert_slice_u8_to_vec_u8(&input.attr_name);
+	log::debug!("read_typed_attribute_value key = {:?}", key);
+
+	// Query the storage of pallet_abac, the value is typed by the schema of the attribute.
+	let typed_value: Option<TypedValue<T::AccountId>> = match AttrName::<T>::try_from(key) {
+		Ok(key) => <pallet_abac::Pallet<T>>::typed_attr(&identity, &key),
+		Err(_) => None,
+	};
+	log::debug!("read_typed_attribute_value typed_value = {:?}", typed_value);
+
+	// Return the result to function on the policy smart-contract, charging the decoded bytes.
+	let return_slice = typed_value.encode();
+	let schedule = <T as pallet_contracts::Config>::Schedule::get();
+	let per_byte = schedule.host_fn_weights.get_storage_per_byte;
+	env.charge_weight(per_byte.saturating_mul(return_slice.len() as Weight))?;
+	env.write(&return_slice, false, None)
+		.map_err(|_| DispatchError::Other("AbacChainExtension failed to read typed attribute"))?;
+
+	// Return a status code of successful status.
+	Ok(RetVal::Converging(0))
+}
+
 impl<T> ChainExtension<T> for AbacChainExtension
 where
 	T: pallet_contracts::Config + pallet_abac::Config,
@@ -160,7 +247,123 @@ where
 		match func_id {
 			FuncId::ReadAttributeValue => read_access_control_attribute_value::<T, E>(env),
 			FuncId::CheckValidEndorsement => check_attribute_had_valid_endorsement::<T, E>(env),
+			FuncId::LookupHandle => lookup_subject_by_handle::<T, E>(env),
+			FuncId::ReadTypedAttributeValue => read_typed_attribute_value::<T, E>(env),
 			// _ => Ok(RetVal::Converging(1)),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AccountId, Contracts, Origin, PalletAbac, PalletDid, Runtime, System, DOLLARS};
+	use frame_support::{assert_ok, sp_io};
+	use pallet_abac::types::{AttrInput, AttrType};
+	use pallet_contracts_primitives::Code;
+	use sp_runtime::AccountId32;
+
+	const ALICE: AccountId = AccountId32::new([1u8; 32]);
+	const GAS_LIMIT: Weight = 100_000_000_000;
+	const LOOKUP_HANDLE: u32 = 0x0003;
+	const READ_TYPED_ATTRIBUTE_VALUE: u32 = 0x0004;
+	const UNREGISTERED: u32 = 0x0009;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(ALICE, 1_000 * DOLLARS)] }
+			.assimilate_storage(&mut t)
+			.unwrap();
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	/// Instantiates `fixtures/chain_extension.wat`, which forwards its input to the extension.
+	fn deploy_policy() -> AccountId {
+		let wasm = wat::parse_file("fixtures/chain_extension.wat").unwrap();
+		Contracts::bare_instantiate(
+			ALICE,
+			0,
+			GAS_LIMIT,
+			None,
+			Code::Upload(wasm),
+			Vec::new(),
+			Vec::new(),
+			false,
+		)
+		.result
+		.unwrap()
+		.account_id
+	}
+
+	/// Calls a function of the extension through the policy, returns its output and gas.
+	fn call(policy: &AccountId, func_id: u32, input: &impl Encode) -> (Vec<u8>, Weight) {
+		let mut data = func_id.encode();
+		input.encode_to(&mut data);
+		let result = Contracts::bare_call(ALICE, policy.clone(), 0, GAS_LIMIT, None, data, false);
+		(result.result.unwrap().data.to_vec(), result.gas_consumed)
+	}
+
+	fn padded(name: &[u8]) -> [u8; MAX_STRING_LENGTH] {
+		let mut padded = [0u8; MAX_STRING_LENGTH];
+		padded[..name.len()].copy_from_slice(name);
+		padded
+	}
+
+	#[test]
+	fn lookup_handle_returns_the_subject_and_charges_its_weight() {
+		new_test_ext().execute_with(|| {
+			let policy = deploy_policy();
+			let input = LookupHandleInput { handle: padded(b"alice") };
+
+			let (output, _) = call(&policy, LOOKUP_HANDLE, &input);
+			assert_eq!(Option::<AccountId>::decode(&mut &output[..]).unwrap(), None);
+
+			assert_ok!(PalletDid::register_handle(Origin::signed(ALICE), ALICE, b"alice".to_vec()));
+			let (output, gas) = call(&policy, LOOKUP_HANDLE, &input);
+			assert_eq!(Option::<AccountId>::decode(&mut &output[..]).unwrap(), Some(ALICE));
+
+			let (output, unregistered_gas) = call(&policy, UNREGISTERED, &input);
+			assert!(output.is_empty());
+			assert!(
+				gas - unregistered_gas >=
+					<Runtime as pallet_did::Config>::WeightInfo::handle_identity()
+			);
+		});
+	}
+
+	#[test]
+	fn read_typed_attribute_value_returns_the_typed_value_and_charges_its_weight() {
+		new_test_ext().execute_with(|| {
+			let policy = deploy_policy();
+			let input = ReadAttrInput { identity: ALICE, attr_name: padded(b"age") };
+
+			let (output, _) = call(&policy, READ_TYPED_ATTRIBUTE_VALUE, &input);
+			let typed_value = Option::<TypedValue<AccountId>>::decode(&mut &output[..]).unwrap();
+			assert_eq!(typed_value, None);
+
+			assert_ok!(PalletAbac::set_attr_schema(
+				Origin::root(),
+				b"age".to_vec().try_into().unwrap(),
+				AttrType::U64,
+				Default::default(),
+			));
+			assert_ok!(PalletAbac::set_attributes(
+				Origin::signed(ALICE),
+				ALICE,
+				vec![AttrInput { name: b"age".to_vec(), value: 42u64.encode() }],
+			));
+			let (output, gas) = call(&policy, READ_TYPED_ATTRIBUTE_VALUE, &input);
+			let typed_value = Option::<TypedValue<AccountId>>::decode(&mut &output[..]).unwrap();
+			assert_eq!(typed_value, Some(TypedValue::U64(42)));
+
+			let (output, unregistered_gas) = call(&policy, UNREGISTERED, &input);
+			assert!(output.is_empty());
+			assert!(
+				gas - unregistered_gas >=
+					<Runtime as pallet_abac::Config>::WeightInfo::typed_attr()
+			);
+		});
+	}
+}
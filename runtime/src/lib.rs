@@ -57,6 +57,9 @@ pub use pallet_abac;
 /// Import the pallet did.
 pub use pallet_did;
 
+/// Import the pallet vc status.
+pub use pallet_vc_status;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -76,6 +79,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp, in milliseconds.
+pub type Moment = u64;
+
 /// Opaque types. These are used by the CLI to instantiate machinery that don't need to know
 /// the specifics of the runtime. They can then be made to be agnostic over specific formats
 /// of data like extrinsics, allowing for them to continue syncing the network through upgrades
@@ -112,10 +118,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -282,7 +288,7 @@ impl pallet_grandpa::Config for Runtime {
 
 impl pallet_timestamp::Config for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
 	type WeightInfo = ();
@@ -331,10 +337,36 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	// The 8 policies of an object evaluated at most take a fifth of the block.
+	pub const PolicyGasLimit: Weight = WEIGHT_PER_SECOND / 20;
+}
+
 /// Configure the pallet_abac.
 impl pallet_abac::Config for Runtime {
 	type Event = Event;
 	type Time = Timestamp;
+	type MaxEndorsements = ConstU32<16>;
+	type MaxAttributesPerCall = ConstU32<64>;
+	type MaxAttrNameLength = ConstU32<64>;
+	type MaxAttrValueLength = ConstU32<64>;
+	type MaxPolicyNameLength = ConstU32<64>;
+	type MaxPolicies = ConstU32<8>;
+	type PolicyGasLimit = PolicyGasLimit;
+	type MaxContextLength = ConstU32<256>;
+	type MaxEnumVariants = ConstU32<16>;
+	type SchemaOrigin = frame_system::EnsureRoot<AccountId>;
+	type WeightInfo = pallet_abac::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub const DidDepositPerItem: Balance = deposit(1, 0);
+	pub const DidDepositPerByte: Balance = deposit(0, 1);
+	pub const DidPermissionDelegateTypes: &'static [&'static [u8]] = &[
+		pallet_did::DELEGATE_ATTRIBUTE_ADMIN,
+		pallet_abac::DELEGATE_POLICY_ADMIN,
+		pallet_vc_status::DELEGATE_CREDENTIAL_ISSUER,
+	];
 }
 
 /// Configure the pallet_did.
@@ -343,6 +375,35 @@ impl pallet_did::Config for Runtime {
 	type Public = MultiSigner;
 	type Signature = Signature;
 	type Time = Timestamp;
+	type Currency = Balances;
+	type DepositPerItem = DidDepositPerItem;
+	type DepositPerByte = DidDepositPerByte;
+	type MaxDelegateTypeLength = ConstU32<64>;
+	type MaxNameLength = ConstU32<64>;
+	type MaxValueLength = ConstU32<1024>;
+	type MaxOperations = ConstU32<16>;
+	type PruningGracePeriod = ConstU32<{ 7 * DAYS }>;
+	type MaxGuardians = ConstU32<10>;
+	type DelegatePermissions = pallet_did::AttributeAdmin;
+	type PermissionDelegateTypes = DidPermissionDelegateTypes;
+	type MaxControllers = ConstU32<10>;
+	type OuterOrigin = Origin;
+	type Proposal = Call;
+	// Handles fit the strings passed to the chain extension.
+	type MaxHandleLength = ConstU32<{ chain_extension::MAX_STRING_LENGTH as u32 }>;
+	type HandlePeriod = ConstU32<{ 365 * DAYS }>;
+	type OnDeactivate = PalletAbac;
+	type WeightInfo = pallet_did::weights::SubstrateWeight<Runtime>;
+}
+
+/// Configure the pallet_vc_status.
+impl pallet_vc_status::Config for Runtime {
+	type Event = Event;
+	// 16KB status lists, the minimum recommended by StatusList2021 for herd privacy.
+	type MaxCredentials = ConstU32<131_072>;
+	// A credential record and its deposit entry, anchored by a 32 bytes account.
+	type AnchorDeposit = ConstU128<{ deposit(2, 45) }>;
+	type WeightInfo = pallet_vc_status::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -369,6 +430,8 @@ construct_runtime!(
 		PalletAbac: pallet_abac,
 		// Include the custom logic from the pallet-did in the runtime.
 		PalletDid: pallet_did,
+		// Include the credential status registry built on pallet-did in the runtime.
+		PalletVcStatus: pallet_vc_status,
 	}
 );
 
@@ -414,6 +477,9 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_did, PalletDid]
+		[pallet_abac, PalletAbac]
+		[pallet_vc_status, PalletVcStatus]
 	);
 }
 
@@ -564,7 +630,7 @@ impl_runtime_apis! {
 			TransactionPayment::query_call_fee_details(call, len)
 		}
 	}
-	
+
 	impl pallet_contracts_rpc_runtime_api::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>
 	for Runtime
 	{
@@ -578,7 +644,7 @@ impl_runtime_apis! {
 	 ) -> pallet_contracts_primitives::ContractExecResult<Balance> {
 			Contracts::bare_call(origin, dest, value, gas_limit, storage_deposit_limit, input_data, CONTRACTS_DEBUG_OUTPUT)
 	 }
-	 
+
 	 fn instantiate(
 			origin: AccountId,
 			value: Balance,
@@ -590,7 +656,7 @@ impl_runtime_apis! {
 	 ) -> pallet_contracts_primitives::ContractInstantiateResult<AccountId, Balance> {
 			Contracts::bare_instantiate(origin, value, gas_limit, storage_deposit_limit, code, data, salt, CONTRACTS_DEBUG_OUTPUT)
 			}
-			
+
 	 fn upload_code(
 			origin: AccountId,
 			code: Vec<u8>,
@@ -598,7 +664,7 @@ impl_runtime_apis! {
 	 ) -> pallet_contracts_primitives::CodeUploadResult<Hash, Balance> {
 			Contracts::bare_upload_code(origin, code, storage_deposit_limit)
 	 }
-	 
+
 	 fn get_storage(
 			address: AccountId,
 			key: Vec<u8>,
@@ -607,6 +673,79 @@ impl_runtime_apis! {
 			}
 	 }
 
+	impl pallet_did_rpc_runtime_api::DidApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn resolve(identity: AccountId) -> pallet_did::types::DidDocument<AccountId, BlockNumber> {
+			PalletDid::resolve(&identity)
+		}
+
+		fn signing_nonce(identity: AccountId) -> u64 {
+			PalletDid::signing_nonce_of(&identity)
+		}
+
+		fn attributes(
+			identity: AccountId,
+			include_expired: bool,
+		) -> Vec<pallet_did::types::AttributeRecord<BlockNumber, Moment>> {
+			PalletDid::attributes(&identity, include_expired)
+		}
+
+		fn delegates(
+			identity: AccountId,
+			delegate_type: Option<Vec<u8>>,
+		) -> Vec<pallet_did::types::DelegateRecord<AccountId, BlockNumber>> {
+			PalletDid::delegates(&identity, delegate_type)
+		}
+
+		fn document_version(
+			identity: AccountId,
+		) -> Option<pallet_did::types::DocumentVersion<BlockNumber, Moment>> {
+			PalletDid::document_version(&identity)
+		}
+
+		fn ethereum_account(address: sp_core::H160) -> AccountId {
+			PalletDid::ethereum_account(&address)
+		}
+
+		fn resolve_ethereum(
+			address: sp_core::H160,
+		) -> pallet_did::types::DidDocument<AccountId, BlockNumber> {
+			PalletDid::resolve_ethereum(&address)
+		}
+
+		fn resolve_handle(handle: Vec<u8>) -> Option<AccountId> {
+			PalletDid::handle_identity(&handle)
+		}
+	}
+
+	impl pallet_abac_rpc_runtime_api::AbacApi<Block, AccountId> for Runtime {
+		fn evaluate(
+			subject: AccountId,
+			object: AccountId,
+			action: Vec<u8>,
+			context: Vec<u8>,
+		) -> Result<pallet_abac::types::AccessDecision<AccountId>, sp_runtime::DispatchError> {
+			PalletAbac::evaluate(&subject, &object, &action, &context)
+		}
+	}
+
+	impl pallet_vc_status_runtime_api::VcStatusApi<Block, AccountId, Hash, BlockNumber>
+		for Runtime
+	{
+		fn status_list(
+			issuer: AccountId,
+			purpose: pallet_vc_status::types::StatusPurpose,
+		) -> Vec<u8> {
+			PalletVcStatus::status_list(&issuer, purpose)
+		}
+
+		fn credential(
+			issuer: AccountId,
+			credential: Hash,
+		) -> Option<pallet_vc_status::types::CredentialRecord<AccountId, BlockNumber>> {
+			PalletVcStatus::credential_of(&issuer, &credential)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (